@@ -0,0 +1,14 @@
+-- The previous version referenced a nonexistent column, so deleting a local user always failed
+CREATE OR REPLACE FUNCTION instance_stats_local_user_delete ()
+    RETURNS TRIGGER
+    LANGUAGE plpgsql
+    AS $$
+BEGIN
+    UPDATE
+        instance_stats
+    SET
+        users = users - 1;
+    RETURN NULL;
+END
+$$;
+
@@ -0,0 +1,8 @@
+DROP TABLE totp_recovery_code;
+
+ALTER TABLE local_user
+    DROP totp_secret;
+
+ALTER TABLE local_user
+    DROP totp_enabled;
+
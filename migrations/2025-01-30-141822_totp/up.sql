@@ -0,0 +1,12 @@
+ALTER TABLE local_user
+    ADD COLUMN totp_secret text;
+
+ALTER TABLE local_user
+    ADD COLUMN totp_enabled bool NOT NULL DEFAULT FALSE;
+
+CREATE TABLE totp_recovery_code (
+    id serial PRIMARY KEY,
+    local_user_id int REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    code_hash text NOT NULL
+);
+
@@ -0,0 +1,8 @@
+CREATE TABLE oidc_identity (
+    id serial PRIMARY KEY,
+    local_user_id int REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    provider text NOT NULL,
+    subject text NOT NULL,
+    UNIQUE (provider, subject)
+);
+
@@ -0,0 +1,13 @@
+CREATE TABLE api_token (
+    id serial PRIMARY KEY,
+    local_user_id int REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    name text NOT NULL,
+    token_hash text UNIQUE NOT NULL,
+    scope_read bool NOT NULL,
+    scope_edit bool NOT NULL,
+    scope_comment bool NOT NULL,
+    scope_admin bool NOT NULL,
+    published timestamptz NOT NULL DEFAULT now(),
+    last_used timestamptz
+);
+
@@ -0,0 +1,3 @@
+ALTER TABLE person
+    ADD COLUMN bot bool NOT NULL DEFAULT FALSE;
+
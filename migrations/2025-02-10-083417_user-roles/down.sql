@@ -0,0 +1,8 @@
+ALTER TABLE local_user
+    DROP COLUMN moderator,
+    DROP COLUMN perm_protect_article,
+    DROP COLUMN perm_approve_article,
+    DROP COLUMN perm_remove_comment,
+    DROP COLUMN perm_ban,
+    DROP COLUMN perm_manage_federation,
+    DROP COLUMN perm_manage_users;
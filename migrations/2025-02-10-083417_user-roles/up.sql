@@ -0,0 +1,8 @@
+ALTER TABLE local_user
+    ADD COLUMN moderator bool NOT NULL DEFAULT FALSE,
+    ADD COLUMN perm_protect_article bool NOT NULL DEFAULT FALSE,
+    ADD COLUMN perm_approve_article bool NOT NULL DEFAULT FALSE,
+    ADD COLUMN perm_remove_comment bool NOT NULL DEFAULT FALSE,
+    ADD COLUMN perm_ban bool NOT NULL DEFAULT FALSE,
+    ADD COLUMN perm_manage_federation bool NOT NULL DEFAULT FALSE,
+    ADD COLUMN perm_manage_users bool NOT NULL DEFAULT FALSE;
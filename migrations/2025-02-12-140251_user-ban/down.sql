@@ -0,0 +1,4 @@
+ALTER TABLE person
+    DROP COLUMN banned,
+    DROP COLUMN ban_reason,
+    DROP COLUMN ban_expires;
@@ -0,0 +1,4 @@
+ALTER TABLE person
+    ADD COLUMN banned bool NOT NULL DEFAULT FALSE,
+    ADD COLUMN ban_reason text,
+    ADD COLUMN ban_expires timestamptz;
@@ -0,0 +1,6 @@
+CREATE TABLE federation_list (
+    domain text PRIMARY KEY,
+    blocked bool NOT NULL,
+    reason text,
+    published timestamptz NOT NULL DEFAULT now()
+);
@@ -0,0 +1,13 @@
+CREATE TABLE modlog (
+    id serial PRIMARY KEY,
+    actor_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    action text NOT NULL,
+    target_article_id int REFERENCES article ON UPDATE CASCADE ON DELETE SET NULL,
+    target_person_id int REFERENCES person ON UPDATE CASCADE ON DELETE SET NULL,
+    target_instance_id int REFERENCES instance ON UPDATE CASCADE ON DELETE SET NULL,
+    target_name text NOT NULL,
+    reason text,
+    published timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_modlog_published ON modlog (published DESC);
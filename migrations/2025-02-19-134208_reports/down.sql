@@ -0,0 +1,4 @@
+DROP TABLE report;
+
+ALTER TABLE local_user
+    DROP COLUMN perm_handle_reports;
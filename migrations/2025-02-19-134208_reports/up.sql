@@ -0,0 +1,19 @@
+ALTER TABLE local_user
+    ADD COLUMN perm_handle_reports bool NOT NULL DEFAULT FALSE;
+
+CREATE TABLE report (
+    id serial PRIMARY KEY,
+    creator_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    article_id int REFERENCES article ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    edit_id int REFERENCES edit ON UPDATE CASCADE ON DELETE CASCADE,
+    comment_id int REFERENCES comment ON UPDATE CASCADE ON DELETE CASCADE,
+    reason text NOT NULL,
+    status text NOT NULL DEFAULT 'open',
+    resolver_id int REFERENCES person ON UPDATE CASCADE ON DELETE SET NULL,
+    ap_id varchar(255) UNIQUE NOT NULL,
+    local bool NOT NULL,
+    published timestamptz NOT NULL DEFAULT now(),
+    updated timestamptz
+);
+
+CREATE INDEX idx_report_status ON report (status);
@@ -0,0 +1,2 @@
+ALTER TABLE comment
+    ADD COLUMN removed bool NOT NULL DEFAULT FALSE;
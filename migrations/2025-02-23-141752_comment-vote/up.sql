@@ -0,0 +1,9 @@
+CREATE TABLE comment_vote (
+    person_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    comment_id int REFERENCES comment ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    score smallint NOT NULL CHECK (score IN (-1, 1)),
+    published timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (person_id, comment_id)
+);
+
+CREATE INDEX comment_vote_comment_idx ON comment_vote (comment_id);
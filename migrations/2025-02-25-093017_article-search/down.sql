@@ -0,0 +1,8 @@
+DROP TRIGGER article_search_update ON article;
+
+DROP TABLE article_search;
+
+DROP FUNCTION article_search_update, article_search_vector, article_search_config;
+
+ALTER TABLE article
+    DROP COLUMN language;
@@ -0,0 +1,90 @@
+-- ISO 639-1 code of the article language, used to select the text search configuration
+ALTER TABLE article
+    ADD COLUMN language text;
+
+-- Map language code to text search configuration. Unknown languages use `simple` which
+-- doesn't do any stemming.
+CREATE FUNCTION article_search_config (language text)
+    RETURNS regconfig
+    LANGUAGE sql
+    IMMUTABLE
+    AS $$
+    SELECT
+        CASE language
+        WHEN 'da' THEN
+            'danish'
+        WHEN 'de' THEN
+            'german'
+        WHEN 'en' THEN
+            'english'
+        WHEN 'es' THEN
+            'spanish'
+        WHEN 'fi' THEN
+            'finnish'
+        WHEN 'fr' THEN
+            'french'
+        WHEN 'hu' THEN
+            'hungarian'
+        WHEN 'it' THEN
+            'italian'
+        WHEN 'nl' THEN
+            'dutch'
+        WHEN 'no' THEN
+            'norwegian'
+        WHEN 'pt' THEN
+            'portuguese'
+        WHEN 'ro' THEN
+            'romanian'
+        WHEN 'ru' THEN
+            'russian'
+        WHEN 'sv' THEN
+            'swedish'
+        WHEN 'tr' THEN
+            'turkish'
+        ELSE
+            'simple'
+        END::regconfig
+$$;
+
+-- Title words are weighted higher than words in the text
+CREATE FUNCTION article_search_vector (title text, text text, language text)
+    RETURNS tsvector
+    LANGUAGE sql
+    IMMUTABLE
+    AS $$
+    SELECT
+        setweight(to_tsvector(article_search_config (language), replace(title, '_', ' ')), 'A') || setweight(to_tsvector(article_search_config (language), text), 'B')
+$$;
+
+CREATE TABLE article_search (
+    article_id int PRIMARY KEY REFERENCES article ON UPDATE CASCADE ON DELETE CASCADE,
+    search_vector tsvector NOT NULL
+);
+
+CREATE INDEX article_search_vector_idx ON article_search USING gin (search_vector);
+
+INSERT INTO article_search (article_id, search_vector)
+SELECT
+    id,
+    article_search_vector (title, text, language)
+FROM
+    article;
+
+CREATE FUNCTION article_search_update ()
+    RETURNS TRIGGER
+    LANGUAGE plpgsql
+    AS $$
+BEGIN
+    INSERT INTO article_search (article_id, search_vector)
+        VALUES (NEW.id, article_search_vector (NEW.title, NEW.text, NEW.language))
+    ON CONFLICT (article_id)
+        DO UPDATE SET
+            search_vector = excluded.search_vector;
+    RETURN NULL;
+END
+$$;
+
+CREATE TRIGGER article_search_update
+    AFTER INSERT OR UPDATE OF title, text, language ON article
+    FOR EACH ROW
+    EXECUTE PROCEDURE article_search_update ();
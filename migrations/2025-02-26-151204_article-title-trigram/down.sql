@@ -0,0 +1,3 @@
+DROP INDEX article_title_trgm_idx;
+
+DROP EXTENSION pg_trgm;
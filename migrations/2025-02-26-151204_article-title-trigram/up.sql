@@ -0,0 +1,4 @@
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+-- Used for prefix and similarity matches of article titles
+CREATE INDEX article_title_trgm_idx ON article USING gin (title gin_trgm_ops);
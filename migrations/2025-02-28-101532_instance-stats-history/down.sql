@@ -0,0 +1,5 @@
+DROP TRIGGER instance_stats_history_local_user_insert ON local_user;
+
+DROP FUNCTION instance_stats_history_local_user_insert, instance_stats_history_update;
+
+DROP TABLE instance_stats_history;
@@ -0,0 +1,97 @@
+-- Daily statistics of the local instance. Days are in UTC.
+CREATE TABLE instance_stats_history (
+    day date PRIMARY KEY,
+    edits int NOT NULL DEFAULT 0,
+    articles int NOT NULL DEFAULT 0,
+    new_users int NOT NULL DEFAULT 0,
+    comments int NOT NULL DEFAULT 0,
+    activities_received int NOT NULL DEFAULT 0,
+    activities_sent int NOT NULL DEFAULT 0,
+    active_editors int NOT NULL DEFAULT 0
+);
+
+-- Person has no published column, so new users are counted when they are created
+CREATE FUNCTION instance_stats_history_local_user_insert ()
+    RETURNS TRIGGER
+    LANGUAGE plpgsql
+    AS $$
+BEGIN
+    INSERT INTO instance_stats_history (day, new_users)
+        VALUES ((now() AT TIME ZONE 'UTC')::date, 1)
+    ON CONFLICT (day)
+        DO UPDATE SET
+            new_users = instance_stats_history.new_users + 1;
+    RETURN NULL;
+END
+$$;
+
+CREATE TRIGGER instance_stats_history_local_user_insert
+    AFTER INSERT ON local_user
+    FOR EACH ROW
+    EXECUTE PROCEDURE instance_stats_history_local_user_insert ();
+
+-- Count edits, articles, comments and active editors of the given day. Called regularly by
+-- scheduled tasks.
+CREATE FUNCTION instance_stats_history_update (day_ date)
+    RETURNS void
+    LANGUAGE plpgsql
+    AS $$
+BEGIN
+    INSERT INTO instance_stats_history (day, edits, articles, comments, active_editors)
+    SELECT
+        day_,
+        (
+            SELECT
+                count(*)
+            FROM
+                edit e
+                INNER JOIN person p ON e.creator_id = p.id
+            WHERE
+                p.local = TRUE
+                AND e.pending = FALSE
+                AND (e.published AT TIME ZONE 'UTC')::date = day_),
+        (
+            SELECT
+                count(*)
+            FROM
+                article a
+            WHERE
+                a.local = TRUE
+                AND (a.published AT TIME ZONE 'UTC')::date = day_),
+        (
+            SELECT
+                count(*)
+            FROM
+                comment c
+            WHERE
+                c.local = TRUE
+                AND c.deleted = FALSE
+                AND c.removed = FALSE
+                AND (c.published AT TIME ZONE 'UTC')::date = day_),
+        (
+            SELECT
+                count(DISTINCT e.creator_id)
+            FROM
+                edit e
+                INNER JOIN person p ON e.creator_id = p.id
+            WHERE
+                p.local = TRUE
+                AND e.pending = FALSE
+                AND (e.published AT TIME ZONE 'UTC')::date = day_)
+    ON CONFLICT (day)
+        DO UPDATE SET
+            edits = excluded.edits,
+            articles = excluded.articles,
+            comments = excluded.comments,
+            active_editors = excluded.active_editors;
+END
+$$;
+
+-- Fill in history from existing content. New users and activities are not known for past days.
+SELECT
+    instance_stats_history_update (day::date)
+FROM
+    generate_series((
+        SELECT
+            min(published AT TIME ZONE 'UTC')::date
+        FROM edit), (now() AT TIME ZONE 'UTC')::date, '1 day') AS day;
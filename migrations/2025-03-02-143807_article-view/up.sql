@@ -0,0 +1,9 @@
+-- Number of times an article was viewed per day (UTC). No information about the reader is stored.
+CREATE TABLE article_view (
+    article_id int REFERENCES article ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    day date NOT NULL,
+    views int NOT NULL DEFAULT 0,
+    PRIMARY KEY (article_id, day)
+);
+
+CREATE INDEX article_view_day_idx ON article_view (day);
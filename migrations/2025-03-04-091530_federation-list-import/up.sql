@@ -0,0 +1,6 @@
+-- Single row which is inserted once the federation lists from the config file were imported,
+-- so that entries removed by admins don't come back after a restart.
+CREATE TABLE federation_list_import (
+    id int PRIMARY KEY DEFAULT 1 CHECK (id = 1),
+    published timestamptz NOT NULL DEFAULT now()
+);
@@ -0,0 +1,8 @@
+-- Last time step for which a TOTP token was accepted, so that tokens can't be reused. Attempts
+-- are counted to limit guessing.
+CREATE TABLE totp_usage (
+    local_user_id int PRIMARY KEY REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE,
+    last_step bigint,
+    attempts int NOT NULL DEFAULT 0,
+    attempts_start timestamptz NOT NULL DEFAULT now()
+);
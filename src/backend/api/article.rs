@@ -1,23 +1,30 @@
-use super::check_is_admin;
+use super::{
+    authorization::{check_can_edit_article, check_permission},
+    empty_to_none,
+};
 use crate::{
     backend::{
         database::{
             article::DbArticleForm,
             conflict::{DbConflict, DbConflictForm},
             edit::DbEditForm,
+            modlog::DbModlogForm,
             IbisContext,
         },
         federation::activities::{create_article::CreateArticle, submit_article_update},
         utils::{
             error::MyResult,
             generate_article_version,
-            validate::{validate_article_title, validate_not_empty},
+            is_bot_user_agent,
+            pagination::Pagination,
+            validate::{validate_article_language, validate_article_title, validate_not_empty},
         },
     },
     common::{
         article::{
             ApiConflict,
             ApproveArticleParams,
+            ArticleSuggestion,
             CreateArticleParams,
             DbArticle,
             DbArticleView,
@@ -29,22 +36,35 @@ use crate::{
             GetArticleParams,
             ListArticlesParams,
             ProtectArticleParams,
+            RemoteSearchResult,
             SearchArticleParams,
+            SearchArticleResponse,
+            SearchArticleResult,
+            SearchSuggestParams,
         },
-        comment::DbComment,
         instance::DbInstance,
-        user::LocalUserView,
+        modlog::{DbModlog, ModlogAction},
+        pagination::Paginated,
+        user::{DbPerson, LocalUserView, Permission},
         utils::{extract_domain, http_protocol_str},
-        validation::can_edit_article,
         ResolveObjectParams,
     },
 };
 use activitypub_federation::{config::Data, fetch::object_id::ObjectId};
 use anyhow::anyhow;
-use axum::{extract::Query, Extension, Form, Json};
+use axum::{
+    extract::Query,
+    http::{header::USER_AGENT, HeaderMap},
+    Extension,
+    Form,
+    Json,
+};
 use axum_macros::debug_handler;
 use chrono::Utc;
 use diffy::create_patch;
+use futures::future::join_all;
+use log::warn;
+use std::{collections::HashSet, time::Duration};
 
 /// Create a new article with empty text, and federate it to followers.
 #[debug_handler]
@@ -55,6 +75,7 @@ pub(in crate::backend::api) async fn create_article(
 ) -> MyResult<Json<DbArticleView>> {
     params.title = validate_article_title(&params.title)?;
     validate_not_empty(&params.text)?;
+    let language = validate_article_language(params.language)?;
 
     let local_instance = DbInstance::read_local(&context)?;
     let ap_id = ObjectId::parse(&format!(
@@ -71,6 +92,7 @@ pub(in crate::backend::api) async fn create_article(
         local: true,
         protected: false,
         approved: !context.config.options.article_approval,
+        language,
     };
     let article = DbArticle::create(form, &context)?;
 
@@ -118,7 +140,7 @@ pub(in crate::backend::api) async fn edit_article(
     if params.summary.is_empty() {
         return Err(anyhow!("No summary given").into());
     }
-    can_edit_article(&original_article.article, user.local_user.admin)?;
+    check_can_edit_article(&user, &original_article.article, &context)?;
     // ensure trailing newline for clean diffs
     if !params.new_text.ends_with('\n') {
         params.new_text.push('\n');
@@ -167,36 +189,49 @@ pub(in crate::backend::api) async fn edit_article(
 /// Retrieve an article by ID. It must already be stored in the local database.
 #[debug_handler]
 pub(in crate::backend::api) async fn get_article(
+    user: Option<Extension<LocalUserView>>,
     Query(query): Query<GetArticleParams>,
+    headers: HeaderMap,
     context: Data<IbisContext>,
 ) -> MyResult<Json<DbArticleView>> {
-    match (query.title, query.id) {
-        (Some(title), None) => Ok(Json(DbArticle::read_view_title(
-            &title,
-            query.domain,
-            &context,
-        )?)),
+    let article = match (query.title, query.id) {
+        (Some(title), None) => DbArticle::read_view_title(&title, query.domain, &context)?,
         (None, Some(id)) => {
             if query.domain.is_some() {
                 return Err(anyhow!("Cant combine id and instance_domain").into());
             }
-            let article = DbArticle::read_view(id, &context)?;
-            Ok(Json(article))
+            DbArticle::read_view(id, &context)?
         }
-        _ => Err(anyhow!("Must pass exactly one of title, id").into()),
+        _ => return Err(anyhow!("Must pass exactly one of title, id").into()),
+    };
+    let user_agent = headers.get(USER_AGENT).and_then(|h| h.to_str().ok());
+    let person_id = user.map(|u| u.person.id);
+    if query.record_view.unwrap_or_default()
+        && !is_bot_user_agent(user_agent)
+        && context.view_limit.check(article.article.id, person_id)
+    {
+        DbArticle::record_view(article.article.id, &context)
+            .inspect_err(|e| warn!("Failed to record article view: {e}"))
+            .ok();
     }
+    Ok(Json(article))
 }
 
 #[debug_handler]
 pub(in crate::backend::api) async fn list_articles(
     Query(query): Query<ListArticlesParams>,
     context: Data<IbisContext>,
-) -> MyResult<Json<Vec<DbArticle>>> {
-    Ok(Json(DbArticle::read_all(
+) -> MyResult<Json<Paginated<DbArticle>>> {
+    let pagination = Pagination::new(query.limit, query.cursor.as_ref())?;
+    let articles = DbArticle::read_all(
         query.only_local,
         query.instance_id,
+        query.hide_bots,
+        query.most_viewed,
+        Some(pagination),
         &context,
-    )?))
+    )?;
+    Ok(Json(pagination.page(articles)))
 }
 
 /// Fork a remote article to local instance. This is useful if there are disagreements about
@@ -226,6 +261,7 @@ pub(in crate::backend::api) async fn fork_article(
         local: true,
         protected: false,
         approved: !context.config.options.article_approval,
+        language: original_article.article.language.clone(),
     };
     let article = DbArticle::create(form, &context)?;
 
@@ -262,38 +298,174 @@ pub(super) async fn resolve_article(
     context: Data<IbisContext>,
 ) -> MyResult<Json<DbArticleView>> {
     let article: DbArticle = ObjectId::from(query.id).dereference(&context).await?;
-    let instance = DbInstance::read(article.instance_id, &context)?;
-    let comments = DbComment::read_for_article(article.id, &context)?;
-    let latest_version = article.latest_edit_version(&context)?;
-    Ok(Json(DbArticleView {
-        article,
-        instance,
-        comments,
-        latest_version,
-    }))
+    Ok(Json(DbArticle::read_view(article.id, &context)?))
 }
 
-/// Search articles for matching title or body text.
+/// Full text search over article title and text.
 #[debug_handler]
 pub(super) async fn search_article(
+    user: Option<Extension<LocalUserView>>,
     Query(query): Query<SearchArticleParams>,
     context: Data<IbisContext>,
-) -> MyResult<Json<Vec<DbArticle>>> {
+) -> MyResult<Json<SearchArticleResponse>> {
     if query.query.is_empty() {
         return Err(anyhow!("Query is empty").into());
     }
-    let article = DbArticle::search(&query.query, &context)?;
-    Ok(Json(article))
+    let pagination = Pagination::new(query.limit, query.cursor.as_ref())?;
+    let author = query.author.as_deref().map(str::trim);
+    let author_id = match author.filter(|a| !a.is_empty()) {
+        Some(author) => {
+            let (name, domain) = match author.split_once('@') {
+                Some((name, domain)) => (name, Some(domain.to_string())),
+                None => (author, None),
+            };
+            Some(DbPerson::read_from_name(name, &domain, &context)?.id)
+        }
+        None => None,
+    };
+    let articles = DbArticle::search(&query, author_id, pagination, &context)?;
+    let mut res = SearchArticleResponse {
+        articles: pagination.page(articles),
+        remote: vec![],
+    };
+    // Remote instances don't know local ids, and remote articles are not wanted in this case
+    let federated = query.federated.unwrap_or_default()
+        && query.instance_id.is_none()
+        && author_id.is_none()
+        && query.local != Some(true);
+    if federated && query.cursor.is_none() {
+        // every federated search sends requests to many instances, so it is not open to anonymous
+        // users
+        if user.is_none() {
+            return Err(anyhow!("Login required for searching other instances").into());
+        }
+        let mut remote = search_remote_instances(&query, pagination.limit, &context).await?;
+        remote.sort_by(|a, b| b.1.total_cmp(&a.1));
+        res.remote = remote
+            .into_iter()
+            .take(pagination.limit as usize)
+            .map(|(article, _)| article)
+            .collect();
+    }
+    Ok(Json(res))
+}
+
+/// Maximum time to wait for the search response of a remote instance
+const FEDERATED_SEARCH_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Send the search query to linked remote instances in parallel, and return articles which
+/// were created on the respective instance and are not known locally, together with their rank.
+/// Instances which fail or don't respond in time are skipped.
+async fn search_remote_instances(
+    query: &SearchArticleParams,
+    limit: i64,
+    context: &Data<IbisContext>,
+) -> MyResult<Vec<(RemoteSearchResult, f32)>> {
+    let client = reqwest::Client::builder()
+        .timeout(FEDERATED_SEARCH_TIMEOUT)
+        .build()?;
+    let params = SearchArticleParams {
+        query: query.query.clone(),
+        limit: Some(limit),
+        edited_after: query.edited_after,
+        edited_before: query.edited_before,
+        protected: query.protected,
+        ..Default::default()
+    };
+    let instances = DbInstance::list_linked(context)?.into_iter().filter(|i| {
+        context
+            .federation_list
+            .check(&i.domain, &context.db_pool)
+            .is_ok()
+    });
+    let requests = instances.map(|instance| {
+        let (client, params) = (&client, &params);
+        async move {
+            let url = instance.ap_id.inner().join("/api/v1/search")?;
+            let res: Paginated<SearchArticleResult> = client
+                .get(url)
+                .query(params)
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            MyResult::Ok((instance, res.items))
+        }
+    });
+
+    let mut seen = HashSet::new();
+    let mut results = vec![];
+    for res in join_all(requests).await {
+        let (instance, items) = match res {
+            Ok(res) => res,
+            Err(e) => {
+                tracing::warn!("Federated search failed: {e}");
+                continue;
+            }
+        };
+        // Only take articles which belong to the remote instance. Others are either known
+        // locally, or can be found by searching their own instance. The instance could also
+        // claim articles of other domains, so the ap_id needs to be checked. Known articles are
+        // already included in the local search.
+        for article in items
+            .into_iter()
+            .filter(|a| a.local && extract_domain(&a.ap_id) == instance.domain)
+        {
+            if !seen.insert(article.ap_id.inner().clone())
+                || DbArticle::read_from_ap_id(&article.ap_id, context).is_ok()
+            {
+                continue;
+            }
+            let remote = RemoteSearchResult {
+                title: article.title,
+                ap_id: article.ap_id,
+                instance_domain: instance.domain.clone(),
+                snippet: article.snippet,
+            };
+            results.push((remote, article.rank));
+        }
+    }
+    Ok(results)
+}
+
+/// Number of suggestions returned if no limit is given
+const SUGGEST_LIMIT_DEFAULT: i64 = 10;
+const SUGGEST_LIMIT_MAX: i64 = 50;
+
+/// Article titles for autocomplete, and to suggest alternatives if search finds nothing.
+#[debug_handler]
+pub(super) async fn search_suggest(
+    Query(query): Query<SearchSuggestParams>,
+    context: Data<IbisContext>,
+) -> MyResult<Json<Vec<ArticleSuggestion>>> {
+    let search = query.query.trim();
+    if search.is_empty() {
+        return Err(anyhow!("Query is empty").into());
+    }
+    let limit = query
+        .limit
+        .unwrap_or(SUGGEST_LIMIT_DEFAULT)
+        .clamp(1, SUGGEST_LIMIT_MAX);
+    Ok(Json(DbArticle::suggest(search, limit, &context)?))
 }
 
 #[debug_handler]
 pub(in crate::backend::api) async fn protect_article(
     Extension(user): Extension<LocalUserView>,
     context: Data<IbisContext>,
-    Form(params): Form<ProtectArticleParams>,
+    Form(mut params): Form<ProtectArticleParams>,
 ) -> MyResult<Json<DbArticle>> {
-    check_is_admin(&user)?;
+    check_permission(&user, Permission::ProtectArticle, &context)?;
+    empty_to_none(&mut params.reason);
     let article = DbArticle::update_protected(params.article_id, params.protected, &context)?;
+    let action = if params.protected {
+        ModlogAction::ProtectArticle
+    } else {
+        ModlogAction::UnprotectArticle
+    };
+    let form = DbModlogForm::article(&user.person, action, &article, params.reason);
+    DbModlog::create(form, &context)?;
     Ok(Json(article))
 }
 
@@ -302,14 +474,24 @@ pub(in crate::backend::api) async fn protect_article(
 pub async fn approve_article(
     Extension(user): Extension<LocalUserView>,
     context: Data<IbisContext>,
-    Form(params): Form<ApproveArticleParams>,
+    Form(mut params): Form<ApproveArticleParams>,
 ) -> MyResult<Json<()>> {
-    check_is_admin(&user)?;
-    if params.approve {
-        DbArticle::update_approved(params.article_id, true, &context)?;
+    check_permission(&user, Permission::ApproveArticle, &context)?;
+    empty_to_none(&mut params.reason);
+    let article = DbArticle::read(params.article_id, &context)?;
+    let action = if params.approve {
+        DbArticle::update_approved(article.id, true, &context)?;
+        ModlogAction::ApproveArticle
     } else {
-        DbArticle::delete(params.article_id, &context)?;
+        DbArticle::delete(article.id, &context)?;
+        ModlogAction::RejectArticle
+    };
+    let mut form = DbModlogForm::article(&user.person, action, &article, params.reason);
+    if !params.approve {
+        // article doesn't exist anymore, only the title is kept
+        form.target_article_id = None;
     }
+    DbModlog::create(form, &context)?;
     Ok(Json(()))
 }
 
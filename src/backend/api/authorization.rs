@@ -0,0 +1,126 @@
+//! Permission checks which are shared by all api handlers. Handlers must not check permissions
+//! inline, but use one of the policies defined here.
+//!
+//! - Login is required by extracting `Extension<LocalUserView>` in the handler
+//! - [check_scope_middleware] restricts requests authenticated with an api token
+//! - [check_permission], [check_is_admin], [check_is_owner], [check_outranks_user] and
+//!   [check_can_edit_article] are called by handlers after reading the affected resource
+
+use crate::{
+    backend::{database::IbisContext, utils::error::MyResult},
+    common::{
+        article::DbArticle,
+        newtypes::PersonId,
+        user::{
+            ApiTokenScope,
+            DbApiToken,
+            DbLocalUser,
+            DbPerson,
+            LocalUserView,
+            Permission,
+            UserRole,
+        },
+        validation::can_edit_article,
+    },
+};
+use anyhow::anyhow;
+use axum::{
+    body::Body,
+    extract::State,
+    http::Request,
+    middleware::Next,
+    response::Response,
+    Extension,
+};
+use axum_macros::debug_middleware;
+
+/// Only admins of the local instance are allowed. Used for actions which can't be granted to
+/// moderators, like changing instance settings or user roles.
+pub(super) fn check_is_admin(user: &LocalUserView, context: &IbisContext) -> MyResult<()> {
+    if !user.local_user.admin {
+        return Err(anyhow!("Only admin can perform this action").into());
+    }
+    check_totp_enabled(user, context)
+}
+
+/// Users with the given permission are allowed, either through their role or because it was
+/// granted individually.
+pub(super) fn check_permission(
+    user: &LocalUserView,
+    permission: Permission,
+    context: &IbisContext,
+) -> MyResult<()> {
+    if !user.local_user.has_permission(permission) {
+        return Err(anyhow!("Missing permission {permission:?}").into());
+    }
+    check_totp_enabled(user, context)
+}
+
+fn check_totp_enabled(user: &LocalUserView, context: &IbisContext) -> MyResult<()> {
+    if context.config.options.admin_totp_required && !user.local_user.totp_enabled {
+        return Err(anyhow!("Privileged actions require two factor authentication").into());
+    }
+    Ok(())
+}
+
+/// Only the user who created a resource is allowed to modify it.
+pub(super) fn check_is_owner(user: &LocalUserView, owner_id: PersonId) -> MyResult<()> {
+    if user.person.id != owner_id {
+        return Err(anyhow!("Cannot modify resource of another user").into());
+    }
+    Ok(())
+}
+
+/// The target must have a lower role than the user. Users who were granted the permission for
+/// the action individually act with the rank of a moderator, so that they can moderate regular
+/// users. Remote users have no role on this instance, so they are treated as regular users.
+pub(super) fn check_outranks_user(
+    user: &LocalUserView,
+    target: &DbPerson,
+    permission: Permission,
+    context: &IbisContext,
+) -> MyResult<()> {
+    let mut rank = user.local_user.role();
+    if user.local_user.has_permission(permission) {
+        rank = rank.max(UserRole::Moderator);
+    }
+    let target_role = if target.local {
+        DbLocalUser::read_from_person(target.id, context)?.role()
+    } else {
+        UserRole::User
+    };
+    if target_role >= rank {
+        return Err(anyhow!("Cannot moderate a user with equal or higher role").into());
+    }
+    Ok(())
+}
+
+/// Protected articles can only be edited by users who are allowed to protect articles.
+pub(super) fn check_can_edit_article(
+    user: &LocalUserView,
+    article: &DbArticle,
+    context: &IbisContext,
+) -> MyResult<()> {
+    let is_admin = check_permission(user, Permission::ProtectArticle, context).is_ok();
+    can_edit_article(article, is_admin)?;
+    Ok(())
+}
+
+/// Requests which are authenticated with an api token can only use endpoints for which the
+/// token has the required scope. The scope is declared for each route in [super::api_routes],
+/// `None` means that the endpoint cannot be used with an api token at all, such as account
+/// management.
+#[debug_middleware]
+pub(super) async fn check_scope_middleware(
+    State(scope): State<Option<ApiTokenScope>>,
+    api_token: Option<Extension<DbApiToken>>,
+    request: Request<Body>,
+    next: Next,
+) -> MyResult<Response> {
+    if let Some(Extension(api_token)) = api_token {
+        if !scope.is_some_and(|s| api_token.has_scope(s)) {
+            return Err(anyhow!("Api token does not have the required scope").into());
+        }
+    }
+    Ok(next.run(request).await)
+}
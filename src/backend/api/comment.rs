@@ -1,30 +1,63 @@
+use super::{
+    authorization::{check_is_owner, check_permission},
+    empty_to_none,
+};
 use crate::{
     backend::{
         database::{
-            comment::{DbCommentInsertForm, DbCommentUpdateForm},
+            comment::{DbCommentInsertForm, DbCommentUpdateForm, DbCommentVoteForm},
+            modlog::DbModlogForm,
             IbisContext,
         },
         federation::activities::comment::{
             create_or_update_comment::CreateOrUpdateComment,
             delete_comment::DeleteComment,
+            remove_comment::RemoveComment,
             undo_delete_comment::UndoDeleteComment,
+            undo_remove_comment::UndoRemoveComment,
+            undo_vote::UndoVote,
+            vote::{Vote, VoteType},
         },
         utils::{
             error::MyResult,
+            pagination::{decode_cursor, encode_cursor},
             validate::{validate_comment_max_depth, validate_not_empty},
         },
     },
     common::{
-        comment::{CreateCommentParams, DbComment, DbCommentView, EditCommentParams},
-        user::LocalUserView,
+        article::DbArticle,
+        comment::{
+            CommentSort,
+            CommentTree,
+            CommentTreeNode,
+            CreateCommentParams,
+            DbComment,
+            DbCommentView,
+            DbCommentVote,
+            EditCommentParams,
+            ListCommentsParams,
+            RemoveCommentParams,
+            VoteCommentParams,
+        },
+        modlog::{DbModlog, ModlogAction},
+        newtypes::{ArticleId, CommentId, PersonId},
+        user::{LocalUserView, Permission},
         utils::http_protocol_str,
     },
 };
 use activitypub_federation::config::Data;
 use anyhow::anyhow;
-use axum::{Extension, Form, Json};
+use axum::{extract::Query, Extension, Form, Json};
 use axum_macros::debug_handler;
 use chrono::Utc;
+use std::collections::HashMap;
+
+/// Number of comments returned on each level of the tree, if no limit is given
+const COMMENT_LIMIT_DEFAULT: i64 = 20;
+const COMMENT_LIMIT_MAX: i64 = 100;
+/// Number of reply levels which are returned, if no max depth is given
+const COMMENT_DEPTH_DEFAULT: i32 = 5;
+const COMMENT_DEPTH_MAX: i32 = 10;
 
 #[debug_handler]
 pub(in crate::backend::api) async fn create_comment(
@@ -36,7 +69,7 @@ pub(in crate::backend::api) async fn create_comment(
     let mut depth = 0;
     if let Some(parent_id) = params.parent_id {
         let parent = DbComment::read(parent_id, &context)?;
-        if parent.deleted {
+        if parent.deleted || parent.removed {
             return Err(anyhow!("Cant reply to deleted comment").into());
         }
         if parent.article_id != params.article_id {
@@ -86,8 +119,9 @@ pub(in crate::backend::api) async fn edit_comment(
         return Err(anyhow!("Edit has no parameters").into());
     }
     let orig_comment = DbComment::read(params.id, &context)?;
-    if orig_comment.creator_id != user.person.id {
-        return Err(anyhow!("Cannot edit comment created by another user").into());
+    check_is_owner(&user, orig_comment.creator_id)?;
+    if orig_comment.removed {
+        return Err(anyhow!("Comment was removed by a moderator").into());
     }
     let form = DbCommentUpdateForm {
         content: params.content,
@@ -110,3 +144,204 @@ pub(in crate::backend::api) async fn edit_comment(
 
     Ok(Json(comment))
 }
+
+/// Remove a comment on a local article, or restore it. Removed comments are hidden like deleted
+/// ones, but can only be restored by a moderator.
+#[debug_handler]
+pub(in crate::backend::api) async fn remove_comment(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    Form(mut params): Form<RemoveCommentParams>,
+) -> MyResult<Json<DbCommentView>> {
+    check_permission(&user, Permission::RemoveComment, &context)?;
+    let orig_comment = DbComment::read(params.id, &context)?;
+    let article = DbArticle::read(orig_comment.article_id, &context)?;
+    if !article.local {
+        return Err(anyhow!("Can only remove comments on local articles").into());
+    }
+    if orig_comment.removed == params.removed {
+        return Ok(Json(DbComment::read_view(params.id, &context)?));
+    }
+    empty_to_none(&mut params.reason);
+    let form = DbCommentUpdateForm {
+        removed: Some(params.removed),
+        updated: Some(Utc::now()),
+        ..Default::default()
+    };
+    let comment = DbComment::update(form, params.id, &context)?;
+
+    let action = if params.removed {
+        ModlogAction::RemoveComment
+    } else {
+        ModlogAction::RestoreComment
+    };
+    let form = DbModlogForm::comment(
+        &user.person,
+        action,
+        &comment.comment,
+        &article,
+        params.reason.clone(),
+    );
+    DbModlog::create(form, &context)?;
+
+    // federate
+    if params.removed {
+        RemoveComment::send(&comment.comment, &user.person, params.reason, &context).await?;
+    } else {
+        UndoRemoveComment::send(&comment.comment, &user.person, &context).await?;
+    }
+
+    Ok(Json(comment))
+}
+
+/// Upvote or downvote a comment, or remove the existing vote with score 0.
+#[debug_handler]
+pub(in crate::backend::api) async fn vote_comment(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    Form(params): Form<VoteCommentParams>,
+) -> MyResult<Json<DbCommentView>> {
+    let comment = DbComment::read(params.comment_id, &context)?;
+    if comment.deleted || comment.removed {
+        return Err(anyhow!("Cant vote on deleted comment").into());
+    }
+    match params.score {
+        1 | -1 => {
+            let form = DbCommentVoteForm {
+                person_id: user.person.id,
+                comment_id: comment.id,
+                score: params.score,
+            };
+            DbCommentVote::create_or_update(form, &context)?;
+            let kind = VoteType::from_score(params.score);
+            Vote::send(&comment, &user.person, kind, &context).await?;
+        }
+        0 => {
+            if let Some(vote) = DbCommentVote::read(user.person.id, comment.id, &context)? {
+                DbCommentVote::delete(user.person.id, comment.id, &context)?;
+                let kind = VoteType::from_score(vote.score);
+                UndoVote::send(&comment, &user.person, kind, &context).await?;
+            }
+        }
+        _ => return Err(anyhow!("Vote score must be 1, -1 or 0").into()),
+    }
+
+    let mut comment = DbComment::read_view(comment.id, &context)?;
+    comment.my_vote = (params.score != 0).then_some(params.score);
+    Ok(Json(comment))
+}
+
+/// List comments of an article as a tree. Only a limited number of comments is returned on each
+/// level, the remaining ones can be loaded with the returned cursors.
+#[debug_handler]
+pub(in crate::backend::api) async fn list_comments(
+    user: Option<Extension<LocalUserView>>,
+    Query(params): Query<ListCommentsParams>,
+    context: Data<IbisContext>,
+) -> MyResult<Json<CommentTree>> {
+    let limit = params
+        .limit
+        .unwrap_or(COMMENT_LIMIT_DEFAULT)
+        .clamp(1, COMMENT_LIMIT_MAX);
+    let max_depth = params
+        .max_depth
+        .unwrap_or(COMMENT_DEPTH_DEFAULT)
+        .clamp(0, COMMENT_DEPTH_MAX);
+    let offset = match params.cursor {
+        Some(cursor) => decode_cursor(&cursor)?,
+        None => 0,
+    };
+    let sort = params.sort.unwrap_or_default();
+    if let Some(parent_id) = params.parent_id {
+        let parent = DbComment::read(parent_id, &context)?;
+        if parent.article_id != params.article_id {
+            return Err(anyhow!("Parent comment belongs to a different article").into());
+        }
+    }
+
+    // Read one more comment than requested to check if there is a next page
+    let mut comments = DbComment::read_replies(
+        params.article_id,
+        &[params.parent_id],
+        sort,
+        offset,
+        limit + 1,
+        &context,
+    )?;
+    let next_cursor = (comments.len() as i64 > limit).then(|| encode_cursor(offset + limit));
+    comments.truncate(limit as usize);
+    let person_id = user.map(|u| u.person.id);
+    let comments = build_tree_level(
+        comments,
+        max_depth,
+        params.article_id,
+        sort,
+        limit,
+        person_id,
+        &context,
+    )?;
+    Ok(Json(CommentTree {
+        comments,
+        next_cursor,
+    }))
+}
+
+/// Convert one level of comments into tree nodes. Replies of the whole level are read at once,
+/// recursing until `depth_left` is exhausted.
+fn build_tree_level(
+    comments: Vec<(DbCommentView, i64)>,
+    depth_left: i32,
+    article_id: ArticleId,
+    sort: CommentSort,
+    limit: i64,
+    person_id: Option<PersonId>,
+    context: &IbisContext,
+) -> MyResult<Vec<CommentTreeNode>> {
+    let (mut comments, reply_counts): (Vec<_>, Vec<_>) = comments.into_iter().unzip();
+    if let Some(person_id) = person_id {
+        DbCommentVote::fill_my_votes(&mut comments, person_id, context)?;
+    }
+
+    let mut children: HashMap<Option<CommentId>, Vec<CommentTreeNode>> = HashMap::new();
+    let parents: Vec<_> = comments
+        .iter()
+        .zip(&reply_counts)
+        .filter(|(_, reply_count)| **reply_count > 0)
+        .map(|(c, _)| Some(c.comment.id))
+        .collect();
+    if depth_left > 0 && !parents.is_empty() {
+        let replies = DbComment::read_replies(article_id, &parents, sort, 0, limit, context)?;
+        let replies = build_tree_level(
+            replies,
+            depth_left - 1,
+            article_id,
+            sort,
+            limit,
+            person_id,
+            context,
+        )?;
+        for reply in replies {
+            children
+                .entry(reply.comment.comment.parent_id)
+                .or_default()
+                .push(reply);
+        }
+    }
+
+    Ok(comments
+        .into_iter()
+        .zip(reply_counts)
+        .map(|(comment, reply_count)| {
+            let children = children
+                .remove(&Some(comment.comment.id))
+                .unwrap_or_default();
+            let loaded = children.len() as i64;
+            CommentTreeNode {
+                comment,
+                children,
+                reply_count,
+                more_replies: (reply_count > loaded).then(|| encode_cursor(loaded)),
+            }
+        })
+        .collect())
+}
@@ -1,24 +1,41 @@
-use super::empty_to_none;
+use super::{
+    authorization::{check_is_admin, check_permission},
+    empty_to_none,
+};
 use crate::{
     backend::{
-        database::{instance::DbInstanceUpdateForm, IbisContext},
+        database::{
+            federation_list::DbFederationListForm,
+            instance::DbInstanceUpdateForm,
+            modlog::DbModlogForm,
+            IbisContext,
+        },
         federation::activities::follow::Follow,
-        utils::error::MyResult,
+        utils::{error::MyResult, pagination::Pagination},
     },
     common::{
         instance::{
+            DbFederationListEntry,
             DbInstance,
             FollowInstanceParams,
             GetInstanceParams,
+            GetInstanceStatsParams,
+            InstanceStatsDay,
             InstanceView,
+            ListInstancesParams,
+            RemoveFederationListParams,
+            UpdateFederationListParams,
             UpdateInstanceParams,
         },
-        user::LocalUserView,
+        modlog::{DbModlog, GetModlogParams, ModlogAction, ModlogView},
+        pagination::Paginated,
+        user::{LocalUserView, Permission, UserRole},
         ResolveObjectParams,
         SuccessResponse,
     },
 };
 use activitypub_federation::{config::Data, fetch::object_id::ObjectId};
+use anyhow::anyhow;
 use axum::{extract::Query, Extension, Form, Json};
 use axum_macros::debug_handler;
 
@@ -32,17 +49,24 @@ pub(in crate::backend::api) async fn get_instance(
     Ok(Json(local_instance))
 }
 
+/// Update the name and topic of the local instance, only allowed for admins.
+#[debug_handler]
 pub(in crate::backend::api) async fn update_instance(
+    Extension(user): Extension<LocalUserView>,
     context: Data<IbisContext>,
     Form(mut params): Form<UpdateInstanceParams>,
 ) -> MyResult<Json<DbInstance>> {
+    check_is_admin(&user, &context)?;
     empty_to_none(&mut params.name);
     empty_to_none(&mut params.topic);
     let form = DbInstanceUpdateForm {
         name: params.name,
         topic: params.topic,
     };
-    Ok(Json(DbInstance::update(form, &context)?))
+    let instance = DbInstance::update(form, &context)?;
+    let form = DbModlogForm::instance(&user.person, ModlogAction::UpdateInstance, &instance);
+    DbModlog::create(form, &context)?;
+    Ok(Json(instance))
 }
 
 /// Make the local instance follow a given remote instance, to receive activities about new and
@@ -74,8 +98,105 @@ pub(super) async fn resolve_instance(
 
 #[debug_handler]
 pub(in crate::backend::api) async fn list_instances(
+    Query(params): Query<ListInstancesParams>,
     context: Data<IbisContext>,
-) -> MyResult<Json<Vec<DbInstance>>> {
-    let instances = DbInstance::list(false, &context)?;
-    Ok(Json(instances))
+) -> MyResult<Json<Paginated<DbInstance>>> {
+    let pagination = Pagination::new(params.limit, params.cursor.as_ref())?;
+    let instances = DbInstance::list(false, Some(pagination), &context)?;
+    Ok(Json(pagination.page(instances)))
+}
+
+/// Daily statistics of the local instance, only allowed for admins.
+#[debug_handler]
+pub(in crate::backend::api) async fn instance_stats(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    Query(params): Query<GetInstanceStatsParams>,
+) -> MyResult<Json<Vec<InstanceStatsDay>>> {
+    check_is_admin(&user, &context)?;
+    Ok(Json(InstanceStatsDay::read(params.days, &context)?))
+}
+
+/// Public list of privileged actions. Depending on config, the names of moderators are only shown
+/// to other admins and moderators.
+#[debug_handler]
+pub(in crate::backend::api) async fn list_modlog(
+    user: Option<Extension<LocalUserView>>,
+    context: Data<IbisContext>,
+    Query(params): Query<GetModlogParams>,
+) -> MyResult<Json<Paginated<ModlogView>>> {
+    let pagination = Pagination::new(params.limit, params.cursor.as_ref())?;
+    let is_moderator = user.is_some_and(|u| u.local_user.role() != UserRole::User);
+    let show_actor = !context.config.options.modlog_hide_moderators || is_moderator;
+    let modlog = DbModlog::list(pagination, &context)?
+        .into_iter()
+        .map(|(mut modlog, actor)| {
+            if !show_actor {
+                modlog.actor_id = None;
+            }
+            ModlogView {
+                modlog,
+                actor: show_actor.then_some(actor),
+            }
+        })
+        .collect();
+    Ok(Json(pagination.page(modlog)))
+}
+
+/// List all domains which are explicitly allowed or blocked for federation.
+#[debug_handler]
+pub(in crate::backend::api) async fn list_federation_list(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+) -> MyResult<Json<Vec<DbFederationListEntry>>> {
+    check_permission(&user, Permission::ManageFederation, &context)?;
+    Ok(Json(DbFederationListEntry::list(&context)?))
+}
+
+/// Allow or block federation with a domain. Takes effect immediately, and optionally deletes
+/// content which was mirrored from a blocked instance.
+#[debug_handler]
+pub(in crate::backend::api) async fn update_federation_list(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    Form(mut params): Form<UpdateFederationListParams>,
+) -> MyResult<Json<DbFederationListEntry>> {
+    check_permission(&user, Permission::ManageFederation, &context)?;
+    let domain = DbFederationListEntry::normalize_domain(&params.domain)
+        .filter(|d| d != &context.config.federation.domain)
+        .ok_or(anyhow!("Invalid domain"))?;
+    empty_to_none(&mut params.reason);
+    let form = DbFederationListForm {
+        domain: domain.clone(),
+        blocked: params.blocked,
+        reason: params.reason.clone(),
+    };
+    let entry = DbFederationListEntry::create_or_update(form, &context)?;
+    let action = if params.blocked {
+        ModlogAction::BlockInstance
+    } else {
+        ModlogAction::AllowInstance
+    };
+    let form = DbModlogForm::domain(&user.person, action, &domain, params.reason);
+    DbModlog::create(form, &context)?;
+    if params.blocked && params.purge {
+        DbFederationListEntry::purge(&domain, &context)?;
+    }
+    Ok(Json(entry))
+}
+
+#[debug_handler]
+pub(in crate::backend::api) async fn remove_federation_list(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    Form(params): Form<RemoveFederationListParams>,
+) -> MyResult<Json<SuccessResponse>> {
+    check_permission(&user, Permission::ManageFederation, &context)?;
+    let domain =
+        DbFederationListEntry::normalize_domain(&params.domain).ok_or(anyhow!("Invalid domain"))?;
+    DbFederationListEntry::delete(&domain, &context)?;
+    let action = ModlogAction::RemoveFederationListEntry;
+    let form = DbModlogForm::domain(&user.person, action, &domain, None);
+    DbModlog::create(form, &context)?;
+    Ok(Json(SuccessResponse::default()))
 }
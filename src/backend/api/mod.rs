@@ -11,75 +11,370 @@ use crate::{
                 protect_article,
                 resolve_article,
                 search_article,
+                search_suggest,
             },
-            comment::{create_comment, edit_comment},
+            comment::{create_comment, edit_comment, list_comments, remove_comment, vote_comment},
             instance::{follow_instance, get_instance, resolve_instance},
+            oidc::{oidc_callback, oidc_login, oidc_totp},
             user::{get_user, login_user, logout_user, register_user},
         },
         database::IbisContext,
-        utils::error::MyResult,
+        utils::{error::MyResult, pagination::Pagination},
     },
     common::{
-        article::{DbEdit, EditView, GetEditList},
+        article::{DbEdit, EditView, GetEditList, RecentChangeView, RecentChangesParams},
         instance::SiteView,
-        user::LocalUserView,
+        pagination::Paginated,
+        user::{
+            ApiTokenScope::{Admin, Comment, Edit, Read},
+            LocalUserView,
+            OidcProvider,
+            Permission,
+        },
     },
 };
 use activitypub_federation::config::Data;
 use anyhow::anyhow;
 use article::{approve_article, delete_conflict};
+use authorization::{check_permission, check_scope_middleware};
 use axum::{
+    body::Body,
     extract::Query,
+    http::{Method, Request},
+    middleware::{from_fn, from_fn_with_state, Next},
+    response::Response,
     routing::{delete, get, patch, post},
     Extension,
     Json,
     Router,
 };
-use axum_macros::debug_handler;
-use instance::{list_instances, update_instance};
-use user::{count_notifications, list_notifications, update_user_profile};
+use axum_macros::{debug_handler, debug_middleware};
+use instance::{
+    instance_stats,
+    list_federation_list,
+    list_instances,
+    list_modlog,
+    remove_federation_list,
+    update_federation_list,
+    update_instance,
+};
+use openapi::{openapi, ApiRoute};
+use report::{create_report, list_reports, resolve_report};
+use user::{
+    ban_user,
+    count_notifications,
+    create_api_token,
+    delete_account,
+    delete_api_token,
+    delete_user,
+    disable_totp,
+    enable_totp,
+    get_user_role,
+    list_api_tokens,
+    list_notifications,
+    setup_totp,
+    update_user_profile,
+    update_user_role,
+    user_articles,
+    user_comments,
+    user_stats,
+};
 
 mod article;
+mod authorization;
 mod comment;
 mod instance;
+mod oidc;
+mod openapi;
+mod report;
 pub(super) mod user;
 
-pub fn api_routes() -> Router<()> {
-    Router::new()
-        .route(
-            "/article",
-            get(get_article).post(create_article).patch(edit_article),
-        )
-        .route("/article/list", get(list_articles))
-        .route("/article/fork", post(fork_article))
-        .route("/article/resolve", get(resolve_article))
-        .route("/article/protect", post(protect_article))
-        .route("/article/approve", post(approve_article))
-        .route("/edit/list", get(edit_list))
-        .route("/conflict", delete(delete_conflict))
-        .route("/comment", post(create_comment))
-        .route("/comment", patch(edit_comment))
-        .route("/instance", get(get_instance))
-        .route("/instance", patch(update_instance))
-        .route("/instance/follow", post(follow_instance))
-        .route("/instance/resolve", get(resolve_instance))
-        .route("/instance/list", get(list_instances))
-        .route("/search", get(search_article))
-        .route("/user", get(get_user))
-        .route("/user/notifications/list", get(list_notifications))
-        .route("/user/notifications/count", get(count_notifications))
-        .route("/account/register", post(register_user))
-        .route("/account/login", post(login_user))
-        .route("/account/logout", post(logout_user))
-        .route("/account/update", post(update_user_profile))
-        .route("/site", get(site_view))
+/// Generates [api_routes] and the OpenAPI documentation from the same list. Each entry has the
+/// form `(method, path, handler, scope, summary)`, the documented params and response are taken
+/// from the handler signature.
+///
+/// Requests which are authenticated with an api token need the given scope, routes with scope
+/// `None` can't be used with an api token at all.
+macro_rules! define_api_routes {
+    ($(($method:ident, $path:literal, $handler:ident, $scope:expr, $summary:literal)),* $(,)?) => {
+        pub fn api_routes() -> Router<()> {
+            Router::new()
+                $(.route(
+                    $path,
+                    $method($handler)
+                        .route_layer(from_fn_with_state($scope, check_scope_middleware)),
+                ))*
+                .route_layer(from_fn(rate_limit_middleware))
+        }
+
+        /// Documentation of all endpoints of [api_routes].
+        fn api_docs() -> Vec<ApiRoute> {
+            vec![
+                $(ApiRoute::new(
+                    stringify!($method),
+                    $path,
+                    stringify!($handler),
+                    $summary,
+                    $handler,
+                )),*
+            ]
+        }
+    };
+}
+
+define_api_routes! {
+    (
+        get, "/article", get_article, Some(Read),
+        "Read an article by id or title."
+    ),
+    (
+        post, "/article", create_article, Some(Edit),
+        "Create a new article."
+    ),
+    (
+        patch, "/article", edit_article, Some(Edit),
+        "Edit an article. Returns the conflict if the edit could not be merged."
+    ),
+    (
+        get, "/article/list", list_articles, Some(Read),
+        "List articles."
+    ),
+    (
+        post, "/article/fork", fork_article, Some(Edit),
+        "Copy a remote article to the local instance."
+    ),
+    (
+        get, "/article/resolve", resolve_article, Some(Read),
+        "Fetch a remote article by its ActivityPub id."
+    ),
+    (
+        post, "/article/protect", protect_article, Some(Admin),
+        "Only allow local users to edit the article."
+    ),
+    (
+        post, "/article/approve", approve_article, Some(Admin),
+        "Approve or reject a new article."
+    ),
+    (
+        get, "/edit/list", edit_list, Some(Read),
+        "List edits of an article or user."
+    ),
+    (
+        get, "/recent_changes", recent_changes, Some(Read),
+        "Latest edits of all articles."
+    ),
+    (
+        delete, "/conflict", delete_conflict, Some(Edit),
+        "Discard an edit conflict."
+    ),
+    (
+        post, "/comment", create_comment, Some(Comment),
+        "Write a comment on an article."
+    ),
+    (
+        patch, "/comment", edit_comment, Some(Comment),
+        "Edit or delete a comment."
+    ),
+    (
+        get, "/comment/list", list_comments, Some(Read),
+        "List comments of an article as a tree."
+    ),
+    (
+        post, "/comment/remove", remove_comment, Some(Admin),
+        "Remove a comment as moderator."
+    ),
+    (
+        post, "/comment/vote", vote_comment, Some(Comment),
+        "Vote on a comment."
+    ),
+    (
+        get, "/instance", get_instance, Some(Read),
+        "Read an instance, or the local one if no id is given."
+    ),
+    (
+        patch, "/instance", update_instance, Some(Admin),
+        "Update the local instance."
+    ),
+    (
+        post, "/instance/follow", follow_instance, Some(Edit),
+        "Follow a remote instance to receive its articles."
+    ),
+    (
+        get, "/instance/resolve", resolve_instance, Some(Read),
+        "Fetch a remote instance by its ActivityPub id."
+    ),
+    (
+        get, "/instance/list", list_instances, Some(Read),
+        "List known instances."
+    ),
+    (
+        get, "/instance/stats", instance_stats, Some(Read),
+        "Daily statistics of the local instance."
+    ),
+    (
+        get, "/instance/federation_list", list_federation_list, Some(Read),
+        "List blocked and allowed instances."
+    ),
+    (
+        post, "/instance/federation_list", update_federation_list, Some(Admin),
+        "Block or allow an instance."
+    ),
+    (
+        delete, "/instance/federation_list", remove_federation_list, Some(Admin),
+        "Remove an instance from the federation list."
+    ),
+    (
+        get, "/modlog", list_modlog, Some(Read),
+        "List moderation actions."
+    ),
+    (
+        post, "/report", create_report, Some(Comment),
+        "Report an article or comment to the admins."
+    ),
+    (
+        get, "/report/list", list_reports, Some(Admin),
+        "List reports."
+    ),
+    (
+        post, "/report/resolve", resolve_report, Some(Admin),
+        "Resolve or dismiss a report."
+    ),
+    (
+        get, "/search", search_article, Some(Read),
+        "Search articles on the local and remote instances."
+    ),
+    (
+        get, "/search/suggest", search_suggest, Some(Read),
+        "Article titles for search autocompletion."
+    ),
+    (
+        get, "/user", get_user, Some(Read),
+        "Read a user by name."
+    ),
+    (
+        post, "/user/delete", delete_user, Some(Admin),
+        "Delete a local user as admin."
+    ),
+    (
+        get, "/user/role", get_user_role, Some(Read),
+        "Read the role of a local user."
+    ),
+    (
+        post, "/user/role", update_user_role, Some(Admin),
+        "Change the role of a local user."
+    ),
+    (
+        post, "/user/ban", ban_user, Some(Admin),
+        "Ban or unban a user."
+    ),
+    (
+        get, "/user/stats", user_stats, Some(Read),
+        "Contribution statistics of a user."
+    ),
+    (
+        get, "/user/comments", user_comments, Some(Read),
+        "Comments written by a user."
+    ),
+    (
+        get, "/user/articles", user_articles, Some(Read),
+        "Articles created by a user."
+    ),
+    (
+        get, "/user/notifications/list", list_notifications, Some(Read),
+        "List notifications of the current user."
+    ),
+    (
+        get, "/user/notifications/count", count_notifications, Some(Read),
+        "Number of unread notifications."
+    ),
+    (
+        post, "/account/register", register_user, None,
+        "Register a new account and log in."
+    ),
+    (
+        post, "/account/login", login_user, None,
+        "Log in and set the auth cookie."
+    ),
+    (
+        post, "/account/logout", logout_user, None,
+        "Remove the auth cookie."
+    ),
+    (
+        post, "/account/update", update_user_profile, None,
+        "Update the profile of the current user."
+    ),
+    (
+        post, "/account/delete", delete_account, None,
+        "Delete the account of the current user."
+    ),
+    (
+        get, "/account/oidc/login", oidc_login, None,
+        "Redirect to the login page of an OpenID Connect provider."
+    ),
+    (
+        get, "/account/oidc/callback", oidc_callback, None,
+        "Finish the OpenID Connect login and redirect to the main page."
+    ),
+    (
+        post, "/account/oidc/totp", oidc_totp, None,
+        "Confirm an OpenID Connect login with the second factor."
+    ),
+    (
+        post, "/account/totp/setup", setup_totp, None,
+        "Generate a new secret for two-factor authentication."
+    ),
+    (
+        post, "/account/totp/enable", enable_totp, None,
+        "Enable two-factor authentication."
+    ),
+    (
+        post, "/account/totp/disable", disable_totp, None,
+        "Disable two-factor authentication."
+    ),
+    (
+        post, "/account/token", create_api_token, None,
+        "Create an api token. The token is only returned once."
+    ),
+    (
+        delete, "/account/token", delete_api_token, None,
+        "Revoke an api token."
+    ),
+    (
+        get, "/account/token/list", list_api_tokens, None,
+        "List api tokens of the current user."
+    ),
+    (
+        get, "/site", site_view, Some(Read),
+        "Site configuration and the current user."
+    ),
+    (
+        get, "/openapi.json", openapi, Some(Read),
+        "This document."
+    ),
 }
 
-fn check_is_admin(user: &LocalUserView) -> MyResult<()> {
-    if !user.local_user.admin {
-        return Err(anyhow!("Only admin can perform this action").into());
+/// Limit write actions per user. Bots have a higher limit as they often perform many edits.
+#[debug_middleware]
+async fn rate_limit_middleware(
+    user: Option<Extension<LocalUserView>>,
+    context: Data<IbisContext>,
+    request: Request<Body>,
+    next: Next,
+) -> MyResult<Response> {
+    if let Some(Extension(user)) = user {
+        if request.method() != Method::GET {
+            let limits = &context.config.rate_limit;
+            let per_minute = if user.person.bot {
+                limits.bot_actions_per_minute
+            } else {
+                limits.actions_per_minute
+            };
+            if !context.rate_limit.check(user.person.id, per_minute) {
+                return Err(anyhow!("Rate limit exceeded").into());
+            }
+        }
     }
-    Ok(())
+    Ok(next.run(request).await)
 }
 
 #[debug_handler]
@@ -90,6 +385,15 @@ pub(in crate::backend::api) async fn site_view(
     Ok(Json(SiteView {
         my_profile: user.map(|u| u.0),
         config: context.config.options.clone(),
+        oidc_providers: context
+            .config
+            .oidc
+            .iter()
+            .map(|o| OidcProvider {
+                name: o.name.clone(),
+                display_name: o.display_name.clone(),
+            })
+            .collect(),
     }))
 }
 
@@ -99,7 +403,7 @@ pub async fn edit_list(
     Query(query): Query<GetEditList>,
     user: Option<Extension<LocalUserView>>,
     context: Data<IbisContext>,
-) -> MyResult<Json<Vec<EditView>>> {
+) -> MyResult<Json<Paginated<EditView>>> {
     let params = if let Some(article_id) = query.article_id {
         ViewEditParams::ArticleId(article_id)
     } else if let Some(person_id) = query.person_id {
@@ -107,7 +411,39 @@ pub async fn edit_list(
     } else {
         return Err(anyhow!("Must provide article_id or person_id").into());
     };
-    Ok(Json(DbEdit::view(params, &user.map(|u| u.0), &context)?))
+    let pagination = Pagination::new(query.limit, query.cursor.as_ref())?;
+    let edits = DbEdit::view(
+        params,
+        query.hide_bots.unwrap_or_default(),
+        pagination,
+        &user.map(|u| u.0),
+        &context,
+    )?;
+    Ok(Json(pagination.page(edits)))
+}
+
+/// Latest edits of all local and remote articles, newest first.
+#[debug_handler]
+pub async fn recent_changes(
+    Query(query): Query<RecentChangesParams>,
+    user: Option<Extension<LocalUserView>>,
+    context: Data<IbisContext>,
+) -> MyResult<Json<Paginated<RecentChangeView>>> {
+    let pagination = Pagination::new(query.limit, query.cursor.as_ref())?;
+    let user = user.map(|u| u.0);
+    let show_pending = user
+        .as_ref()
+        .is_some_and(|u| check_permission(u, Permission::ApproveArticle, &context).is_ok());
+    let edits = DbEdit::recent_changes(&query, pagination, &user, show_pending, &context)?
+        .into_iter()
+        .map(|e| RecentChangeView {
+            byte_delta: e.edit.byte_delta(),
+            edit: e.edit,
+            article: e.article,
+            creator: e.creator,
+        })
+        .collect();
+    Ok(Json(pagination.page(edits)))
 }
 
 /// Trims the string param, and converts to None if it is empty
@@ -0,0 +1,405 @@
+use super::user::{create_cookie, generate_login_token};
+use crate::{
+    backend::{
+        database::{read_jwt_secret, IbisContext},
+        utils::{
+            config::IbisConfigOidc,
+            error::MyResult,
+            totp::check_totp_token,
+            validate::validate_user_name,
+        },
+    },
+    common::{
+        user::{DbLocalUser, DbPerson, LocalUserView, OidcTotpParams},
+        utils::http_protocol_str,
+    },
+};
+use activitypub_federation::config::Data;
+use anyhow::anyhow;
+use axum::{extract::Query, response::Redirect, Extension, Form, Json};
+use axum_extra::extract::cookie::{Cookie, CookieJar, SameSite};
+use axum_macros::debug_handler;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use jsonwebtoken::{
+    decode,
+    encode,
+    get_current_timestamp,
+    DecodingKey,
+    EncodingKey,
+    Header,
+    Validation,
+};
+use rand::{distributions::Alphanumeric, thread_rng, Rng};
+use schemars::JsonSchema;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use url::Url;
+
+/// Stores [LoginStateClaims] between login and callback
+const OIDC_STATE_COOKIE: &str = "oidc_state";
+
+/// Audience of the login state token, so that it can't be used as auth token
+const OIDC_STATE_AUDIENCE: &str = "oidc_state";
+
+/// Time in seconds to finish the login with the provider
+const OIDC_STATE_EXPIRATION: u64 = 60 * 10;
+
+/// Stores the login which is waiting for the second factor, for accounts with two factor
+/// authentication
+const OIDC_TOTP_COOKIE: &str = "oidc_totp";
+
+/// Audience of the pending login token, so that it can't be used as auth token
+const OIDC_TOTP_AUDIENCE: &str = "oidc_totp";
+
+/// Time in seconds to enter the second factor
+const OIDC_TOTP_EXPIRATION: u64 = 60 * 5;
+
+/// Set when a logged in user confirms their identity by logging in with the provider again.
+/// This allows accounts without a known password to confirm sensitive actions.
+const OIDC_REAUTH_COOKIE: &str = "oidc_reauth";
+
+const OIDC_REAUTH_AUDIENCE: &str = "oidc_reauth";
+
+/// Time in seconds during which the re-authentication is valid
+const OIDC_REAUTH_EXPIRATION: u64 = 60 * 5;
+
+#[derive(Serialize, Deserialize)]
+struct LoginStateClaims {
+    provider: String,
+    state: String,
+    /// Must be included in the id token
+    nonce: String,
+    /// PKCE verifier, only the challenge derived from it is sent to the authorization endpoint
+    code_verifier: String,
+    aud: String,
+    exp: u64,
+}
+
+/// Short lived token for a login which waits for the second factor, or for a recent
+/// re-authentication with the provider
+#[derive(Serialize, Deserialize)]
+struct PendingLoginClaims {
+    /// person.username
+    sub: String,
+    aud: String,
+    exp: u64,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub(in crate::backend::api) struct OidcLoginParams {
+    provider: String,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub(in crate::backend::api) struct OidcCallbackParams {
+    code: String,
+    state: String,
+}
+
+/// Subset of the provider metadata which is needed for the authorization code flow
+#[derive(Deserialize)]
+struct OidcDiscovery {
+    authorization_endpoint: Url,
+    token_endpoint: Url,
+    userinfo_endpoint: Url,
+}
+
+#[derive(Deserialize)]
+struct OidcTokenResponse {
+    access_token: String,
+    id_token: String,
+}
+
+#[derive(Deserialize)]
+struct IdTokenClaims {
+    sub: String,
+    nonce: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct OidcUserInfo {
+    sub: String,
+    preferred_username: Option<String>,
+}
+
+/// Redirect the user to the login page of the identity provider.
+#[debug_handler]
+pub(in crate::backend::api) async fn oidc_login(
+    context: Data<IbisContext>,
+    jar: CookieJar,
+    Query(params): Query<OidcLoginParams>,
+) -> MyResult<(CookieJar, Redirect)> {
+    let provider = read_provider(&params.provider, &context)?;
+    let discovery = discover(provider).await?;
+    let claims = LoginStateClaims {
+        provider: provider.name.clone(),
+        state: random_string(32),
+        nonce: random_string(32),
+        code_verifier: random_string(64),
+        aud: OIDC_STATE_AUDIENCE.to_string(),
+        exp: get_current_timestamp() + OIDC_STATE_EXPIRATION,
+    };
+    let code_challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(&claims.code_verifier));
+
+    let mut url = discovery.authorization_endpoint;
+    url.query_pairs_mut()
+        .append_pair("response_type", "code")
+        .append_pair("client_id", &provider.client_id)
+        .append_pair("redirect_uri", &redirect_uri(&context))
+        .append_pair("scope", "openid profile")
+        .append_pair("state", &claims.state)
+        .append_pair("nonce", &claims.nonce)
+        .append_pair("code_challenge", &code_challenge)
+        .append_pair("code_challenge_method", "S256");
+
+    // Needs to be lax so that it is included in the redirect back from the provider
+    let cookie = Cookie::build((OIDC_STATE_COOKIE, encode_claims(&claims, &context)?))
+        .same_site(SameSite::Lax)
+        .path("/")
+        .http_only(true)
+        .secure(!cfg!(debug_assertions))
+        .build();
+    Ok((jar.add(cookie), Redirect::to(url.as_str())))
+}
+
+/// Called by the identity provider after successful login. Fetches the user info from the
+/// provider and logs in the linked local user. If there is no linked user yet, the identity is
+/// linked to the currently logged in user, or a new account is created. Accounts with two factor
+/// authentication are redirected to the login page, and need to confirm with [oidc_totp].
+/// If the identity is already linked to the logged in user, this counts as re-authentication,
+/// see [check_oidc_reauth].
+///
+/// The id token and user info are fetched directly from the provider over TLS, so there is no
+/// need to verify the id token signature. It is only used to check the nonce.
+#[debug_handler]
+pub(in crate::backend::api) async fn oidc_callback(
+    user: Option<Extension<LocalUserView>>,
+    context: Data<IbisContext>,
+    jar: CookieJar,
+    Query(params): Query<OidcCallbackParams>,
+) -> MyResult<(CookieJar, Redirect)> {
+    let Some(cookie) = jar.get(OIDC_STATE_COOKIE) else {
+        return Err(anyhow!("Missing OpenID Connect state").into());
+    };
+    let login_state: LoginStateClaims =
+        decode_claims(cookie.value(), OIDC_STATE_AUDIENCE, &context)?;
+    if login_state.state != params.state {
+        return Err(anyhow!("Invalid OpenID Connect state").into());
+    }
+    let provider = read_provider(&login_state.provider, &context)?;
+    let discovery = discover(provider).await?;
+    let client = reqwest::Client::new();
+
+    let token: OidcTokenResponse = client
+        .post(discovery.token_endpoint)
+        .form(&[
+            ("grant_type", "authorization_code"),
+            ("code", &params.code),
+            ("redirect_uri", &redirect_uri(&context)),
+            ("client_id", &provider.client_id),
+            ("client_secret", &provider.client_secret),
+            ("code_verifier", &login_state.code_verifier),
+        ])
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    let mut validation = Validation::default();
+    validation.insecure_disable_signature_validation();
+    validation.set_audience(&[&provider.client_id]);
+    let id_token =
+        decode::<IdTokenClaims>(&token.id_token, &DecodingKey::from_secret(&[]), &validation)?
+            .claims;
+    if id_token.nonce.as_ref() != Some(&login_state.nonce) {
+        return Err(anyhow!("Invalid OpenID Connect nonce").into());
+    }
+    let user_info: OidcUserInfo = client
+        .get(discovery.userinfo_endpoint)
+        .bearer_auth(token.access_token)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    if user_info.sub != id_token.sub {
+        return Err(anyhow!("OpenID Connect user info does not match id token").into());
+    }
+
+    let jar = jar.remove(Cookie::build(OIDC_STATE_COOKIE).path("/"));
+    let logged_in_id = user.as_ref().map(|u| u.person.id);
+    let local_user = match DbPerson::read_local_from_oidc(&provider.name, &user_info.sub, &context)?
+    {
+        Some(local_user) if Some(local_user.person.id) == logged_in_id => {
+            let claims = PendingLoginClaims {
+                sub: local_user.person.username,
+                aud: OIDC_REAUTH_AUDIENCE.to_string(),
+                exp: get_current_timestamp() + OIDC_REAUTH_EXPIRATION,
+            };
+            let cookie = short_lived_cookie(OIDC_REAUTH_COOKIE, encode_claims(&claims, &context)?);
+            return Ok((jar.add(cookie), Redirect::to("/edit_profile")));
+        }
+        Some(local_user) => local_user,
+        None => {
+            let local_user = match user {
+                Some(Extension(user)) => user,
+                None => {
+                    if !context.config.options.registration_open {
+                        return Err(anyhow!("Registration is closed").into());
+                    }
+                    let username = generate_username(&user_info, &context)?;
+                    // Password is never shown, so login is only possible via the provider
+                    DbPerson::create_local(username, random_string(32), false, &context)?
+                }
+            };
+            DbLocalUser::link_oidc(
+                local_user.local_user.id,
+                &provider.name,
+                &user_info.sub,
+                &context,
+            )?;
+            local_user
+        }
+    };
+    if local_user.person.is_banned() {
+        let reason = local_user.person.ban_reason.unwrap_or_default();
+        return Err(anyhow!("You are banned: {reason}").into());
+    }
+
+    if local_user.local_user.totp_enabled && logged_in_id != Some(local_user.person.id) {
+        let claims = PendingLoginClaims {
+            sub: local_user.person.username,
+            aud: OIDC_TOTP_AUDIENCE.to_string(),
+            exp: get_current_timestamp() + OIDC_TOTP_EXPIRATION,
+        };
+        let cookie = short_lived_cookie(OIDC_TOTP_COOKIE, encode_claims(&claims, &context)?);
+        return Ok((jar.add(cookie), Redirect::to("/login?oidc_totp=true")));
+    }
+    let token = generate_login_token(&local_user.person, &context)?;
+    Ok((jar.add(create_cookie(token, &context)), Redirect::to("/")))
+}
+
+/// Finish an OpenID Connect login which is waiting for the second factor.
+#[debug_handler]
+pub(in crate::backend::api) async fn oidc_totp(
+    context: Data<IbisContext>,
+    jar: CookieJar,
+    Form(params): Form<OidcTotpParams>,
+) -> MyResult<(CookieJar, Json<LocalUserView>)> {
+    let Some(jwt) = jar.get(OIDC_TOTP_COOKIE) else {
+        return Err(anyhow!("No pending OpenID Connect login").into());
+    };
+    let claims: PendingLoginClaims = decode_claims(jwt.value(), OIDC_TOTP_AUDIENCE, &context)?;
+    let user = DbPerson::read_local_from_name(&claims.sub, &context)?;
+    check_totp_token(&user, &params.totp_token, &context)?;
+
+    let token = generate_login_token(&user.person, &context)?;
+    let jar = jar
+        .remove(Cookie::build(OIDC_TOTP_COOKIE).path("/"))
+        .add(create_cookie(token, &context));
+    Ok((jar, Json(user)))
+}
+
+/// Returns true if the user has recently logged in again with a linked provider.
+pub(super) fn check_oidc_reauth(
+    jar: &CookieJar,
+    user: &LocalUserView,
+    context: &IbisContext,
+) -> bool {
+    jar.get(OIDC_REAUTH_COOKIE)
+        .and_then(|c| {
+            decode_claims::<PendingLoginClaims>(c.value(), OIDC_REAUTH_AUDIENCE, context).ok()
+        })
+        .is_some_and(|claims| claims.sub == user.person.username)
+}
+
+fn short_lived_cookie(name: &'static str, value: String) -> Cookie<'static> {
+    Cookie::build((name, value))
+        .same_site(SameSite::Strict)
+        .path("/")
+        .http_only(true)
+        .secure(!cfg!(debug_assertions))
+        .build()
+}
+
+/// Claims are signed with the same secret as auth tokens, so each kind of token needs its own
+/// audience.
+fn encode_claims<T: Serialize>(claims: &T, context: &IbisContext) -> MyResult<String> {
+    let secret = read_jwt_secret(context)?;
+    Ok(encode(
+        &Header::default(),
+        claims,
+        &EncodingKey::from_secret(secret.as_bytes()),
+    )?)
+}
+
+fn decode_claims<T: DeserializeOwned>(
+    jwt: &str,
+    audience: &str,
+    context: &IbisContext,
+) -> MyResult<T> {
+    let mut validation = Validation::default();
+    validation.set_audience(&[audience]);
+    let secret = read_jwt_secret(context)?;
+    let key = DecodingKey::from_secret(secret.as_bytes());
+    Ok(decode::<T>(jwt, &key, &validation)?.claims)
+}
+
+fn read_provider<'a>(name: &str, context: &'a IbisContext) -> MyResult<&'a IbisConfigOidc> {
+    context
+        .config
+        .oidc
+        .iter()
+        .find(|o| o.name == name)
+        .ok_or_else(|| anyhow!("Unknown OpenID Connect provider").into())
+}
+
+async fn discover(provider: &IbisConfigOidc) -> MyResult<OidcDiscovery> {
+    let url = format!(
+        "{}/.well-known/openid-configuration",
+        provider.issuer_url.trim_end_matches('/')
+    );
+    Ok(reqwest::get(url).await?.error_for_status()?.json().await?)
+}
+
+fn redirect_uri(context: &IbisContext) -> String {
+    format!(
+        "{}://{}/api/v1/account/oidc/callback",
+        http_protocol_str(),
+        context.config.federation.domain
+    )
+}
+
+/// Use the username from the provider if possible. Otherwise, or if it is already taken, append
+/// a random suffix.
+fn generate_username(user_info: &OidcUserInfo, context: &Data<IbisContext>) -> MyResult<String> {
+    let base: String = user_info
+        .preferred_username
+        .clone()
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '_')
+        .take(15)
+        .collect();
+    let is_available = |name: &str| {
+        validate_user_name(name).is_ok() && DbPerson::read_from_name(name, &None, context).is_err()
+    };
+    if is_available(&base) {
+        return Ok(base);
+    }
+    for _ in 0..10 {
+        let name = format!("{base}_{}", random_string(4).to_lowercase());
+        if is_available(&name) {
+            return Ok(name);
+        }
+    }
+    Err(anyhow!("Failed to generate username").into())
+}
+
+fn random_string(len: usize) -> String {
+    thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(len)
+        .map(char::from)
+        .collect()
+}
@@ -0,0 +1,251 @@
+use super::api_docs;
+use crate::{
+    backend::{database::IbisContext, utils::error::MyResult},
+    common::utils::http_protocol_str,
+};
+use activitypub_federation::config::Data;
+use axum::{extract::Query, http::HeaderMap, response::Redirect, Extension, Form, Json};
+use axum_extra::extract::CookieJar;
+use axum_macros::debug_handler;
+use schemars::{
+    generate::{SchemaGenerator, SchemaSettings},
+    JsonSchema,
+    Schema,
+};
+use serde_json::{json, Map, Value};
+use std::future::Future;
+
+type SchemaFn = fn(&mut SchemaGenerator) -> Schema;
+
+/// Documentation of a single endpoint. Params and response are taken from the signature of the
+/// handler, so they can't get out of sync with the implementation.
+pub(super) struct ApiRoute {
+    method: &'static str,
+    path: &'static str,
+    handler: &'static str,
+    summary: &'static str,
+    /// Query parameters for `GET`, form body for other methods
+    params: Option<SchemaFn>,
+    /// Json response, or `None` if the endpoint redirects
+    response: Option<SchemaFn>,
+}
+
+impl ApiRoute {
+    pub(super) fn new<H: ApiHandler<Args>, Args>(
+        method: &'static str,
+        path: &'static str,
+        handler: &'static str,
+        summary: &'static str,
+        _: H,
+    ) -> Self {
+        Self {
+            method,
+            path,
+            handler,
+            summary,
+            params: H::params(),
+            response: H::response(),
+        }
+    }
+}
+
+/// Implemented for all api handlers, with the extractors as `Args`.
+pub(super) trait ApiHandler<Args> {
+    fn params() -> Option<SchemaFn>;
+    fn response() -> Option<SchemaFn>;
+}
+
+macro_rules! impl_api_handler {
+    ($($arg:ident),+) => {
+        impl<F, Fut, R, $($arg),+> ApiHandler<($($arg,)+)> for F
+        where
+            F: FnOnce($($arg),+) -> Fut,
+            Fut: Future<Output = MyResult<R>>,
+            R: ApiResponse,
+            $($arg: ApiExtractor),+
+        {
+            fn params() -> Option<SchemaFn> {
+                [$(<$arg as ApiExtractor>::params()),+]
+                    .into_iter()
+                    .flatten()
+                    .next()
+            }
+
+            fn response() -> Option<SchemaFn> {
+                R::response()
+            }
+        }
+    };
+}
+impl_api_handler!(T1);
+impl_api_handler!(T1, T2);
+impl_api_handler!(T1, T2, T3);
+impl_api_handler!(T1, T2, T3, T4);
+
+/// Extractor of an api handler. Only [Query] and [Form] contain parameters which are documented.
+pub(super) trait ApiExtractor {
+    fn params() -> Option<SchemaFn> {
+        None
+    }
+}
+
+impl<T: JsonSchema> ApiExtractor for Query<T> {
+    fn params() -> Option<SchemaFn> {
+        Some(SchemaGenerator::subschema_for::<T>)
+    }
+}
+
+impl<T: JsonSchema> ApiExtractor for Form<T> {
+    fn params() -> Option<SchemaFn> {
+        Some(SchemaGenerator::subschema_for::<T>)
+    }
+}
+
+impl<T: ApiExtractor> ApiExtractor for Option<T> {
+    fn params() -> Option<SchemaFn> {
+        T::params()
+    }
+}
+impl<T> ApiExtractor for Extension<T> {}
+impl<T: Clone> ApiExtractor for Data<T> {}
+impl ApiExtractor for HeaderMap {}
+impl ApiExtractor for CookieJar {}
+
+/// Successful response of an api handler.
+pub(super) trait ApiResponse {
+    fn response() -> Option<SchemaFn>;
+}
+
+impl<T: JsonSchema> ApiResponse for Json<T> {
+    fn response() -> Option<SchemaFn> {
+        Some(SchemaGenerator::subschema_for::<T>)
+    }
+}
+
+impl ApiResponse for Redirect {
+    fn response() -> Option<SchemaFn> {
+        None
+    }
+}
+
+impl<R: ApiResponse> ApiResponse for (CookieJar, R) {
+    fn response() -> Option<SchemaFn> {
+        R::response()
+    }
+}
+
+/// OpenAPI 3 document which describes all api endpoints.
+#[debug_handler]
+pub(in crate::backend::api) async fn openapi(context: Data<IbisContext>) -> MyResult<Json<Value>> {
+    let mut generator = SchemaSettings::openapi3().into_generator();
+    let mut paths = Map::new();
+    for route in api_docs() {
+        let operation = operation(&route, &mut generator);
+        let path = paths
+            .entry(route.path)
+            .or_insert_with(|| Value::Object(Map::new()));
+        path[route.method] = operation;
+    }
+    Ok(Json(json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "Ibis API",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "servers": [{
+            "url": format!(
+                "{}://{}/api/v1",
+                http_protocol_str(),
+                context.config.federation.domain
+            ),
+        }],
+        "paths": paths,
+        "components": {
+            "schemas": generator.take_definitions(true),
+            "securitySchemes": {
+                "cookie": { "type": "apiKey", "in": "cookie", "name": "auth" },
+                "bearer": { "type": "http", "scheme": "bearer" },
+            },
+        },
+        // Most endpoints can be used without login, the handlers check permissions
+        "security": [{}, { "cookie": [] }, { "bearer": [] }],
+    })))
+}
+
+fn operation(route: &ApiRoute, generator: &mut SchemaGenerator) -> Value {
+    let mut operation = json!({
+        "operationId": route.handler,
+        "summary": route.summary,
+    });
+    if let Some(params) = route.params {
+        let schema = params(generator);
+        if route.method == "get" {
+            operation["parameters"] = query_parameters(schema, generator).into();
+        } else {
+            operation["requestBody"] = json!({
+                "required": true,
+                "content": {
+                    "application/x-www-form-urlencoded": {
+                        "schema": transform(schema, generator),
+                    },
+                },
+            });
+        }
+    }
+    let (status, response) = match route.response {
+        Some(response) => (
+            "200",
+            json!({
+                "description": "Success",
+                "content": {
+                    "application/json": { "schema": transform(response(generator), generator) },
+                },
+            }),
+        ),
+        None => ("303", json!({ "description": "Redirect" })),
+    };
+    operation["responses"] = json!({
+        "default": {
+            "description": "Error",
+            "content": { "text/plain": { "schema": { "type": "string" } } },
+        },
+    });
+    operation["responses"][status] = response;
+    operation
+}
+
+/// Applies the OpenAPI specific transforms of the generator. Definitions are only transformed
+/// when they are taken at the end.
+fn transform(mut schema: Schema, generator: &mut SchemaGenerator) -> Value {
+    for transform in generator.transforms_mut() {
+        transform.transform(&mut schema);
+    }
+    schema.to_value()
+}
+
+/// Converts the properties of a params struct into a list of query parameters.
+fn query_parameters(schema: Schema, generator: &mut SchemaGenerator) -> Vec<Value> {
+    let schema = schema
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|r| r.rsplit('/').next())
+        .and_then(|name| generator.definitions().get(name).cloned())
+        .and_then(|s| Schema::try_from(s).ok())
+        .unwrap_or(schema);
+    let schema = transform(schema, generator);
+    let required = schema["required"].as_array();
+    let Some(properties) = schema["properties"].as_object() else {
+        return vec![];
+    };
+    properties
+        .iter()
+        .map(|(name, schema)| {
+            json!({
+                "name": name,
+                "in": "query",
+                "required": required.is_some_and(|r| r.contains(&name.as_str().into())),
+                "schema": schema,
+            })
+        })
+        .collect()
+}
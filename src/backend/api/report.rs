@@ -0,0 +1,111 @@
+use super::authorization::check_permission;
+use crate::{
+    backend::{
+        database::{modlog::DbModlogForm, report::DbReportForm, IbisContext},
+        federation::activities::flag::Flag,
+        utils::{
+            error::MyResult,
+            generate_activity_id,
+            pagination::Pagination,
+            validate::validate_not_empty,
+        },
+    },
+    common::{
+        article::{DbArticle, DbEdit},
+        comment::DbComment,
+        instance::DbInstance,
+        modlog::{DbModlog, ModlogAction},
+        pagination::Paginated,
+        report::{
+            CreateReportParams,
+            DbReport,
+            ListReportsParams,
+            ReportStatus,
+            ReportView,
+            ResolveReportParams,
+        },
+        user::{LocalUserView, Permission},
+    },
+};
+use activitypub_federation::config::Data;
+use anyhow::anyhow;
+use axum::{extract::Query, Extension, Form, Json};
+use axum_macros::debug_handler;
+
+/// Report an article, edit or comment to the moderators. If the article is hosted on another
+/// instance, the report is also forwarded there.
+#[debug_handler]
+pub(in crate::backend::api) async fn create_report(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    Form(params): Form<CreateReportParams>,
+) -> MyResult<Json<DbReport>> {
+    let reason = params.reason.trim().to_string();
+    validate_not_empty(&reason)?;
+    let article = DbArticle::read(params.article_id, &context)?;
+    let object = if let Some(comment_id) = params.comment_id {
+        let comment = DbComment::read(comment_id, &context)?;
+        if comment.article_id != article.id {
+            return Err(anyhow!("Comment does not belong to article").into());
+        }
+        comment.ap_id.into_inner()
+    } else if let Some(edit_id) = params.edit_id {
+        let edit = DbEdit::read_by_id(edit_id, &context)?;
+        if edit.article_id != article.id {
+            return Err(anyhow!("Edit does not belong to article").into());
+        }
+        edit.ap_id.into_inner()
+    } else {
+        article.ap_id.inner().clone()
+    };
+    let form = DbReportForm {
+        creator_id: user.person.id,
+        article_id: article.id,
+        edit_id: params.edit_id.filter(|_| params.comment_id.is_none()),
+        comment_id: params.comment_id,
+        reason,
+        ap_id: generate_activity_id(&context)?.to_string(),
+        local: true,
+    };
+    let report = DbReport::create(&form, &context)?;
+    if !article.local {
+        let instance = DbInstance::read(article.instance_id, &context)?;
+        Flag::send(&user.person, &report, object, &instance, &context).await?;
+    }
+    Ok(Json(report))
+}
+
+/// Reports for moderators, newest first.
+#[debug_handler]
+pub(in crate::backend::api) async fn list_reports(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    Query(params): Query<ListReportsParams>,
+) -> MyResult<Json<Paginated<ReportView>>> {
+    check_permission(&user, Permission::HandleReports, &context)?;
+    let include_closed = params.include_closed.unwrap_or_default();
+    let pagination = Pagination::new(params.limit, params.cursor.as_ref())?;
+    let reports = DbReport::list(include_closed, pagination, &context)?;
+    Ok(Json(pagination.page(reports)))
+}
+
+/// Close a report, either as resolved or as dismissed. The action on the reported content itself
+/// needs to be taken separately.
+#[debug_handler]
+pub(in crate::backend::api) async fn resolve_report(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    Form(params): Form<ResolveReportParams>,
+) -> MyResult<Json<DbReport>> {
+    check_permission(&user, Permission::HandleReports, &context)?;
+    let (status, action) = if params.dismiss {
+        (ReportStatus::Dismissed, ModlogAction::DismissReport)
+    } else {
+        (ReportStatus::Resolved, ModlogAction::ResolveReport)
+    };
+    let report = DbReport::update_status(params.report_id, status, user.person.id, &context)?;
+    let article = DbArticle::read(report.article_id, &context)?;
+    let form = DbModlogForm::article(&user.person, action, &article, Some(report.reason.clone()));
+    DbModlog::create(form, &context)?;
+    Ok(Json(report))
+}
@@ -1,22 +1,61 @@
-use super::{check_is_admin, empty_to_none};
+use super::{
+    authorization::{check_is_admin, check_is_owner, check_outranks_user, check_permission},
+    empty_to_none,
+    oidc::check_oidc_reauth,
+};
 use crate::{
     backend::{
-        database::{conflict::DbConflict, read_jwt_secret, IbisContext},
+        database::{conflict::DbConflict, modlog::DbModlogForm, read_jwt_secret, IbisContext},
+        federation::activities::{
+            block_user::BlockUser,
+            delete_user::DeleteUser,
+            submit_article_update,
+            undo_block_user::UndoBlockUser,
+        },
         utils::{
             error::MyResult,
+            generate_article_version,
+            pagination::Pagination,
+            totp::{build_totp, check_totp_token, generate_recovery_codes, generate_totp_secret},
             validate::{validate_display_name, validate_user_name},
         },
     },
     common::{
-        article::DbArticle,
+        article::{DbArticle, DbEdit},
+        comment::DbComment,
+        instance::DbInstance,
+        modlog::{DbModlog, ModlogAction},
+        pagination::Paginated,
+        report::DbReport,
         user::{
+            BanUserParams,
+            CreateApiTokenParams,
+            CreateApiTokenResponse,
+            DbApiToken,
+            DbLocalUser,
             DbPerson,
+            DeleteAccountParams,
+            DeleteApiTokenParams,
+            DeleteUserParams,
+            DisableTotpParams,
+            EnableTotpParams,
             GetUserParams,
+            GetUserRoleParams,
+            GetUserStatsParams,
             LocalUserView,
             LoginUserParams,
+            Permission,
             RegisterUserParams,
+            TotpRecoveryCodes,
+            TotpSetupResponse,
             UpdateUserParams,
+            UpdateUserRoleParams,
+            UserCommentView,
+            UserContributionsParams,
+            UserStats,
+            TOTP_REQUIRED_ERROR,
         },
+        ListNotificationsParams,
         Notification,
         SuccessResponse,
         AUTH_COOKIE,
@@ -28,7 +67,7 @@ use axum::{extract::Query, Extension, Form, Json};
 use axum_extra::extract::cookie::{Cookie, CookieJar, Expiration, SameSite};
 use axum_macros::debug_handler;
 use bcrypt::verify;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use futures::future::try_join_all;
 use jsonwebtoken::{
     decode,
@@ -54,7 +93,10 @@ struct Claims {
     pub exp: u64,
 }
 
-fn generate_login_token(person: &DbPerson, context: &Data<IbisContext>) -> MyResult<String> {
+pub(super) fn generate_login_token(
+    person: &DbPerson,
+    context: &Data<IbisContext>,
+) -> MyResult<String> {
     let hostname = context.domain().to_string();
     let claims = Claims {
         sub: person.username.clone(),
@@ -86,6 +128,9 @@ pub(in crate::backend::api) async fn register_user(
     if !context.config.options.registration_open {
         return Err(anyhow!("Registration is closed").into());
     }
+    if !context.config.options.password_registration {
+        return Err(anyhow!("Registration is only possible via OpenID Connect").into());
+    }
     validate_user_name(&params.username)?;
     let user = DbPerson::create_local(params.username, params.password, false, &context)?;
     let token = generate_login_token(&user.person, &context)?;
@@ -104,12 +149,22 @@ pub(in crate::backend::api) async fn login_user(
     if !valid {
         return Err(anyhow!("Invalid login").into());
     }
+    if user.person.is_banned() {
+        let reason = user.person.ban_reason.unwrap_or_default();
+        return Err(anyhow!("You are banned: {reason}").into());
+    }
+    if user.local_user.totp_enabled {
+        let Some(totp_token) = &params.totp_token else {
+            return Err(anyhow!(TOTP_REQUIRED_ERROR).into());
+        };
+        check_totp_token(&user, totp_token, &context)?;
+    }
     let token = generate_login_token(&user.person, &context)?;
     let jar = jar.add(create_cookie(token, &context));
     Ok((jar, Json(user)))
 }
 
-fn create_cookie(jwt: String, context: &Data<IbisContext>) -> Cookie<'static> {
+pub(super) fn create_cookie(jwt: String, context: &Data<IbisContext>) -> Cookie<'static> {
     let mut cookie = Cookie::build((AUTH_COOKIE, jwt));
 
     // Must not set cookie domain on localhost
@@ -150,11 +205,43 @@ pub(in crate::backend::api) async fn get_user(
     )?))
 }
 
+/// Totals over the edits and comments of a user.
+#[debug_handler]
+pub(in crate::backend::api) async fn user_stats(
+    Query(params): Query<GetUserStatsParams>,
+    context: Data<IbisContext>,
+) -> MyResult<Json<UserStats>> {
+    Ok(Json(UserStats::read(params.person_id, &context)?))
+}
+
+#[debug_handler]
+pub(in crate::backend::api) async fn user_comments(
+    Query(params): Query<UserContributionsParams>,
+    context: Data<IbisContext>,
+) -> MyResult<Json<Paginated<UserCommentView>>> {
+    let pagination = Pagination::new(params.limit, params.cursor.as_ref())?;
+    let comments = DbComment::read_for_creator(params.person_id, pagination, &context)?;
+    Ok(Json(pagination.page(comments)))
+}
+
+/// Articles which were created by the user.
+#[debug_handler]
+pub(in crate::backend::api) async fn user_articles(
+    Query(params): Query<UserContributionsParams>,
+    context: Data<IbisContext>,
+) -> MyResult<Json<Paginated<DbArticle>>> {
+    let pagination = Pagination::new(params.limit, params.cursor.as_ref())?;
+    let articles = DbArticle::read_created_by(params.person_id, pagination, &context)?;
+    Ok(Json(pagination.page(articles)))
+}
+
 #[debug_handler]
 pub(in crate::backend::api) async fn update_user_profile(
+    Extension(user): Extension<LocalUserView>,
     context: Data<IbisContext>,
     Form(mut params): Form<UpdateUserParams>,
 ) -> MyResult<Json<SuccessResponse>> {
+    check_is_owner(&user, params.person_id)?;
     empty_to_none(&mut params.display_name);
     empty_to_none(&mut params.bio);
     validate_display_name(&params.display_name)?;
@@ -162,34 +249,303 @@ pub(in crate::backend::api) async fn update_user_profile(
     Ok(Json(SuccessResponse::default()))
 }
 
+/// Delete the account of the logged in user. This needs to be confirmed, see [check_confirmation].
 #[debug_handler]
-pub(crate) async fn list_notifications(
+pub(in crate::backend::api) async fn delete_account(
     Extension(user): Extension<LocalUserView>,
     context: Data<IbisContext>,
-) -> MyResult<Json<Vec<Notification>>> {
-    let conflicts = DbConflict::list(&user.person, &context)?;
-    let conflicts: Vec<_> = try_join_all(conflicts.into_iter().map(|c| {
-        let data = context.reset_request_count();
-        async move { c.to_api_conflict(&data).await }
+    jar: CookieJar,
+    Form(params): Form<DeleteAccountParams>,
+) -> MyResult<(CookieJar, Json<SuccessResponse>)> {
+    check_confirmation(&user, &params.password, &jar, &context)?;
+    DeleteUser::send(&user.person, params.delete_comments, &context).await?;
+    DbPerson::delete_account(user.person.id, params.delete_comments, &context)?;
+    let jar = jar.remove(create_cookie(String::new(), &context));
+    Ok((jar, Json(SuccessResponse::default())))
+}
+
+/// Delete the account of a local user, who must have a lower role than the acting user.
+#[debug_handler]
+pub(in crate::backend::api) async fn delete_user(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    Form(params): Form<DeleteUserParams>,
+) -> MyResult<Json<SuccessResponse>> {
+    check_permission(&user, Permission::ManageUsers, &context)?;
+    let person = DbPerson::read(params.person_id, &context)?;
+    if !person.local {
+        return Err(anyhow!("Can only delete local users").into());
+    }
+    check_outranks_user(&user, &person, Permission::ManageUsers, &context)?;
+    DeleteUser::send(&person, params.delete_comments, &context).await?;
+    DbPerson::delete_account(person.id, params.delete_comments, &context)?;
+    let mut form = DbModlogForm::person(&user.person, ModlogAction::DeleteUser, &person, None);
+    // user doesn't exist anymore, only the name is kept
+    form.target_person_id = None;
+    DbModlog::create(form, &context)?;
+    Ok(Json(SuccessResponse::default()))
+}
+
+/// Ban or unban a user from the local instance. This works for local and remote users, and is
+/// federated to other instances.
+#[debug_handler]
+pub(in crate::backend::api) async fn ban_user(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    Form(mut params): Form<BanUserParams>,
+) -> MyResult<Json<DbPerson>> {
+    check_permission(&user, Permission::Ban, &context)?;
+    if params.person_id == user.person.id {
+        return Err(anyhow!("Cannot ban yourself").into());
+    }
+    let target = DbPerson::read(params.person_id, &context)?;
+    check_outranks_user(&user, &target, Permission::Ban, &context)?;
+    empty_to_none(&mut params.reason);
+    let person = DbPerson::update_ban(
+        target.id,
+        params.ban,
+        params.reason.clone(),
+        params.expires,
+        &context,
+    )?;
+    let action = if params.ban {
+        ModlogAction::BanUser
+    } else {
+        ModlogAction::UnbanUser
+    };
+    let form = DbModlogForm::person(&user.person, action, &person, params.reason);
+    DbModlog::create(form, &context)?;
+    if params.ban {
+        if params.remove_content {
+            let local_instance = DbInstance::read_local(&context)?;
+            DbComment::remove_for_creator(person.id, local_instance.id, &context)?;
+            revert_latest_edits(&person, &user.person, &context).await?;
+        }
+        BlockUser::send(&user.person, &person, params.remove_content, &context).await?;
+    } else {
+        UndoBlockUser::send(&user.person, &person, &context).await?;
+    }
+    Ok(Json(person))
+}
+
+/// Restore local articles to the last version before the user's edits. Articles are only changed
+/// if the user made the latest edit, and if there is an earlier version by someone else.
+async fn revert_latest_edits(
+    person: &DbPerson,
+    moderator: &DbPerson,
+    context: &Data<IbisContext>,
+) -> MyResult<()> {
+    for article in DbArticle::list_local_edited_by(person.id, context)? {
+        let edits = DbEdit::list_for_article(article.id, context)?;
+        let own_edits = edits
+            .iter()
+            .rev()
+            .take_while(|e| e.creator_id == person.id)
+            .count();
+        if own_edits == 0 || own_edits == edits.len() {
+            continue;
+        }
+        let previous_version = &edits[edits.len() - own_edits - 1].hash;
+        let text = generate_article_version(&edits, previous_version)?;
+        let latest_version = article.latest_edit_version(context)?;
+        let summary = format!("Revert edits by {}", person.username);
+        submit_article_update(
+            text,
+            summary,
+            latest_version,
+            &article,
+            moderator.id,
+            context,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Read the role and individual permissions of a local user, only for admins.
+#[debug_handler]
+pub(in crate::backend::api) async fn get_user_role(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    Query(params): Query<GetUserRoleParams>,
+) -> MyResult<Json<DbLocalUser>> {
+    check_is_admin(&user, &context)?;
+    Ok(Json(DbLocalUser::read_from_person(
+        params.person_id,
+        &context,
+    )?))
+}
+
+/// Promote or demote a local user, and grant individual permissions. Only for admins.
+#[debug_handler]
+pub(in crate::backend::api) async fn update_user_role(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    Form(params): Form<UpdateUserRoleParams>,
+) -> MyResult<Json<DbLocalUser>> {
+    check_is_admin(&user, &context)?;
+    // prevent admins from accidentally locking themselves out
+    if params.person_id == user.person.id {
+        return Err(anyhow!("Cannot change own role").into());
+    }
+    let person = DbPerson::read(params.person_id, &context)?;
+    check_outranks_user(&user, &person, Permission::ManageUsers, &context)?;
+    let local_user = DbLocalUser::update_role(&params, &context)?;
+    let form = DbModlogForm::person(&user.person, ModlogAction::UpdateUserRole, &person, None);
+    DbModlog::create(form, &context)?;
+    Ok(Json(local_user))
+}
+
+/// Generate a new TOTP secret for the user. Two factor authentication only becomes active after
+/// confirming a token with [enable_totp].
+#[debug_handler]
+pub(in crate::backend::api) async fn setup_totp(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+) -> MyResult<Json<TotpSetupResponse>> {
+    if user.local_user.totp_enabled {
+        return Err(anyhow!("Two factor authentication is already enabled").into());
+    }
+    let secret = generate_totp_secret();
+    let totp = build_totp(&secret, &user.person.username, context.domain())?;
+    DbLocalUser::update_totp_secret(user.local_user.id, Some(secret.clone()), &context)?;
+    Ok(Json(TotpSetupResponse {
+        secret,
+        otpauth_url: totp.get_url(),
     }))
-    .await?;
-    let mut notifications: Vec<_> = conflicts
+}
+
+/// Enable two factor authentication after checking that the authenticator app is set up
+/// correctly. Returns recovery codes which are only shown once.
+#[debug_handler]
+pub(in crate::backend::api) async fn enable_totp(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    Form(params): Form<EnableTotpParams>,
+) -> MyResult<Json<TotpRecoveryCodes>> {
+    if user.local_user.totp_enabled {
+        return Err(anyhow!("Two factor authentication is already enabled").into());
+    }
+    check_totp_token(&user, &params.totp_token, &context)?;
+    let (codes, hashes) = generate_recovery_codes();
+    DbLocalUser::update_totp_enabled(user.local_user.id, true, hashes, &context)?;
+    Ok(Json(TotpRecoveryCodes { codes }))
+}
+
+#[debug_handler]
+pub(in crate::backend::api) async fn disable_totp(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    jar: CookieJar,
+    Form(params): Form<DisableTotpParams>,
+) -> MyResult<Json<SuccessResponse>> {
+    check_confirmation(&user, &params.password, &jar, &context)?;
+    if !user.local_user.totp_enabled {
+        return Err(anyhow!("Two factor authentication is not enabled").into());
+    }
+    check_totp_token(&user, &params.totp_token, &context)?;
+    DbLocalUser::update_totp_enabled(user.local_user.id, false, vec![], &context)?;
+    DbLocalUser::update_totp_secret(user.local_user.id, None, &context)?;
+    Ok(Json(SuccessResponse::default()))
+}
+
+#[debug_handler]
+pub(in crate::backend::api) async fn create_api_token(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    Form(params): Form<CreateApiTokenParams>,
+) -> MyResult<Json<CreateApiTokenResponse>> {
+    if params.name.trim().is_empty() {
+        return Err(anyhow!("Api token name must not be empty").into());
+    }
+    let (api_token, token) = DbApiToken::create(user.local_user.id, params, &context)?;
+    Ok(Json(CreateApiTokenResponse { api_token, token }))
+}
+
+#[debug_handler]
+pub(in crate::backend::api) async fn list_api_tokens(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+) -> MyResult<Json<Vec<DbApiToken>>> {
+    Ok(Json(DbApiToken::list(user.local_user.id, &context)?))
+}
+
+#[debug_handler]
+pub(in crate::backend::api) async fn delete_api_token(
+    Extension(user): Extension<LocalUserView>,
+    context: Data<IbisContext>,
+    Form(params): Form<DeleteApiTokenParams>,
+) -> MyResult<Json<SuccessResponse>> {
+    DbApiToken::delete(params.id, user.local_user.id, &context)?;
+    Ok(Json(SuccessResponse::default()))
+}
+
+#[debug_handler]
+pub(crate) async fn list_notifications(
+    Extension(user): Extension<LocalUserView>,
+    Query(params): Query<ListNotificationsParams>,
+    context: Data<IbisContext>,
+) -> MyResult<Json<Paginated<Notification>>> {
+    let pagination = Pagination::new(params.limit, params.cursor.as_ref())?;
+    // Each source is sorted the same way, so the page can only contain items from the first
+    // `offset + limit` of each one.
+    let limit = pagination.offset + pagination.query_limit();
+    let mut pending: Vec<_> = DbConflict::list(&user.person, limit, &context)?
         .into_iter()
-        .flatten()
-        .map(Notification::EditConflict)
+        .map(PendingNotification::EditConflict)
         .collect();
-
-    if check_is_admin(&user).is_ok() {
-        let articles = DbArticle::list_approval_required(&context)?;
-        notifications.extend(
+    if check_permission(&user, Permission::ApproveArticle, &context).is_ok() {
+        let articles = DbArticle::list_approval_required(limit, &context)?;
+        pending.extend(
             articles
                 .into_iter()
-                .map(Notification::ArticleApprovalRequired),
+                .map(|a| PendingNotification::Ready(Notification::ArticleApprovalRequired(a))),
+        )
+    }
+    if check_permission(&user, Permission::HandleReports, &context).is_ok() {
+        let reports = DbReport::list_open_oldest(limit, &context)?;
+        pending.extend(
+            reports
+                .into_iter()
+                .map(|r| PendingNotification::Ready(Notification::Report(Box::new(r)))),
         )
     }
-    notifications.sort_by(|a, b| a.published().cmp(b.published()));
+    pending.sort_by(|a, b| a.published().cmp(b.published()));
+    let page = pagination.page_from_all(pending);
+
+    // Conflicts are only converted when they are shown, as this needs to fetch the article
+    let notifications = try_join_all(page.items.into_iter().map(|n| {
+        let data = context.reset_request_count();
+        async move {
+            MyResult::Ok(match n {
+                PendingNotification::EditConflict(c) => c
+                    .to_api_conflict(&data)
+                    .await?
+                    .map(Notification::EditConflict),
+                PendingNotification::Ready(n) => Some(n),
+            })
+        }
+    }))
+    .await?;
+    Ok(Json(Paginated {
+        items: notifications.into_iter().flatten().collect(),
+        next_cursor: page.next_cursor,
+    }))
+}
 
-    Ok(Json(notifications))
+/// Notification which is converted for the api only if it is on the requested page.
+enum PendingNotification {
+    EditConflict(DbConflict),
+    Ready(Notification),
+}
+
+impl PendingNotification {
+    fn published(&self) -> &DateTime<Utc> {
+        match self {
+            PendingNotification::EditConflict(c) => &c.published,
+            PendingNotification::Ready(n) => n.published(),
+        }
+    }
 }
 
 #[debug_handler]
@@ -197,13 +553,30 @@ pub(crate) async fn count_notifications(
     Extension(user): Extension<LocalUserView>,
     context: Data<IbisContext>,
 ) -> MyResult<Json<usize>> {
-    let mut count = 0;
-    let conflicts = DbConflict::list(&user.person, &context)?;
-    count += conflicts.len();
-    if check_is_admin(&user).is_ok() {
-        let articles = DbArticle::list_approval_required(&context)?;
-        count += articles.len();
+    let mut count = DbConflict::count(&user.person, &context)?;
+    if check_permission(&user, Permission::ApproveArticle, &context).is_ok() {
+        count += DbArticle::count_approval_required(&context)?;
+    }
+    if check_permission(&user, Permission::HandleReports, &context).is_ok() {
+        count += DbReport::count_open(&context)?;
     }
 
-    Ok(Json(count))
+    Ok(Json(count as usize))
+}
+
+/// Sensitive account changes need to be confirmed with the current password. Accounts which
+/// were created through OpenID Connect don't have a known password, so they can confirm by
+/// logging in with the provider again instead.
+fn check_confirmation(
+    user: &LocalUserView,
+    password: &str,
+    jar: &CookieJar,
+    context: &IbisContext,
+) -> MyResult<()> {
+    if check_oidc_reauth(jar, user, context)
+        || verify(password, &user.local_user.password_encrypted)?
+    {
+        return Ok(());
+    }
+    Err(anyhow!("Invalid password").into())
 }
@@ -0,0 +1,114 @@
+use crate::{
+    backend::{
+        database::{
+            schema::{api_token, local_user, person},
+            IbisContext,
+        },
+        utils::error::MyResult,
+    },
+    common::{
+        newtypes::{ApiTokenId, LocalUserId},
+        user::{CreateApiTokenParams, DbApiToken, DbPerson, LocalUserView},
+    },
+};
+use anyhow::anyhow;
+use chrono::Utc;
+use diesel::{delete, insert_into, update, ExpressionMethods, Insertable, QueryDsl, RunQueryDsl};
+use rand::{distributions::Alphanumeric, thread_rng, Rng};
+use sha2::{Digest, Sha256};
+use std::ops::DerefMut;
+
+/// Prefix for generated tokens, makes it easier to recognize them (eg by secret scanners)
+const TOKEN_PREFIX: &str = "ibis_";
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = api_token, check_for_backend(diesel::pg::Pg))]
+struct DbApiTokenForm {
+    local_user_id: LocalUserId,
+    name: String,
+    token_hash: String,
+    scope_read: bool,
+    scope_edit: bool,
+    scope_comment: bool,
+    scope_admin: bool,
+}
+
+impl DbApiToken {
+    /// Generate a new token. Only its hash is stored, so the plaintext token is returned
+    /// separately.
+    pub fn create(
+        local_user_id: LocalUserId,
+        params: CreateApiTokenParams,
+        context: &IbisContext,
+    ) -> MyResult<(Self, String)> {
+        let token: String = thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(40)
+            .map(char::from)
+            .collect();
+        let token = format!("{TOKEN_PREFIX}{token}");
+        let form = DbApiTokenForm {
+            local_user_id,
+            name: params.name,
+            token_hash: hash_token(&token),
+            scope_read: params.scope_read,
+            scope_edit: params.scope_edit,
+            scope_comment: params.scope_comment,
+            scope_admin: params.scope_admin,
+        };
+        let mut conn = context.db_pool.get()?;
+        let api_token = insert_into(api_token::table)
+            .values(form)
+            .get_result(conn.deref_mut())?;
+        Ok((api_token, token))
+    }
+
+    pub fn list(local_user_id: LocalUserId, context: &IbisContext) -> MyResult<Vec<Self>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(api_token::table
+            .filter(api_token::local_user_id.eq(local_user_id))
+            .order_by(api_token::published.desc())
+            .get_results(conn.deref_mut())?)
+    }
+
+    /// Revoke a token, it can only be deleted by its owner.
+    pub fn delete(
+        id: ApiTokenId,
+        local_user_id: LocalUserId,
+        context: &IbisContext,
+    ) -> MyResult<()> {
+        let mut conn = context.db_pool.get()?;
+        let deleted = delete(
+            api_token::table
+                .find(id)
+                .filter(api_token::local_user_id.eq(local_user_id)),
+        )
+        .execute(conn.deref_mut())?;
+        if deleted == 0 {
+            return Err(anyhow!("Api token not found").into());
+        }
+        Ok(())
+    }
+
+    /// Read the token and the user it belongs to, and update the last used timestamp.
+    pub fn validate(token: &str, context: &IbisContext) -> MyResult<(Self, LocalUserView)> {
+        let mut conn = context.db_pool.get()?;
+        let api_token: DbApiToken =
+            update(api_token::table.filter(api_token::token_hash.eq(hash_token(token))))
+                .set(api_token::last_used.eq(Utc::now()))
+                .get_result(conn.deref_mut())?;
+        let username: String = local_user::table
+            .inner_join(person::table)
+            .filter(local_user::id.eq(api_token.local_user_id))
+            .select(person::username)
+            .get_result(conn.deref_mut())?;
+        let local_user = DbPerson::read_local_from_name(&username, context)?;
+        Ok((api_token, local_user))
+    }
+}
+
+fn hash_token(token: &str) -> String {
+    let mut sha256 = Sha256::new();
+    sha256.update(token);
+    hex::encode(sha256.finalize())
+}
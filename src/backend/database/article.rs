@@ -1,33 +1,47 @@
 use crate::{
     backend::{
         database::{
-            schema::{article, edit, instance},
+            schema::{article, article_view, edit, instance, person},
             IbisContext,
         },
         federation::objects::edits_collection::DbEditCollection,
-        utils::error::MyResult,
+        utils::{error::MyResult, pagination::Pagination},
     },
     common::{
-        article::{DbArticle, DbArticleView, EditVersion},
+        article::{
+            ArticleSuggestion,
+            DbArticle,
+            DbArticleView,
+            EditVersion,
+            SearchArticleParams,
+            SearchArticleResult,
+            ARTICLE_LANGUAGES,
+        },
         comment::DbComment,
         instance::DbInstance,
-        newtypes::{ArticleId, InstanceId},
+        newtypes::{ArticleId, InstanceId, PersonId},
     },
 };
 use activitypub_federation::fetch::{collection_id::CollectionId, object_id::ObjectId};
+use chrono::Utc;
 use diesel::{
-    dsl::max,
+    dsl::{self, sql},
     insert_into,
+    sql_query,
+    sql_types::{BigInt, Bool, Integer, Nullable, Text, Timestamptz},
     AsChangeset,
-    BoolExpressionMethods,
     ExpressionMethods,
     Insertable,
-    PgTextExpressionMethods,
     QueryDsl,
     RunQueryDsl,
 };
 use std::ops::DerefMut;
 
+/// Number of views of an article in the last seven days, including today
+const WEEKLY_VIEWS: &str = "(SELECT sum(views) FROM article_view \
+    WHERE article_view.article_id = article.id \
+    AND article_view.day > (now() AT TIME ZONE 'UTC')::date - 7)";
+
 #[derive(Debug, Clone, Insertable, AsChangeset)]
 #[diesel(table_name = article, check_for_backend(diesel::pg::Pg))]
 pub struct DbArticleForm {
@@ -38,6 +52,7 @@ pub struct DbArticleForm {
     pub local: bool,
     pub protected: bool,
     pub approved: bool,
+    pub language: Option<String>,
 }
 
 // TODO: get rid of unnecessary methods
@@ -103,12 +118,12 @@ impl DbArticle {
             .inner_join(instance::table)
             .into_boxed();
         let (article, instance): (DbArticle, DbInstance) = query.get_result(conn.deref_mut())?;
-        let comments = DbComment::read_for_article(article.id, context)?;
+        let comment_count = DbComment::count_for_article(article.id, context)?;
         let latest_version = article.latest_edit_version(context)?;
         Ok(DbArticleView {
             article,
             instance,
-            comments,
+            comment_count,
             latest_version,
         })
     }
@@ -131,12 +146,12 @@ impl DbArticle {
             };
             query.get_result(conn.deref_mut())?
         };
-        let comments = DbComment::read_for_article(article.id, context)?;
+        let comment_count = DbComment::count_for_article(article.id, context)?;
         let latest_version = article.latest_edit_version(context)?;
         Ok(DbArticleView {
             article,
             instance,
-            comments,
+            comment_count,
             latest_version,
         })
     }
@@ -148,21 +163,30 @@ impl DbArticle {
             .get_result(conn.deref_mut())?)
     }
 
-    /// Read all articles, ordered by most recently edited first.
+    /// Read all articles, ordered by most recently edited first. With `most_viewed` they are
+    /// ordered by views in the last week instead, and articles without views are left out.
+    /// Without pagination all articles are returned, this is used for the federated articles
+    /// collection.
     ///
     /// TODO: Should get rid of only_local param and rely on instance_id
     pub fn read_all(
         only_local: Option<bool>,
         instance_id: Option<InstanceId>,
+        hide_bots: Option<bool>,
+        most_viewed: Option<bool>,
+        pagination: Option<Pagination>,
         context: &IbisContext,
     ) -> MyResult<Vec<Self>> {
         let mut conn = context.db_pool.get()?;
         let mut query = article::table
-            .inner_join(edit::table)
+            .inner_join(edit::table.inner_join(person::table))
             .inner_join(instance::table)
             .filter(article::dsl::approved.eq(true))
             .group_by(article::dsl::id)
-            .order_by(max(edit::dsl::published).desc())
+            .order_by((
+                dsl::max(edit::dsl::published).desc(),
+                article::dsl::id.desc(),
+            ))
             .select(article::all_columns)
             .into_boxed();
 
@@ -172,25 +196,147 @@ impl DbArticle {
         if let Some(instance_id) = instance_id {
             query = query.filter(instance::dsl::id.eq(instance_id));
         }
+        if let Some(true) = hide_bots {
+            query = query.filter(person::dsl::bot.eq(false));
+        }
+        if let Some(true) = most_viewed {
+            query = query
+                .filter(sql::<Bool>(&format!("{WEEKLY_VIEWS} > 0")))
+                .order_by((
+                    sql::<Nullable<BigInt>>(WEEKLY_VIEWS).desc(),
+                    article::dsl::id.desc(),
+                ));
+        }
+        if let Some(pagination) = pagination {
+            query = query
+                .limit(pagination.query_limit())
+                .offset(pagination.offset);
+        }
         Ok(query.get_results(&mut conn)?)
     }
 
-    pub fn search(query: &str, context: &IbisContext) -> MyResult<Vec<Self>> {
+    /// Count a view of the article for the current day.
+    pub fn record_view(id: ArticleId, context: &IbisContext) -> MyResult<()> {
+        let mut conn = context.db_pool.get()?;
+        insert_into(article_view::table)
+            .values((
+                article_view::article_id.eq(id),
+                article_view::day.eq(Utc::now().date_naive()),
+                article_view::views.eq(1),
+            ))
+            .on_conflict((article_view::article_id, article_view::day))
+            .do_update()
+            .set(article_view::views.eq(article_view::views + 1))
+            .execute(conn.deref_mut())?;
+        Ok(())
+    }
+
+    /// Approved articles where the first edit was made by the user, newest first.
+    pub fn read_created_by(
+        creator_id: PersonId,
+        pagination: Pagination,
+        context: &IbisContext,
+    ) -> MyResult<Vec<Self>> {
         let mut conn = context.db_pool.get()?;
-        let replaced = query
-            .replace('%', "\\%")
-            .replace('_', "\\_")
-            .replace(' ', "%");
-        let replaced = format!("%{replaced}%");
         Ok(article::table
-            .filter(
-                article::dsl::title
-                    .ilike(&replaced)
-                    .or(article::dsl::text.ilike(&replaced)),
-            )
+            .inner_join(edit::table)
+            .filter(article::dsl::approved.eq(true))
+            .filter(edit::dsl::creator_id.eq(creator_id))
+            .filter(edit::dsl::previous_version_id.eq(EditVersion::default()))
+            .order_by((edit::dsl::published.desc(), article::dsl::id.desc()))
+            .select(article::all_columns)
+            .limit(pagination.query_limit())
+            .offset(pagination.offset)
             .get_results(conn.deref_mut())?)
     }
 
+    /// Full text search over title and text of approved articles, ordered by relevance.
+    /// Filters from `params` are applied, except for `author` which needs to be resolved to
+    /// `author_id` by the caller.
+    pub fn search(
+        params: &SearchArticleParams,
+        author_id: Option<PersonId>,
+        pagination: Pagination,
+        context: &IbisContext,
+    ) -> MyResult<Vec<SearchArticleResult>> {
+        let mut conn = context.db_pool.get()?;
+        // Combine the query parsed in all supported languages, so that the index can be used to
+        // find candidates. The exact match and rank use the language of each article.
+        let any_language_query = ARTICLE_LANGUAGES
+            .iter()
+            .map(|(code, _)| format!("websearch_to_tsquery(article_search_config('{code}'), $1)"))
+            .fold(
+                "websearch_to_tsquery('simple', $1)".to_string(),
+                |acc, q| format!("{acc} || {q}"),
+            );
+        let sql = format!(
+            "SELECT article.id, article.title, article.ap_id, article.instance_id, article.local,
+                ts_headline(article_search_config(article.language), article.text, query,
+                    'MaxFragments=2, MaxWords=20, MinWords=5') AS snippet,
+                ts_rank(article_search.search_vector, query) AS rank,
+                instance.domain AS instance_domain
+            FROM article
+            INNER JOIN article_search ON article_search.article_id = article.id
+            INNER JOIN instance ON instance.id = article.instance_id
+            CROSS JOIN LATERAL websearch_to_tsquery(article_search_config(article.language), $1)
+                AS query
+            CROSS JOIN LATERAL (
+                SELECT max(edit.published) AS published FROM edit
+                WHERE edit.article_id = article.id) AS last_edit
+            WHERE article.approved
+                AND article_search.search_vector @@ ({any_language_query})
+                AND article_search.search_vector @@ query
+                AND ($4::int IS NULL OR article.instance_id = $4)
+                AND ($5::bool IS NULL OR article.local = $5)
+                AND ($6::bool IS NULL OR article.protected = $6)
+                AND ($7::int IS NULL OR EXISTS (
+                    SELECT 1 FROM edit
+                    WHERE edit.article_id = article.id AND edit.creator_id = $7))
+                AND ($8::timestamptz IS NULL OR last_edit.published >= $8)
+                AND ($9::timestamptz IS NULL OR last_edit.published < $9)
+            ORDER BY rank DESC, article.id
+            LIMIT $2 OFFSET $3"
+        );
+        Ok(sql_query(sql)
+            .bind::<Text, _>(&params.query)
+            .bind::<BigInt, _>(pagination.query_limit())
+            .bind::<BigInt, _>(pagination.offset)
+            .bind::<Nullable<Integer>, _>(params.instance_id.map(|i| i.0))
+            .bind::<Nullable<Bool>, _>(params.local)
+            .bind::<Nullable<Bool>, _>(params.protected)
+            .bind::<Nullable<Integer>, _>(author_id.map(|p| p.0))
+            .bind::<Nullable<Timestamptz>, _>(params.edited_after)
+            .bind::<Nullable<Timestamptz>, _>(params.edited_before)
+            .get_results(conn.deref_mut())?)
+    }
+
+    /// Titles which start with the query, followed by titles which are similar to it (eg because
+    /// of a typo).
+    pub fn suggest(
+        query: &str,
+        limit: i64,
+        context: &IbisContext,
+    ) -> MyResult<Vec<ArticleSuggestion>> {
+        let mut conn = context.db_pool.get()?;
+        let prefix = query
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            // titles are stored with underscores instead of spaces
+            .replace(['_', ' '], "\\_");
+        let prefix = format!("{prefix}%");
+        Ok(sql_query(
+            "SELECT id, title, ap_id, local
+            FROM article
+            WHERE approved AND (title ILIKE $1 OR title % $2)
+            ORDER BY title ILIKE $1 DESC, similarity(title, $2) DESC, title
+            LIMIT $3",
+        )
+        .bind::<Text, _>(prefix)
+        .bind::<Text, _>(query)
+        .bind::<BigInt, _>(limit)
+        .get_results(conn.deref_mut())?)
+    }
+
     pub fn latest_edit_version(&self, context: &IbisContext) -> MyResult<EditVersion> {
         let mut conn = context.db_pool.get()?;
         let latest_version: Option<EditVersion> = edit::table
@@ -206,14 +352,37 @@ impl DbArticle {
         }
     }
 
-    pub fn list_approval_required(context: &IbisContext) -> MyResult<Vec<Self>> {
+    /// Oldest unapproved articles first.
+    pub fn list_approval_required(limit: i64, context: &IbisContext) -> MyResult<Vec<Self>> {
         let mut conn = context.db_pool.get()?;
-        let query = article::table
-            .group_by(article::dsl::id)
+        Ok(article::table
             .filter(article::dsl::approved.eq(false))
             .select(article::all_columns)
-            .into_boxed();
+            .order_by((article::dsl::published.asc(), article::dsl::id.asc()))
+            .limit(limit)
+            .get_results(&mut conn)?)
+    }
 
-        Ok(query.get_results(&mut conn)?)
+    pub fn count_approval_required(context: &IbisContext) -> MyResult<i64> {
+        let mut conn = context.db_pool.get()?;
+        Ok(article::table
+            .filter(article::dsl::approved.eq(false))
+            .count()
+            .get_result(&mut conn)?)
+    }
+
+    /// Local articles which were edited by the given user.
+    pub fn list_local_edited_by(
+        creator_id: PersonId,
+        context: &IbisContext,
+    ) -> MyResult<Vec<Self>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(article::table
+            .inner_join(edit::table)
+            .filter(edit::dsl::creator_id.eq(creator_id))
+            .filter(article::dsl::local.eq(true))
+            .group_by(article::dsl::id)
+            .select(article::all_columns)
+            .get_results(conn.deref_mut())?)
     }
 }
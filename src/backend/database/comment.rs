@@ -1,27 +1,33 @@
 use super::{
-    schema::{comment, person},
+    schema::{article, comment, comment_vote, person},
     IbisContext,
 };
 use crate::{
-    backend::utils::error::MyResult,
+    backend::utils::{error::MyResult, pagination::Pagination},
     common::{
-        comment::{DbComment, DbCommentView},
-        newtypes::{ArticleId, CommentId, PersonId},
-        user::DbPerson,
+        article::DbArticle,
+        comment::{CommentSort, DbComment, DbCommentView, DbCommentVote},
+        newtypes::{ArticleId, CommentId, InstanceId, PersonId},
+        user::{DbPerson, UserCommentView},
     },
 };
 use activitypub_federation::fetch::object_id::ObjectId;
 use chrono::{DateTime, Utc};
 use diesel::{
+    delete,
     dsl::insert_into,
+    sql_query,
+    sql_types::{Array, BigInt, Bool, Integer},
     update,
     AsChangeset,
     ExpressionMethods,
     Insertable,
+    OptionalExtension,
     QueryDsl,
+    QueryableByName,
     RunQueryDsl,
 };
-use std::ops::DerefMut;
+use std::{collections::HashMap, ops::DerefMut};
 
 #[derive(Insertable, AsChangeset, Debug)]
 #[diesel(table_name = comment, check_for_backend(diesel::pg::Pg))]
@@ -45,6 +51,7 @@ pub struct DbCommentUpdateForm {
     pub deleted: Option<bool>,
     pub ap_id: Option<ObjectId<DbComment>>,
     pub updated: Option<DateTime<Utc>>,
+    pub removed: Option<bool>,
 }
 
 impl DbComment {
@@ -65,7 +72,35 @@ impl DbComment {
             .set(form)
             .get_result(conn.deref_mut())?;
         let creator = DbPerson::read(comment.creator_id, context)?;
-        Ok(DbCommentView { comment, creator })
+        let (upvotes, downvotes) = DbCommentVote::count(comment.id, context)?;
+        Ok(DbCommentView {
+            comment,
+            creator,
+            upvotes,
+            downvotes,
+            my_vote: None,
+        })
+    }
+
+    /// Remove all comments which the user wrote on articles of the given instance. Like removal
+    /// by a moderator, the content is only hidden so that the comments can be restored.
+    pub fn remove_for_creator(
+        creator_id: PersonId,
+        instance_id: InstanceId,
+        context: &IbisContext,
+    ) -> MyResult<()> {
+        let mut conn = context.db_pool.get()?;
+        let articles = article::table
+            .filter(article::instance_id.eq(instance_id))
+            .select(article::id);
+        update(
+            comment::table
+                .filter(comment::creator_id.eq(creator_id))
+                .filter(comment::article_id.eq_any(articles)),
+        )
+        .set((comment::removed.eq(true), comment::updated.eq(Utc::now())))
+        .execute(conn.deref_mut())?;
+        Ok(())
     }
 
     pub fn create_or_update(form: DbCommentInsertForm, context: &IbisContext) -> MyResult<Self> {
@@ -91,7 +126,14 @@ impl DbComment {
             .find(id)
             .get_result::<Self>(conn.deref_mut())?;
         let creator = DbPerson::read(comment.creator_id, context)?;
-        Ok(DbCommentView { comment, creator })
+        let (upvotes, downvotes) = DbCommentVote::count(comment.id, context)?;
+        Ok(DbCommentView {
+            comment,
+            creator,
+            upvotes,
+            downvotes,
+            my_vote: None,
+        })
     }
 
     pub fn read_from_ap_id(ap_id: &ObjectId<DbComment>, context: &IbisContext) -> MyResult<Self> {
@@ -101,27 +143,213 @@ impl DbComment {
             .get_result(conn.deref_mut())?)
     }
 
-    pub fn read_for_article(
+    pub fn count_for_article(article_id: ArticleId, context: &IbisContext) -> MyResult<i64> {
+        let mut conn = context.db_pool.get()?;
+        Ok(comment::table
+            .filter(comment::article_id.eq(article_id))
+            .filter(comment::deleted.eq(false))
+            .filter(comment::removed.eq(false))
+            .count()
+            .get_result(conn.deref_mut())?)
+    }
+
+    /// Comments written by the user which are not deleted or removed, newest first.
+    pub fn read_for_creator(
+        creator_id: PersonId,
+        pagination: Pagination,
+        context: &IbisContext,
+    ) -> MyResult<Vec<UserCommentView>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(comment::table
+            .inner_join(article::table)
+            .filter(comment::creator_id.eq(creator_id))
+            .filter(comment::deleted.eq(false))
+            .filter(comment::removed.eq(false))
+            .filter(article::approved.eq(true))
+            .order_by((comment::published.desc(), comment::id.desc()))
+            .limit(pagination.query_limit())
+            .offset(pagination.offset)
+            .get_results::<(DbComment, DbArticle)>(conn.deref_mut())?
+            .into_iter()
+            .map(|(comment, article)| UserCommentView { comment, article })
+            .collect())
+    }
+
+    /// Replies to each of the given parents, where `None` stands for the top level comments.
+    /// After skipping `offset` replies of each parent, at most `limit` are returned per parent,
+    /// together with the number of replies to each returned comment.
+    pub fn read_replies(
         article_id: ArticleId,
+        parents: &[Option<CommentId>],
+        sort: CommentSort,
+        offset: i64,
+        limit: i64,
         context: &IbisContext,
-    ) -> MyResult<Vec<DbCommentView>> {
+    ) -> MyResult<Vec<(DbCommentView, i64)>> {
         let mut conn = context.db_pool.get()?;
-        let comments = comment::table
+        let order = match sort {
+            CommentSort::New => "comment.published DESC, comment.id DESC",
+            CommentSort::Top => {
+                "(SELECT coalesce(sum(score), 0) FROM comment_vote
+                    WHERE comment_vote.comment_id = comment.id) DESC,
+                comment.published DESC, comment.id DESC"
+            }
+        };
+        let sql = format!(
+            "SELECT id, reply_count FROM (
+                SELECT comment.id,
+                    row_number() OVER (PARTITION BY comment.parent_id ORDER BY {order}) AS rank,
+                    (SELECT count(*) FROM comment AS reply WHERE reply.parent_id = comment.id)
+                        AS reply_count
+                FROM comment
+                WHERE comment.article_id = $1
+                    AND (comment.parent_id = ANY($2) OR ($3 AND comment.parent_id IS NULL))
+            ) AS ranked
+            WHERE rank > $4 AND rank <= $4 + $5
+            ORDER BY rank"
+        );
+        let parent_ids: Vec<i32> = parents.iter().flatten().map(|p| p.0).collect();
+        let ranked: Vec<RankedReply> = sql_query(sql)
+            .bind::<Integer, _>(article_id.0)
+            .bind::<Array<Integer>, _>(parent_ids)
+            .bind::<Bool, _>(parents.contains(&None))
+            .bind::<BigInt, _>(offset)
+            .bind::<BigInt, _>(limit)
+            .get_results(conn.deref_mut())?;
+
+        let ids: Vec<_> = ranked.iter().map(|r| CommentId(r.id)).collect();
+        let mut comments: HashMap<CommentId, (DbComment, DbPerson)> = comment::table
             .inner_join(person::table)
-            .filter(comment::article_id.eq(article_id))
-            .order_by(comment::published.desc())
-            .get_results::<(DbComment, DbPerson)>(conn.deref_mut())?;
+            .filter(comment::id.eq_any(&ids))
+            .get_results::<(DbComment, DbPerson)>(conn.deref_mut())?
+            .into_iter()
+            .map(|(comment, creator)| (comment.id, (comment, creator)))
+            .collect();
+        let mut votes = DbCommentVote::count_for_comments(&ids, context)?;
 
-        // Clear content of deleted comments. comments themselves are returned
+        // Clear content of deleted and removed comments. comments themselves are returned
         // so that tree can be rendered.
-        Ok(comments
+        Ok(ranked
             .into_iter()
-            .map(|(mut comment, creator)| {
-                if comment.deleted {
+            .filter_map(|r| {
+                let (mut comment, creator) = comments.remove(&CommentId(r.id))?;
+                if comment.deleted || comment.removed {
                     comment.content = String::new()
                 };
-                DbCommentView { comment, creator }
+                let (upvotes, downvotes) = votes.remove(&comment.id).unwrap_or_default();
+                let view = DbCommentView {
+                    comment,
+                    creator,
+                    upvotes,
+                    downvotes,
+                    my_vote: None,
+                };
+                Some((view, r.reply_count))
             })
             .collect())
     }
 }
+
+#[derive(QueryableByName)]
+struct RankedReply {
+    #[diesel(sql_type = Integer)]
+    id: i32,
+    #[diesel(sql_type = BigInt)]
+    reply_count: i64,
+}
+
+#[derive(Insertable, AsChangeset, Debug)]
+#[diesel(table_name = comment_vote, check_for_backend(diesel::pg::Pg))]
+pub struct DbCommentVoteForm {
+    pub person_id: PersonId,
+    pub comment_id: CommentId,
+    pub score: i16,
+}
+
+impl DbCommentVote {
+    pub fn create_or_update(form: DbCommentVoteForm, context: &IbisContext) -> MyResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(insert_into(comment_vote::table)
+            .values(&form)
+            .on_conflict((comment_vote::person_id, comment_vote::comment_id))
+            .do_update()
+            .set(&form)
+            .get_result(conn.deref_mut())?)
+    }
+
+    pub fn read(
+        person_id: PersonId,
+        comment_id: CommentId,
+        context: &IbisContext,
+    ) -> MyResult<Option<Self>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(comment_vote::table
+            .find((person_id, comment_id))
+            .get_result(conn.deref_mut())
+            .optional()?)
+    }
+
+    pub fn delete(
+        person_id: PersonId,
+        comment_id: CommentId,
+        context: &IbisContext,
+    ) -> MyResult<()> {
+        let mut conn = context.db_pool.get()?;
+        delete(comment_vote::table.find((person_id, comment_id))).execute(conn.deref_mut())?;
+        Ok(())
+    }
+
+    /// Returns number of upvotes and downvotes.
+    pub fn count(comment_id: CommentId, context: &IbisContext) -> MyResult<(i64, i64)> {
+        let mut conn = context.db_pool.get()?;
+        let scores = comment_vote::table
+            .filter(comment_vote::comment_id.eq(comment_id))
+            .select(comment_vote::score)
+            .get_results::<i16>(conn.deref_mut())?;
+        let upvotes = scores.iter().filter(|s| **s > 0).count() as i64;
+        Ok((upvotes, scores.len() as i64 - upvotes))
+    }
+
+    /// Returns number of upvotes and downvotes for each of the given comments which has votes.
+    pub fn count_for_comments(
+        comment_ids: &[CommentId],
+        context: &IbisContext,
+    ) -> MyResult<HashMap<CommentId, (i64, i64)>> {
+        let mut conn = context.db_pool.get()?;
+        let scores = comment_vote::table
+            .filter(comment_vote::comment_id.eq_any(comment_ids))
+            .select((comment_vote::comment_id, comment_vote::score))
+            .get_results::<(CommentId, i16)>(conn.deref_mut())?;
+        let mut counts: HashMap<CommentId, (i64, i64)> = HashMap::new();
+        for (comment_id, score) in scores {
+            let count = counts.entry(comment_id).or_default();
+            if score > 0 {
+                count.0 += 1;
+            } else {
+                count.1 += 1;
+            }
+        }
+        Ok(counts)
+    }
+
+    /// Set `my_vote` for comments which the given user voted on.
+    pub fn fill_my_votes(
+        comments: &mut [DbCommentView],
+        person_id: PersonId,
+        context: &IbisContext,
+    ) -> MyResult<()> {
+        let mut conn = context.db_pool.get()?;
+        let ids: Vec<_> = comments.iter().map(|c| c.comment.id).collect();
+        let votes: HashMap<CommentId, i16> = comment_vote::table
+            .filter(comment_vote::person_id.eq(person_id))
+            .filter(comment_vote::comment_id.eq_any(ids))
+            .select((comment_vote::comment_id, comment_vote::score))
+            .get_results::<(CommentId, i16)>(conn.deref_mut())?
+            .into_iter()
+            .collect();
+        for c in comments {
+            c.my_vote = votes.get(&c.comment.id).copied();
+        }
+        Ok(())
+    }
+}
@@ -64,13 +64,24 @@ impl DbConflict {
             .get_result(conn.deref_mut())?)
     }
 
-    pub fn list(person: &DbPerson, context: &IbisContext) -> MyResult<Vec<Self>> {
+    /// Oldest conflicts of the user first.
+    pub fn list(person: &DbPerson, limit: i64, context: &IbisContext) -> MyResult<Vec<Self>> {
         let mut conn = context.db_pool.get()?;
         Ok(conflict::table
             .filter(conflict::dsl::creator_id.eq(person.id))
+            .order_by((conflict::dsl::published.asc(), conflict::dsl::id.asc()))
+            .limit(limit)
             .get_results(conn.deref_mut())?)
     }
 
+    pub fn count(person: &DbPerson, context: &IbisContext) -> MyResult<i64> {
+        let mut conn = context.db_pool.get()?;
+        Ok(conflict::table
+            .filter(conflict::dsl::creator_id.eq(person.id))
+            .count()
+            .get_result(conn.deref_mut())?)
+    }
+
     /// Delete merge conflict which was created by specific user
     pub fn delete(id: ConflictId, creator_id: PersonId, context: &IbisContext) -> MyResult<()> {
         let mut conn = context.db_pool.get()?;
@@ -1,12 +1,12 @@
 use crate::{
     backend::{
         database::schema::{article, edit, person},
-        utils::error::MyResult,
+        utils::{error::MyResult, pagination::Pagination},
         IbisContext,
     },
     common::{
-        article::{DbArticle, DbEdit, EditVersion, EditView},
-        newtypes::{ArticleId, PersonId},
+        article::{DbArticle, DbEdit, EditVersion, EditView, RecentChangesParams},
+        newtypes::{ArticleId, EditId, PersonId},
         user::LocalUserView,
     },
 };
@@ -94,6 +94,11 @@ impl DbEdit {
             .get_result(conn.deref_mut())?)
     }
 
+    pub fn read_by_id(id: EditId, context: &IbisContext) -> MyResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(edit::table.find(id).get_result(conn.deref_mut())?)
+    }
+
     pub fn read_from_ap_id(ap_id: &ObjectId<DbEdit>, context: &IbisContext) -> MyResult<Self> {
         let mut conn = context.db_pool.get()?;
         Ok(edit::table
@@ -111,6 +116,8 @@ impl DbEdit {
 
     pub fn view(
         params: ViewEditParams,
+        hide_bots: bool,
+        pagination: Pagination,
         user: &Option<LocalUserView>,
         context: &IbisContext,
     ) -> MyResult<Vec<EditView>> {
@@ -123,12 +130,59 @@ impl DbEdit {
             .filter(not(edit::pending).or(edit::creator_id.eq(person_id)))
             .into_boxed();
 
-        let query = match params {
+        let mut query = match params {
             ViewEditParams::PersonId(person_id) => query.filter(edit::creator_id.eq(person_id)),
             ViewEditParams::ArticleId(article_id) => query.filter(edit::article_id.eq(article_id)),
         };
+        if hide_bots {
+            query = query.filter(person::bot.eq(false));
+        }
 
-        Ok(query.order(edit::published).get_results(conn.deref_mut())?)
+        Ok(query
+            .order((edit::published.desc(), edit::id.desc()))
+            .limit(pagination.query_limit())
+            .offset(pagination.offset)
+            .get_results(conn.deref_mut())?)
+    }
+
+    /// Latest edits of all articles, for patrolling. With `show_pending` all pending edits are
+    /// included, otherwise only those of the user.
+    pub fn recent_changes(
+        params: &RecentChangesParams,
+        pagination: Pagination,
+        user: &Option<LocalUserView>,
+        show_pending: bool,
+        context: &IbisContext,
+    ) -> MyResult<Vec<EditView>> {
+        let mut conn = context.db_pool.get()?;
+        let person_id = user.as_ref().map(|u| u.person.id).unwrap_or(PersonId(-1));
+        let mut query = edit::table
+            .inner_join(article::table)
+            .inner_join(person::table)
+            .filter(article::approved.eq(true))
+            .into_boxed();
+        if !show_pending {
+            // only the creator can view pending edits
+            query = query.filter(not(edit::pending).or(edit::creator_id.eq(person_id)));
+        }
+        if let Some(local) = params.local {
+            query = query.filter(article::local.eq(local));
+        }
+        if params.hide_bots.unwrap_or_default() {
+            query = query.filter(person::bot.eq(false));
+        }
+        if let Some(pending) = params.pending {
+            query = query.filter(edit::pending.eq(pending));
+        }
+        if params.new_articles.unwrap_or_default() {
+            query = query.filter(edit::previous_version_id.eq(EditVersion::default()));
+        }
+
+        Ok(query
+            .order((edit::published.desc(), edit::id.desc()))
+            .limit(pagination.query_limit())
+            .offset(pagination.offset)
+            .get_results(conn.deref_mut())?)
     }
 }
 
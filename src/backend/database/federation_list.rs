@@ -0,0 +1,213 @@
+use crate::{
+    backend::{
+        database::{
+            domain_like_pattern,
+            schema::{article, comment, federation_list, federation_list_import, instance, person},
+            DbPool,
+            IbisContext,
+        },
+        utils::{config::IbisConfig, error::MyResult},
+    },
+    common::{instance::DbFederationListEntry, newtypes::InstanceId, utils::url_domain},
+};
+use anyhow::anyhow;
+use chrono::Utc;
+use diesel::{
+    delete,
+    insert_into,
+    update,
+    AsChangeset,
+    Connection,
+    ExpressionMethods,
+    Insertable,
+    OptionalExtension,
+    QueryDsl,
+    RunQueryDsl,
+    TextExpressionMethods,
+};
+use std::{
+    collections::HashSet,
+    ops::DerefMut,
+    sync::{Arc, RwLock},
+};
+use url::Url;
+
+#[derive(Debug, Clone, Insertable, AsChangeset)]
+#[diesel(table_name = federation_list, check_for_backend(diesel::pg::Pg))]
+pub struct DbFederationListForm {
+    pub domain: String,
+    pub blocked: bool,
+    pub reason: Option<String>,
+}
+
+impl DbFederationListEntry {
+    /// Convert user input like `https://example.com/` or `Example.com:8080` to the domain format
+    /// which is used for instances, so that entries match incoming urls.
+    pub fn normalize_domain(input: &str) -> Option<String> {
+        let input = input.trim().to_lowercase();
+        let url = if input.contains("://") {
+            Url::parse(&input)
+        } else {
+            Url::parse(&format!("http://{input}"))
+        };
+        url.ok()
+            .filter(|u| u.host_str().is_some_and(|h| !h.is_empty()))
+            .map(|u| url_domain(&u))
+    }
+
+    pub fn create_or_update(form: DbFederationListForm, context: &IbisContext) -> MyResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        let entry = insert_into(federation_list::table)
+            .values(&form)
+            .on_conflict(federation_list::domain)
+            .do_update()
+            .set(&form)
+            .get_result(conn.deref_mut())?;
+        context.federation_list.invalidate();
+        Ok(entry)
+    }
+
+    pub fn list(context: &IbisContext) -> MyResult<Vec<Self>> {
+        Self::list_from_pool(&context.db_pool)
+    }
+
+    fn list_from_pool(db_pool: &DbPool) -> MyResult<Vec<Self>> {
+        let mut conn = db_pool.get()?;
+        Ok(federation_list::table
+            .order_by(federation_list::domain)
+            .get_results(conn.deref_mut())?)
+    }
+
+    pub fn delete(domain: &str, context: &IbisContext) -> MyResult<()> {
+        let mut conn = context.db_pool.get()?;
+        let deleted = delete(federation_list::table.find(domain)).execute(conn.deref_mut())?;
+        if deleted == 0 {
+            return Err(anyhow!("Domain is not in federation list").into());
+        }
+        context.federation_list.invalidate();
+        Ok(())
+    }
+
+    /// Copy allowlist and blocklist from the config file into the database. This only happens
+    /// once, afterwards the database is the source of truth and changes made by admins are kept.
+    pub fn import_config(config: &IbisConfig, db_pool: &DbPool) -> MyResult<()> {
+        let split = |list: &Option<String>| -> Vec<String> {
+            list.iter()
+                .flat_map(|l| l.split(','))
+                .filter_map(Self::normalize_domain)
+                .collect()
+        };
+        let allowed = split(&config.federation.allowlist)
+            .into_iter()
+            .map(|d| (d, false));
+        let blocked = split(&config.federation.blocklist)
+            .into_iter()
+            .map(|d| (d, true));
+        let forms: Vec<_> = allowed
+            .chain(blocked)
+            .map(|(domain, blocked)| DbFederationListForm {
+                domain,
+                blocked,
+                reason: Some("Imported from config file".to_string()),
+            })
+            .collect();
+        let mut conn = db_pool.get()?;
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            let first_import = insert_into(federation_list_import::table)
+                .values(federation_list_import::id.eq(1))
+                .on_conflict_do_nothing()
+                .execute(conn)?
+                > 0;
+            if first_import && !forms.is_empty() {
+                insert_into(federation_list::table)
+                    .values(forms)
+                    .on_conflict_do_nothing()
+                    .execute(conn)?;
+            }
+            Ok(())
+        })?;
+        Ok(())
+    }
+
+    /// Delete all articles which are hosted on the instance, and wipe comments written by its
+    /// users.
+    pub fn purge(domain: &str, context: &IbisContext) -> MyResult<()> {
+        let mut conn = context.db_pool.get()?;
+        let instance_id = instance::table
+            .filter(instance::domain.eq(domain))
+            .filter(instance::local.eq(false))
+            .select(instance::id)
+            .get_result::<InstanceId>(conn.deref_mut())
+            .optional()?;
+        let persons = person::table
+            .filter(person::ap_id.like(domain_like_pattern(domain)))
+            .select(person::id);
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            if let Some(instance_id) = instance_id {
+                delete(article::table.filter(article::instance_id.eq(instance_id)))
+                    .execute(conn)?;
+            }
+            update(comment::table.filter(comment::creator_id.eq_any(persons)))
+                .set((
+                    comment::content.eq(""),
+                    comment::deleted.eq(true),
+                    comment::updated.eq(Utc::now()),
+                ))
+                .execute(conn)?;
+            Ok(())
+        })?;
+        Ok(())
+    }
+}
+
+/// Cached copy of the federation list, so that it doesn't need to be read from the database for
+/// every federated request. It is reloaded after changes.
+#[derive(Default)]
+pub struct FederationListCache {
+    lists: RwLock<Option<Arc<FederationLists>>>,
+}
+
+#[derive(Default)]
+struct FederationLists {
+    allowed: HashSet<String>,
+    blocked: HashSet<String>,
+}
+
+impl FederationListCache {
+    /// Returns an error if federation with the domain is not allowed.
+    pub fn check(&self, domain: &str, db_pool: &DbPool) -> MyResult<()> {
+        let lists = self.read(db_pool)?;
+        if !lists.allowed.is_empty() && !lists.allowed.contains(domain) {
+            return Err(anyhow!("Domain {domain} is not allowed").into());
+        }
+        if lists.blocked.contains(domain) {
+            return Err(anyhow!("Domain {domain} is blocked").into());
+        }
+        Ok(())
+    }
+
+    fn read(&self, db_pool: &DbPool) -> MyResult<Arc<FederationLists>> {
+        if let Some(lists) = self.lists.read().ok().and_then(|l| l.as_ref().cloned()) {
+            return Ok(lists);
+        }
+        let mut lists = FederationLists::default();
+        for entry in DbFederationListEntry::list_from_pool(db_pool)? {
+            if entry.blocked {
+                lists.blocked.insert(entry.domain);
+            } else {
+                lists.allowed.insert(entry.domain);
+            }
+        }
+        let lists = Arc::new(lists);
+        if let Ok(mut cache) = self.lists.write() {
+            *cache = Some(lists.clone());
+        }
+        Ok(lists)
+    }
+
+    fn invalidate(&self) {
+        if let Ok(mut cache) = self.lists.write() {
+            *cache = None;
+        }
+    }
+}
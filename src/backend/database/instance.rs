@@ -1,14 +1,14 @@
 use crate::{
     backend::{
         database::{
-            schema::{article, comment, instance, instance_follow},
+            schema::{article, comment, instance, instance_follow, person},
             IbisContext,
         },
         federation::objects::{
             articles_collection::DbArticleCollection,
             instance_collection::DbInstanceCollection,
         },
-        utils::error::MyResult,
+        utils::{error::MyResult, pagination::Pagination},
     },
     common::{
         instance::{DbInstance, InstanceView},
@@ -25,6 +25,7 @@ use diesel::{
     insert_into,
     update,
     AsChangeset,
+    BoolExpressionMethods,
     ExpressionMethods,
     Insertable,
     JoinOnDsl,
@@ -137,7 +138,6 @@ impl DbInstance {
     }
 
     pub fn read_followers(id_: InstanceId, context: &IbisContext) -> MyResult<Vec<DbPerson>> {
-        use crate::backend::database::schema::person;
         use instance_follow::dsl::{follower_id, instance_id};
         let mut conn = context.db_pool.get()?;
         Ok(instance_follow::table
@@ -147,15 +147,47 @@ impl DbInstance {
             .get_results(conn.deref_mut())?)
     }
 
-    pub fn list(only_remote: bool, context: &Data<IbisContext>) -> MyResult<Vec<DbInstance>> {
+    /// List known instances, ordered by id. Without pagination all instances are returned, which
+    /// is used for federation.
+    pub fn list(
+        only_remote: bool,
+        pagination: Option<Pagination>,
+        context: &Data<IbisContext>,
+    ) -> MyResult<Vec<DbInstance>> {
         let mut conn = context.db_pool.get()?;
-        let mut query = instance::table.into_boxed();
+        let mut query = instance::table.order_by(instance::id).into_boxed();
         if only_remote {
             query = query.filter(instance::local.eq(false));
         }
+        if let Some(pagination) = pagination {
+            query = query
+                .limit(pagination.query_limit())
+                .offset(pagination.offset);
+        }
         Ok(query.get_results(conn.deref_mut())?)
     }
 
+    /// Remote instances which are followed by a local user, or which host articles that are
+    /// known locally. Used to limit requests which would otherwise go to every known instance.
+    pub fn list_linked(context: &Data<IbisContext>) -> MyResult<Vec<DbInstance>> {
+        let mut conn = context.db_pool.get()?;
+        let followed = instance_follow::table
+            .inner_join(person::table.on(instance_follow::follower_id.eq(person::id)))
+            .filter(person::local.eq(true))
+            .filter(instance_follow::pending.eq(false))
+            .select(instance_follow::instance_id);
+        let with_articles = article::table.select(article::instance_id);
+        Ok(instance::table
+            .filter(instance::local.eq(false))
+            .filter(
+                instance::id
+                    .eq_any(followed)
+                    .or(instance::id.eq_any(with_articles)),
+            )
+            .order_by(instance::id)
+            .get_results(conn.deref_mut())?)
+    }
+
     /// Read the instance where an article is hosted, based on a comment id.
     /// Note this may be different from the instance where the comment is hosted.
     pub fn read_for_comment(
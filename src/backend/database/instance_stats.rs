@@ -1,8 +1,26 @@
-use super::schema::instance_stats;
-use crate::backend::{IbisContext, MyResult};
-use diesel::{query_dsl::methods::FindDsl, Queryable, RunQueryDsl, Selectable};
+use super::schema::{instance_stats, instance_stats_history};
+use crate::{
+    backend::{IbisContext, MyResult},
+    common::instance::InstanceStatsDay,
+};
+use chrono::{Days, NaiveDate, Utc};
+use diesel::{
+    dsl::insert_into,
+    sql_query,
+    sql_types::Date,
+    upsert::excluded,
+    ExpressionMethods,
+    PgConnection,
+    QueryDsl,
+    Queryable,
+    RunQueryDsl,
+    Selectable,
+};
 use std::ops::DerefMut;
 
+/// Maximum number of days which can be read at once
+const MAX_HISTORY_DAYS: i64 = 365;
+
 #[derive(Queryable, Selectable)]
 #[diesel(table_name = instance_stats, check_for_backend(diesel::pg::Pg))]
 pub struct InstanceStats {
@@ -20,3 +38,53 @@ impl InstanceStats {
         Ok(instance_stats::table.find(1).get_result(conn.deref_mut())?)
     }
 }
+
+impl InstanceStatsDay {
+    /// Daily stats for the last days, oldest first. Days without any data are filled with zeros.
+    pub fn read(days: Option<i64>, context: &IbisContext) -> MyResult<Vec<Self>> {
+        let days = days.unwrap_or(30).clamp(1, MAX_HISTORY_DAYS);
+        let today = Utc::now().date_naive();
+        let first_day = today - Days::new(days as u64 - 1);
+        let mut conn = context.db_pool.get()?;
+        // Include edits and comments which were made since the last scheduled update
+        Self::update(today, conn.deref_mut())?;
+        let stored: Vec<Self> = instance_stats_history::table
+            .filter(instance_stats_history::day.ge(first_day))
+            .order_by(instance_stats_history::day.asc())
+            .get_results(conn.deref_mut())?;
+        let mut stored = stored.into_iter().peekable();
+        Ok(first_day
+            .iter_days()
+            .take(days as usize)
+            .map(|day| stored.next_if(|s| s.day == day).unwrap_or(Self::empty(day)))
+            .collect())
+    }
+
+    /// Recount edits, articles, comments and active editors of the given day.
+    pub fn update(day: NaiveDate, conn: &mut PgConnection) -> MyResult<()> {
+        sql_query("SELECT instance_stats_history_update($1)")
+            .bind::<Date, _>(day)
+            .execute(conn)?;
+        Ok(())
+    }
+
+    /// Add federated activities to the counters of the current day.
+    pub fn count_activities(received: i32, sent: i32, context: &IbisContext) -> MyResult<()> {
+        use super::schema::instance_stats_history::dsl::*;
+        let mut conn = context.db_pool.get()?;
+        insert_into(instance_stats_history)
+            .values((
+                day.eq(Utc::now().date_naive()),
+                activities_received.eq(received),
+                activities_sent.eq(sent),
+            ))
+            .on_conflict(day)
+            .do_update()
+            .set((
+                activities_received.eq(activities_received + excluded(activities_received)),
+                activities_sent.eq(activities_sent + excluded(activities_sent)),
+            ))
+            .execute(conn.deref_mut())?;
+        Ok(())
+    }
+}
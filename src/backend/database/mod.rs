@@ -1,6 +1,6 @@
 use crate::backend::{
-    database::schema::jwt_secret,
-    utils::{config::IbisConfig, error::MyResult},
+    database::{federation_list::FederationListCache, schema::jwt_secret},
+    utils::{config::IbisConfig, error::MyResult, rate_limit::RateLimit, view_limit::ViewLimit},
 };
 use diesel::{
     r2d2::{ConnectionManager, Pool},
@@ -8,16 +8,21 @@ use diesel::{
     QueryDsl,
     RunQueryDsl,
 };
-use std::ops::DerefMut;
+use std::{ops::DerefMut, sync::Arc};
 
+pub mod api_token;
 pub mod article;
 pub mod comment;
 pub mod conflict;
 pub mod edit;
+pub mod federation_list;
 pub mod instance;
 pub mod instance_stats;
+pub mod modlog;
+pub mod report;
 pub(crate) mod schema;
 pub mod user;
+pub mod user_stats;
 
 pub type DbPool = Pool<ConnectionManager<PgConnection>>;
 
@@ -25,6 +30,9 @@ pub type DbPool = Pool<ConnectionManager<PgConnection>>;
 pub struct IbisContext {
     pub db_pool: DbPool,
     pub config: IbisConfig,
+    pub rate_limit: Arc<RateLimit>,
+    pub federation_list: Arc<FederationListCache>,
+    pub view_limit: Arc<ViewLimit>,
 }
 
 pub fn read_jwt_secret(context: &IbisContext) -> MyResult<String> {
@@ -33,3 +41,13 @@ pub fn read_jwt_secret(context: &IbisContext) -> MyResult<String> {
         .select(jwt_secret::dsl::secret)
         .first(conn.deref_mut())?)
 }
+
+/// Pattern for `LIKE` which matches all urls on the given domain. Wildcards in the domain are
+/// escaped, so that they only match literally.
+pub fn domain_like_pattern(domain: &str) -> String {
+    let escaped = domain
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%://{escaped}/%")
+}
@@ -0,0 +1,205 @@
+use super::{
+    schema::{modlog, person},
+    IbisContext,
+};
+use crate::{
+    backend::utils::{error::MyResult, pagination::Pagination},
+    common::{
+        article::DbArticle,
+        comment::DbComment,
+        instance::DbInstance,
+        modlog::{DbModlog, ModlogAction},
+        newtypes::{ArticleId, InstanceId, PersonId},
+        user::DbPerson,
+    },
+};
+use diesel::{
+    deserialize::{self, FromSql},
+    dsl::insert_into,
+    pg::{Pg, PgValue},
+    serialize::{self, IsNull, Output, ToSql},
+    sql_types::Text,
+    ExpressionMethods,
+    Insertable,
+    JoinOnDsl,
+    QueryDsl,
+    RunQueryDsl,
+};
+use std::{io::Write, ops::DerefMut};
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = modlog, check_for_backend(diesel::pg::Pg))]
+pub struct DbModlogForm {
+    pub actor_id: PersonId,
+    pub action: ModlogAction,
+    pub target_article_id: Option<ArticleId>,
+    pub target_person_id: Option<PersonId>,
+    pub target_instance_id: Option<InstanceId>,
+    pub target_name: String,
+    pub reason: Option<String>,
+}
+
+impl DbModlogForm {
+    pub fn article(
+        actor: &DbPerson,
+        action: ModlogAction,
+        article: &DbArticle,
+        reason: Option<String>,
+    ) -> Self {
+        Self {
+            actor_id: actor.id,
+            action,
+            target_article_id: Some(article.id),
+            target_person_id: None,
+            target_instance_id: Some(article.instance_id),
+            target_name: article.title.clone(),
+            reason,
+        }
+    }
+
+    pub fn person(
+        actor: &DbPerson,
+        action: ModlogAction,
+        person: &DbPerson,
+        reason: Option<String>,
+    ) -> Self {
+        Self {
+            actor_id: actor.id,
+            action,
+            target_article_id: None,
+            target_person_id: Some(person.id),
+            target_instance_id: None,
+            target_name: person.username.clone(),
+            reason,
+        }
+    }
+
+    /// Comments have no name, so the article title is used instead.
+    pub fn comment(
+        actor: &DbPerson,
+        action: ModlogAction,
+        comment: &DbComment,
+        article: &DbArticle,
+        reason: Option<String>,
+    ) -> Self {
+        Self {
+            actor_id: actor.id,
+            action,
+            target_article_id: Some(article.id),
+            target_person_id: Some(comment.creator_id),
+            target_instance_id: Some(article.instance_id),
+            target_name: article.title.clone(),
+            reason,
+        }
+    }
+
+    /// Federation list entries can exist for domains which are not known as instance.
+    pub fn domain(
+        actor: &DbPerson,
+        action: ModlogAction,
+        domain: &str,
+        reason: Option<String>,
+    ) -> Self {
+        Self {
+            actor_id: actor.id,
+            action,
+            target_article_id: None,
+            target_person_id: None,
+            target_instance_id: None,
+            target_name: domain.to_string(),
+            reason,
+        }
+    }
+
+    pub fn instance(actor: &DbPerson, action: ModlogAction, instance: &DbInstance) -> Self {
+        Self {
+            actor_id: actor.id,
+            action,
+            target_article_id: None,
+            target_person_id: None,
+            target_instance_id: Some(instance.id),
+            target_name: instance.domain.clone(),
+            reason: None,
+        }
+    }
+}
+
+impl DbModlog {
+    pub fn create(form: DbModlogForm, context: &IbisContext) -> MyResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(insert_into(modlog::table)
+            .values(form)
+            .get_result(conn.deref_mut())?)
+    }
+
+    /// Newest entries first, together with the moderator who performed the action.
+    pub fn list(
+        pagination: Pagination,
+        context: &IbisContext,
+    ) -> MyResult<Vec<(DbModlog, DbPerson)>> {
+        let mut conn = context.db_pool.get()?;
+        Ok(modlog::table
+            .inner_join(person::table.on(person::id.eq(modlog::actor_id)))
+            .select((modlog::all_columns, person::all_columns))
+            .order_by(modlog::published.desc())
+            .then_order_by(modlog::id.desc())
+            .limit(pagination.query_limit())
+            .offset(pagination.offset)
+            .get_results(conn.deref_mut())?)
+    }
+}
+
+impl ModlogAction {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ModlogAction::ProtectArticle => "protect_article",
+            ModlogAction::UnprotectArticle => "unprotect_article",
+            ModlogAction::ApproveArticle => "approve_article",
+            ModlogAction::RejectArticle => "reject_article",
+            ModlogAction::UpdateInstance => "update_instance",
+            ModlogAction::BanUser => "ban_user",
+            ModlogAction::UnbanUser => "unban_user",
+            ModlogAction::DeleteUser => "delete_user",
+            ModlogAction::ResolveReport => "resolve_report",
+            ModlogAction::DismissReport => "dismiss_report",
+            ModlogAction::RemoveComment => "remove_comment",
+            ModlogAction::RestoreComment => "restore_comment",
+            ModlogAction::UpdateUserRole => "update_user_role",
+            ModlogAction::BlockInstance => "block_instance",
+            ModlogAction::AllowInstance => "allow_instance",
+            ModlogAction::RemoveFederationListEntry => "remove_federation_list_entry",
+        }
+    }
+}
+
+impl ToSql<Text, Pg> for ModlogAction {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        out.write_all(self.as_str().as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Text, Pg> for ModlogAction {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        let value = <String as FromSql<Text, Pg>>::from_sql(bytes)?;
+        Ok(match value.as_str() {
+            "protect_article" => ModlogAction::ProtectArticle,
+            "unprotect_article" => ModlogAction::UnprotectArticle,
+            "approve_article" => ModlogAction::ApproveArticle,
+            "reject_article" => ModlogAction::RejectArticle,
+            "update_instance" => ModlogAction::UpdateInstance,
+            "ban_user" => ModlogAction::BanUser,
+            "unban_user" => ModlogAction::UnbanUser,
+            "delete_user" => ModlogAction::DeleteUser,
+            "resolve_report" => ModlogAction::ResolveReport,
+            "dismiss_report" => ModlogAction::DismissReport,
+            "remove_comment" => ModlogAction::RemoveComment,
+            "restore_comment" => ModlogAction::RestoreComment,
+            "update_user_role" => ModlogAction::UpdateUserRole,
+            "block_instance" => ModlogAction::BlockInstance,
+            "allow_instance" => ModlogAction::AllowInstance,
+            "remove_federation_list_entry" => ModlogAction::RemoveFederationListEntry,
+            other => return Err(format!("Unknown modlog action {other}").into()),
+        })
+    }
+}
@@ -0,0 +1,191 @@
+use super::{
+    schema::{article, comment, edit, person, report},
+    IbisContext,
+};
+use crate::{
+    backend::utils::{error::MyResult, pagination::Pagination},
+    common::{
+        article::{DbArticle, DbEdit},
+        comment::DbComment,
+        newtypes::{ArticleId, CommentId, EditId, PersonId, ReportId},
+        report::{DbReport, ReportStatus, ReportView},
+        user::DbPerson,
+    },
+};
+use chrono::Utc;
+use diesel::{
+    deserialize::{self, FromSql},
+    dsl::insert_into,
+    pg::{Pg, PgValue},
+    serialize::{self, IsNull, Output, ToSql},
+    sql_types::Text,
+    update,
+    ExpressionMethods,
+    Insertable,
+    JoinOnDsl,
+    NullableExpressionMethods,
+    OptionalExtension,
+    QueryDsl,
+    RunQueryDsl,
+};
+use std::{io::Write, ops::DerefMut};
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = report, check_for_backend(diesel::pg::Pg))]
+pub struct DbReportForm {
+    pub creator_id: PersonId,
+    pub article_id: ArticleId,
+    pub edit_id: Option<EditId>,
+    pub comment_id: Option<CommentId>,
+    pub reason: String,
+    pub ap_id: String,
+    pub local: bool,
+}
+
+impl DbReport {
+    /// If a report with the same `ap_id` exists already, it is returned unchanged. This way a
+    /// repeated delivery of the same activity doesn't fail or overwrite the report.
+    pub fn create(form: &DbReportForm, context: &IbisContext) -> MyResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        let report = insert_into(report::table)
+            .values(form)
+            .on_conflict(report::ap_id)
+            .do_nothing()
+            .get_result(conn.deref_mut())
+            .optional()?;
+        match report {
+            Some(report) => Ok(report),
+            None => Ok(report::table
+                .filter(report::ap_id.eq(&form.ap_id))
+                .get_result(conn.deref_mut())?),
+        }
+    }
+
+    pub fn read(id: ReportId, context: &IbisContext) -> MyResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(report::table.find(id).get_result(conn.deref_mut())?)
+    }
+
+    pub fn update_status(
+        id: ReportId,
+        status: ReportStatus,
+        resolver_id: PersonId,
+        context: &IbisContext,
+    ) -> MyResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(update(report::table.find(id))
+            .set((
+                report::status.eq(status),
+                report::resolver_id.eq(resolver_id),
+                report::updated.eq(Utc::now()),
+            ))
+            .get_result(conn.deref_mut())?)
+    }
+
+    /// Newest reports first, only open reports unless `include_closed` is set.
+    pub fn list(
+        include_closed: bool,
+        pagination: Pagination,
+        context: &IbisContext,
+    ) -> MyResult<Vec<ReportView>> {
+        Self::list_internal(
+            include_closed,
+            false,
+            pagination.offset,
+            pagination.query_limit(),
+            context,
+        )
+    }
+
+    /// Oldest open reports first.
+    pub fn list_open_oldest(limit: i64, context: &IbisContext) -> MyResult<Vec<ReportView>> {
+        Self::list_internal(false, true, 0, limit, context)
+    }
+
+    fn list_internal(
+        include_closed: bool,
+        oldest_first: bool,
+        offset: i64,
+        limit: i64,
+        context: &IbisContext,
+    ) -> MyResult<Vec<ReportView>> {
+        let mut conn = context.db_pool.get()?;
+        let mut query = report::table
+            .inner_join(person::table.on(person::id.eq(report::creator_id)))
+            .inner_join(article::table)
+            .left_join(edit::table)
+            .left_join(comment::table)
+            .select((
+                report::all_columns,
+                person::all_columns,
+                article::all_columns,
+                edit::all_columns.nullable(),
+                comment::all_columns.nullable(),
+            ))
+            .into_boxed();
+        if !include_closed {
+            query = query.filter(report::status.eq(ReportStatus::Open));
+        }
+        query = if oldest_first {
+            query.order_by((report::published.asc(), report::id.asc()))
+        } else {
+            query.order_by((report::published.desc(), report::id.desc()))
+        };
+        Ok(query
+            .offset(offset)
+            .limit(limit)
+            .get_results::<(
+                DbReport,
+                DbPerson,
+                DbArticle,
+                Option<DbEdit>,
+                Option<DbComment>,
+            )>(conn.deref_mut())?
+            .into_iter()
+            .map(|(report, creator, article, edit, comment)| ReportView {
+                report,
+                creator,
+                article,
+                edit,
+                comment,
+            })
+            .collect())
+    }
+
+    pub fn count_open(context: &IbisContext) -> MyResult<i64> {
+        let mut conn = context.db_pool.get()?;
+        Ok(report::table
+            .filter(report::status.eq(ReportStatus::Open))
+            .count()
+            .get_result(conn.deref_mut())?)
+    }
+}
+
+impl ReportStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ReportStatus::Open => "open",
+            ReportStatus::Resolved => "resolved",
+            ReportStatus::Dismissed => "dismissed",
+        }
+    }
+}
+
+impl ToSql<Text, Pg> for ReportStatus {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        out.write_all(self.as_str().as_bytes())?;
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Text, Pg> for ReportStatus {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        let value = <String as FromSql<Text, Pg>>::from_sql(bytes)?;
+        Ok(match value.as_str() {
+            "open" => ReportStatus::Open,
+            "resolved" => ReportStatus::Resolved,
+            "dismissed" => ReportStatus::Dismissed,
+            other => return Err(format!("Unknown report status {other}").into()),
+        })
+    }
+}
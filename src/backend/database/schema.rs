@@ -1,5 +1,26 @@
 // @generated automatically by Diesel CLI.
 
+pub mod sql_types {
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "tsvector", schema = "pg_catalog"))]
+    pub struct Tsvector;
+}
+
+diesel::table! {
+    api_token (id) {
+        id -> Int4,
+        local_user_id -> Int4,
+        name -> Text,
+        token_hash -> Text,
+        scope_read -> Bool,
+        scope_edit -> Bool,
+        scope_comment -> Bool,
+        scope_admin -> Bool,
+        published -> Timestamptz,
+        last_used -> Nullable<Timestamptz>,
+    }
+}
+
 diesel::table! {
     article (id) {
         id -> Int4,
@@ -12,6 +33,25 @@ diesel::table! {
         protected -> Bool,
         approved -> Bool,
         published -> Timestamptz,
+        language -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::Tsvector;
+
+    article_search (article_id) {
+        article_id -> Int4,
+        search_vector -> Tsvector,
+    }
+}
+
+diesel::table! {
+    article_view (article_id, day) {
+        article_id -> Int4,
+        day -> Date,
+        views -> Int4,
     }
 }
 
@@ -29,6 +69,16 @@ diesel::table! {
         deleted -> Bool,
         published -> Timestamptz,
         updated -> Nullable<Timestamptz>,
+        removed -> Bool,
+    }
+}
+
+diesel::table! {
+    comment_vote (person_id, comment_id) {
+        person_id -> Int4,
+        comment_id -> Int4,
+        score -> Int2,
+        published -> Timestamptz,
     }
 }
 
@@ -61,6 +111,22 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    federation_list (domain) {
+        domain -> Text,
+        blocked -> Bool,
+        reason -> Nullable<Text>,
+        published -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    federation_list_import (id) {
+        id -> Int4,
+        published -> Timestamptz,
+    }
+}
+
 diesel::table! {
     instance (id) {
         id -> Int4,
@@ -102,6 +168,19 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    instance_stats_history (day) {
+        day -> Date,
+        edits -> Int4,
+        articles -> Int4,
+        new_users -> Int4,
+        comments -> Int4,
+        activities_received -> Int4,
+        activities_sent -> Int4,
+        active_editors -> Int4,
+    }
+}
+
 diesel::table! {
     jwt_secret (id) {
         id -> Int4,
@@ -115,6 +194,39 @@ diesel::table! {
         password_encrypted -> Text,
         person_id -> Int4,
         admin -> Bool,
+        totp_secret -> Nullable<Text>,
+        totp_enabled -> Bool,
+        moderator -> Bool,
+        perm_protect_article -> Bool,
+        perm_approve_article -> Bool,
+        perm_remove_comment -> Bool,
+        perm_ban -> Bool,
+        perm_manage_federation -> Bool,
+        perm_manage_users -> Bool,
+        perm_handle_reports -> Bool,
+    }
+}
+
+diesel::table! {
+    modlog (id) {
+        id -> Int4,
+        actor_id -> Int4,
+        action -> Text,
+        target_article_id -> Nullable<Int4>,
+        target_person_id -> Nullable<Int4>,
+        target_instance_id -> Nullable<Int4>,
+        target_name -> Text,
+        reason -> Nullable<Text>,
+        published -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    oidc_identity (id) {
+        id -> Int4,
+        local_user_id -> Int4,
+        provider -> Text,
+        subject -> Text,
     }
 }
 
@@ -134,12 +246,56 @@ diesel::table! {
         display_name -> Nullable<Varchar>,
         #[max_length = 1000]
         bio -> Nullable<Varchar>,
+        bot -> Bool,
+        banned -> Bool,
+        ban_reason -> Nullable<Text>,
+        ban_expires -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    report (id) {
+        id -> Int4,
+        creator_id -> Int4,
+        article_id -> Int4,
+        edit_id -> Nullable<Int4>,
+        comment_id -> Nullable<Int4>,
+        reason -> Text,
+        status -> Text,
+        resolver_id -> Nullable<Int4>,
+        #[max_length = 255]
+        ap_id -> Varchar,
+        local -> Bool,
+        published -> Timestamptz,
+        updated -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    totp_recovery_code (id) {
+        id -> Int4,
+        local_user_id -> Int4,
+        code_hash -> Text,
+    }
+}
+
+diesel::table! {
+    totp_usage (local_user_id) {
+        local_user_id -> Int4,
+        last_step -> Nullable<Int8>,
+        attempts -> Int4,
+        attempts_start -> Timestamptz,
     }
 }
 
+diesel::joinable!(api_token -> local_user (local_user_id));
 diesel::joinable!(article -> instance (instance_id));
+diesel::joinable!(article_search -> article (article_id));
+diesel::joinable!(article_view -> article (article_id));
 diesel::joinable!(comment -> article (article_id));
 diesel::joinable!(comment -> person (creator_id));
+diesel::joinable!(comment_vote -> comment (comment_id));
+diesel::joinable!(comment_vote -> person (person_id));
 diesel::joinable!(conflict -> article (article_id));
 diesel::joinable!(conflict -> person (creator_id));
 diesel::joinable!(edit -> article (article_id));
@@ -147,16 +303,36 @@ diesel::joinable!(edit -> person (creator_id));
 diesel::joinable!(instance_follow -> instance (instance_id));
 diesel::joinable!(instance_follow -> person (follower_id));
 diesel::joinable!(local_user -> person (person_id));
+diesel::joinable!(modlog -> article (target_article_id));
+diesel::joinable!(modlog -> instance (target_instance_id));
+diesel::joinable!(oidc_identity -> local_user (local_user_id));
+diesel::joinable!(report -> article (article_id));
+diesel::joinable!(report -> comment (comment_id));
+diesel::joinable!(report -> edit (edit_id));
+diesel::joinable!(totp_recovery_code -> local_user (local_user_id));
+diesel::joinable!(totp_usage -> local_user (local_user_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    api_token,
     article,
+    article_search,
+    article_view,
     comment,
+    comment_vote,
     conflict,
     edit,
+    federation_list,
+    federation_list_import,
     instance,
     instance_follow,
     instance_stats,
+    instance_stats_history,
     jwt_secret,
     local_user,
+    modlog,
+    oidc_identity,
     person,
+    report,
+    totp_recovery_code,
+    totp_usage,
 );
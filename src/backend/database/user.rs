@@ -1,27 +1,53 @@
 use crate::{
     backend::{
         database::{
-            schema::{instance, instance_follow, local_user, person},
+            domain_like_pattern,
+            schema::{
+                comment,
+                edit,
+                instance,
+                instance_follow,
+                local_user,
+                modlog,
+                oidc_identity,
+                person,
+                totp_recovery_code,
+                totp_usage,
+            },
             IbisContext,
         },
         utils::{error::MyResult, generate_keypair},
     },
     common::{
         instance::DbInstance,
-        newtypes::PersonId,
-        user::{DbLocalUser, DbPerson, LocalUserView, UpdateUserParams},
+        newtypes::{LocalUserId, PersonId},
+        user::{
+            DbLocalUser,
+            DbPerson,
+            LocalUserView,
+            UpdateUserParams,
+            UpdateUserRoleParams,
+            UserRole,
+        },
         utils::http_protocol_str,
     },
 };
 use activitypub_federation::{config::Data, fetch::object_id::ObjectId};
+use anyhow::anyhow;
 use bcrypt::{hash, DEFAULT_COST};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, TimeDelta, Utc};
 use diesel::{
+    delete,
+    dsl::case_when,
     insert_into,
+    update,
     AsChangeset,
+    BoolExpressionMethods,
+    Connection,
     ExpressionMethods,
     Insertable,
     JoinOnDsl,
+    OptionalExtension,
     PgTextExpressionMethods,
     QueryDsl,
     RunQueryDsl,
@@ -48,6 +74,7 @@ pub struct DbPersonForm {
     pub local: bool,
     pub display_name: Option<String>,
     pub bio: Option<String>,
+    pub bot: bool,
 }
 
 impl DbPerson {
@@ -90,6 +117,7 @@ impl DbPerson {
             local: true,
             display_name: None,
             bio: None,
+            bot: false,
         };
 
         let person = insert_into(person::table)
@@ -134,9 +162,8 @@ impl DbPerson {
             .select(person::all_columns)
             .into_boxed();
         query = if let Some(domain) = domain {
-            let domain_pattern = format!("%://{domain}/%");
             query
-                .filter(person::ap_id.ilike(domain_pattern))
+                .filter(person::ap_id.ilike(domain_like_pattern(domain)))
                 .filter(person::local.eq(false))
         } else {
             query.filter(person::local.eq(true))
@@ -155,6 +182,29 @@ impl DbPerson {
         Ok(())
     }
 
+    /// Ban or unban the user. Reason and expiration are removed when unbanning.
+    pub fn update_ban(
+        id: PersonId,
+        banned: bool,
+        reason: Option<String>,
+        expires: Option<DateTime<Utc>>,
+        context: &IbisContext,
+    ) -> MyResult<DbPerson> {
+        let (reason, expires) = if banned {
+            (reason, expires)
+        } else {
+            (None, None)
+        };
+        let mut conn = context.db_pool.get()?;
+        Ok(update(person::table.find(id))
+            .set((
+                person::banned.eq(banned),
+                person::ban_reason.eq(reason),
+                person::ban_expires.eq(expires),
+            ))
+            .get_result(conn.deref_mut())?)
+    }
+
     pub fn read_local_from_name(username: &str, context: &IbisContext) -> MyResult<LocalUserView> {
         let mut conn = context.db_pool.get()?;
         let (person, local_user) = person::table
@@ -171,6 +221,32 @@ impl DbPerson {
         })
     }
 
+    /// Read the local user which is linked to the given OpenID Connect identity. Returns `None`
+    /// if the identity is not linked yet.
+    pub fn read_local_from_oidc(
+        provider: &str,
+        subject: &str,
+        context: &IbisContext,
+    ) -> MyResult<Option<LocalUserView>> {
+        let mut conn = context.db_pool.get()?;
+        let Some((person, local_user)) = person::table
+            .inner_join(local_user::table.inner_join(oidc_identity::table))
+            .filter(oidc_identity::provider.eq(provider))
+            .filter(oidc_identity::subject.eq(subject))
+            .select((person::all_columns, local_user::all_columns))
+            .get_result::<(DbPerson, DbLocalUser)>(conn.deref_mut())
+            .optional()?
+        else {
+            return Ok(None);
+        };
+        let following = Self::read_following(person.id, context)?;
+        Ok(Some(LocalUserView {
+            person,
+            local_user,
+            following,
+        }))
+    }
+
     fn read_following(id_: PersonId, context: &IbisContext) -> MyResult<Vec<DbInstance>> {
         use instance_follow::dsl::{follower_id, instance_id};
         let mut conn = context.db_pool.get()?;
@@ -205,8 +281,229 @@ impl DbPerson {
                 local: true,
                 display_name: None,
                 bio: None,
+                bot: false,
             };
             DbPerson::create(&person_form, context)
         }
     }
+
+    /// Delete a user account. Edits and comments are reassigned to the ghost user so that article
+    /// history and comment threads stay intact. With `delete_comments` the comment content is
+    /// wiped as well. Works for both local and remote users, for local users the `local_user`
+    /// row is removed together with the person.
+    pub fn delete_account(
+        person_id: PersonId,
+        delete_comments: bool,
+        context: &Data<IbisContext>,
+    ) -> MyResult<()> {
+        let ghost = DbPerson::ghost(context)?;
+        if person_id == ghost.id {
+            return Err(anyhow!("Cannot delete ghost user").into());
+        }
+        let mut conn = context.db_pool.get()?;
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            update(edit::table.filter(edit::creator_id.eq(person_id)))
+                .set(edit::creator_id.eq(ghost.id))
+                .execute(conn)?;
+            if delete_comments {
+                update(comment::table.filter(comment::creator_id.eq(person_id)))
+                    .set((
+                        comment::content.eq(""),
+                        comment::deleted.eq(true),
+                        comment::updated.eq(Utc::now()),
+                    ))
+                    .execute(conn)?;
+            }
+            update(comment::table.filter(comment::creator_id.eq(person_id)))
+                .set(comment::creator_id.eq(ghost.id))
+                .execute(conn)?;
+            update(modlog::table.filter(modlog::actor_id.eq(person_id)))
+                .set(modlog::actor_id.eq(ghost.id))
+                .execute(conn)?;
+            // cascades to local_user, instance_follow and conflict
+            delete(person::table.find(person_id)).execute(conn)?;
+            Ok(())
+        })?;
+        Ok(())
+    }
+}
+
+impl DbLocalUser {
+    pub fn read_from_person(person_id: PersonId, context: &IbisContext) -> MyResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(local_user::table
+            .filter(local_user::person_id.eq(person_id))
+            .get_result(conn.deref_mut())?)
+    }
+
+    /// Also sets the bot flag of the person, which can only be changed by admins.
+    pub fn update_role(params: &UpdateUserRoleParams, context: &IbisContext) -> MyResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        Ok(conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            update(person::table.find(params.person_id))
+                .set(person::bot.eq(params.bot))
+                .execute(conn)?;
+            update(local_user::table.filter(local_user::person_id.eq(params.person_id)))
+                .set((
+                    local_user::admin.eq(params.role == UserRole::Admin),
+                    local_user::moderator.eq(params.role == UserRole::Moderator),
+                    local_user::perm_protect_article.eq(params.perm_protect_article),
+                    local_user::perm_approve_article.eq(params.perm_approve_article),
+                    local_user::perm_remove_comment.eq(params.perm_remove_comment),
+                    local_user::perm_ban.eq(params.perm_ban),
+                    local_user::perm_manage_federation.eq(params.perm_manage_federation),
+                    local_user::perm_manage_users.eq(params.perm_manage_users),
+                    local_user::perm_handle_reports.eq(params.perm_handle_reports),
+                ))
+                .get_result(conn)
+        })?)
+    }
+
+    /// Store a new TOTP secret. It is only used for login after enabling with
+    /// [DbLocalUser::update_totp_enabled].
+    pub fn update_totp_secret(
+        id: LocalUserId,
+        secret: Option<String>,
+        context: &IbisContext,
+    ) -> MyResult<()> {
+        let mut conn = context.db_pool.get()?;
+        update(local_user::table.find(id))
+            .set((
+                local_user::totp_secret.eq(secret),
+                local_user::totp_enabled.eq(false),
+            ))
+            .execute(conn.deref_mut())?;
+        Ok(())
+    }
+
+    /// Enable or disable 2FA. Existing recovery codes are replaced with the given ones.
+    pub fn update_totp_enabled(
+        id: LocalUserId,
+        enabled: bool,
+        recovery_code_hashes: Vec<String>,
+        context: &IbisContext,
+    ) -> MyResult<()> {
+        let mut conn = context.db_pool.get()?;
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            update(local_user::table.find(id))
+                .set(local_user::totp_enabled.eq(enabled))
+                .execute(conn)?;
+            delete(totp_recovery_code::table.filter(totp_recovery_code::local_user_id.eq(id)))
+                .execute(conn)?;
+            let forms: Vec<_> = recovery_code_hashes
+                .into_iter()
+                .map(|hash| {
+                    (
+                        totp_recovery_code::local_user_id.eq(id),
+                        totp_recovery_code::code_hash.eq(hash),
+                    )
+                })
+                .collect();
+            insert_into(totp_recovery_code::table)
+                .values(forms)
+                .execute(conn)?;
+            Ok(())
+        })?;
+        Ok(())
+    }
+
+    /// Link an OpenID Connect identity to the user, so that it can be used for login.
+    pub fn link_oidc(
+        id: LocalUserId,
+        provider: &str,
+        subject: &str,
+        context: &IbisContext,
+    ) -> MyResult<()> {
+        let mut conn = context.db_pool.get()?;
+        insert_into(oidc_identity::table)
+            .values((
+                oidc_identity::local_user_id.eq(id),
+                oidc_identity::provider.eq(provider),
+                oidc_identity::subject.eq(subject),
+            ))
+            .execute(conn.deref_mut())?;
+        Ok(())
+    }
+
+    /// Count an attempt to enter a TOTP token, and return the number of attempts in the current
+    /// window including this one. A new window starts with the first attempt after the previous
+    /// one has expired.
+    pub fn add_totp_attempt(
+        id: LocalUserId,
+        window: TimeDelta,
+        context: &IbisContext,
+    ) -> MyResult<i32> {
+        let mut conn = context.db_pool.get()?;
+        let now = Utc::now();
+        let in_window = totp_usage::attempts_start.gt(now - window);
+        Ok(insert_into(totp_usage::table)
+            .values((
+                totp_usage::local_user_id.eq(id),
+                totp_usage::attempts.eq(1),
+                totp_usage::attempts_start.eq(now),
+            ))
+            .on_conflict(totp_usage::local_user_id)
+            .do_update()
+            .set((
+                totp_usage::attempts
+                    .eq(case_when(in_window, totp_usage::attempts + 1).otherwise(1)),
+                totp_usage::attempts_start
+                    .eq(case_when(in_window, totp_usage::attempts_start).otherwise(now)),
+            ))
+            .returning(totp_usage::attempts)
+            .get_result(conn.deref_mut())?)
+    }
+
+    /// Store the time step of an accepted TOTP token and reset the attempts. Returns false if a
+    /// token for the same or a later step was accepted before, then the token must be rejected
+    /// so that it can't be reused.
+    pub fn update_totp_last_step(
+        id: LocalUserId,
+        step: i64,
+        context: &IbisContext,
+    ) -> MyResult<bool> {
+        // filter for the upsert, [QueryDsl::filter] is not implemented for insert statements
+        use diesel::query_dsl::methods::FilterDsl;
+        let mut conn = context.db_pool.get()?;
+        let updated = insert_into(totp_usage::table)
+            .values((
+                totp_usage::local_user_id.eq(id),
+                totp_usage::last_step.eq(step),
+            ))
+            .on_conflict(totp_usage::local_user_id)
+            .do_update()
+            .set((totp_usage::last_step.eq(step), totp_usage::attempts.eq(0)))
+            .filter(
+                totp_usage::last_step
+                    .is_null()
+                    .or(totp_usage::last_step.lt(step)),
+            )
+            .execute(conn.deref_mut())?;
+        Ok(updated > 0)
+    }
+
+    pub fn reset_totp_attempts(id: LocalUserId, context: &IbisContext) -> MyResult<()> {
+        let mut conn = context.db_pool.get()?;
+        update(totp_usage::table.find(id))
+            .set(totp_usage::attempts.eq(0))
+            .execute(conn.deref_mut())?;
+        Ok(())
+    }
+
+    /// Returns true if the recovery code was valid. In this case it is deleted so that it cannot
+    /// be used again.
+    pub fn use_recovery_code(
+        id: LocalUserId,
+        code_hash: &str,
+        context: &IbisContext,
+    ) -> MyResult<bool> {
+        let mut conn = context.db_pool.get()?;
+        let deleted = delete(
+            totp_recovery_code::table
+                .filter(totp_recovery_code::local_user_id.eq(id))
+                .filter(totp_recovery_code::code_hash.eq(code_hash)),
+        )
+        .execute(conn.deref_mut())?;
+        Ok(deleted > 0)
+    }
 }
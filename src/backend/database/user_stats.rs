@@ -0,0 +1,107 @@
+use super::schema::{article, comment, edit};
+use crate::{
+    backend::{IbisContext, MyResult},
+    common::{
+        article::{DbArticle, EditVersion},
+        newtypes::PersonId,
+        user::{UserStats, UserTopArticle},
+    },
+};
+use chrono::{DateTime, Utc};
+use diesel::{
+    dsl::{self, count_star},
+    sql_query,
+    sql_types::{BigInt, Integer},
+    ExpressionMethods,
+    QueryDsl,
+    QueryableByName,
+    RunQueryDsl,
+};
+use std::ops::DerefMut;
+
+/// Number of articles included in [UserStats::top_articles]
+const TOP_ARTICLES_COUNT: i64 = 5;
+
+/// Same as [crate::common::article::diff_byte_counts], summed over all accepted edits of a
+/// user. Lines before the first hunk are the file headers and are skipped.
+const BYTE_COUNTS: &str = r"
+SELECT coalesce(sum(octet_length(line)) FILTER (WHERE line LIKE '+%'), 0) AS bytes_added,
+    coalesce(sum(octet_length(line)) FILTER (WHERE line LIKE '-%'), 0) AS bytes_removed
+FROM edit,
+    LATERAL regexp_split_to_table(
+        substr(E'\n' || diff, strpos(E'\n' || diff, E'\n@@') + 1), E'\n'
+    ) AS line
+WHERE creator_id = $1 AND NOT pending AND strpos(E'\n' || diff, E'\n@@') > 0";
+
+#[derive(QueryableByName)]
+struct ByteCounts {
+    #[diesel(sql_type = BigInt)]
+    bytes_added: i64,
+    #[diesel(sql_type = BigInt)]
+    bytes_removed: i64,
+}
+
+impl UserStats {
+    pub fn read(person_id: PersonId, context: &IbisContext) -> MyResult<Self> {
+        let mut conn = context.db_pool.get()?;
+        let (edit_count, first_edit, last_edit): (
+            i64,
+            Option<DateTime<Utc>>,
+            Option<DateTime<Utc>>,
+        ) = edit::table
+            .filter(edit::creator_id.eq(person_id))
+            .filter(edit::pending.eq(false))
+            .select((
+                count_star(),
+                dsl::min(edit::published),
+                dsl::max(edit::published),
+            ))
+            .get_result(conn.deref_mut())?;
+
+        let bytes: ByteCounts = sql_query(BYTE_COUNTS)
+            .bind::<Integer, _>(person_id.0)
+            .get_result(conn.deref_mut())?;
+
+        let article_count = edit::table
+            .filter(edit::creator_id.eq(person_id))
+            .filter(edit::pending.eq(false))
+            .filter(edit::previous_version_id.eq(EditVersion::default()))
+            .count()
+            .get_result(conn.deref_mut())?;
+
+        let comment_count = comment::table
+            .filter(comment::creator_id.eq(person_id))
+            .filter(comment::deleted.eq(false))
+            .filter(comment::removed.eq(false))
+            .count()
+            .get_result(conn.deref_mut())?;
+
+        let top_articles = article::table
+            .inner_join(edit::table)
+            .filter(edit::creator_id.eq(person_id))
+            .filter(edit::pending.eq(false))
+            .filter(article::approved.eq(true))
+            .group_by(article::id)
+            .select((article::all_columns, count_star()))
+            .order_by((count_star().desc(), article::id))
+            .limit(TOP_ARTICLES_COUNT)
+            .get_results::<(DbArticle, i64)>(conn.deref_mut())?
+            .into_iter()
+            .map(|(article, edit_count)| UserTopArticle {
+                article,
+                edit_count,
+            })
+            .collect();
+
+        Ok(UserStats {
+            edit_count,
+            bytes_added: bytes.bytes_added,
+            bytes_removed: bytes.bytes_removed,
+            first_edit,
+            last_edit,
+            comment_count,
+            article_count,
+            top_articles,
+        })
+    }
+}
@@ -0,0 +1,114 @@
+use crate::{
+    backend::{
+        database::IbisContext,
+        federation::{activities::all_known_inboxes, send_activity},
+        utils::{
+            error::{Error, MyResult},
+            generate_activity_id,
+        },
+    },
+    common::{comment::DbComment, instance::DbInstance, user::DbPerson, utils::extract_domain},
+};
+use activitypub_federation::{
+    config::Data,
+    fetch::object_id::ObjectId,
+    kinds::{activity::BlockType, public},
+    protocol::{helpers::deserialize_one_or_many, verification::verify_domains_match},
+    traits::ActivityHandler,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Sent when a user is banned from the local instance. The ban is only applied by other instances
+/// if it comes from the home instance of the user.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockUser {
+    pub(crate) actor: ObjectId<DbPerson>,
+    #[serde(deserialize_with = "deserialize_one_or_many")]
+    pub(crate) to: Vec<Url>,
+    pub(crate) object: ObjectId<DbPerson>,
+    /// Instance from which the user is banned
+    pub(crate) target: ObjectId<DbInstance>,
+    #[serde(rename = "type")]
+    pub(crate) kind: BlockType,
+    /// Reason for the ban
+    pub(crate) summary: Option<String>,
+    pub(crate) end_time: Option<DateTime<Utc>>,
+    /// Whether comments written by the user on articles of the target instance should be wiped
+    pub(crate) remove_data: bool,
+    pub(crate) id: Url,
+}
+
+impl BlockUser {
+    pub(super) fn new(
+        moderator: &DbPerson,
+        person: &DbPerson,
+        remove_data: bool,
+        context: &Data<IbisContext>,
+    ) -> MyResult<Self> {
+        let local_instance = DbInstance::read_local(context)?;
+        Ok(BlockUser {
+            actor: moderator.ap_id.clone(),
+            to: vec![public()],
+            object: person.ap_id.clone(),
+            target: local_instance.ap_id,
+            kind: Default::default(),
+            summary: person.ban_reason.clone(),
+            end_time: person.ban_expires,
+            remove_data,
+            id: generate_activity_id(context)?,
+        })
+    }
+
+    /// Send to all known instances, so that they can act on the ban.
+    pub async fn send(
+        moderator: &DbPerson,
+        person: &DbPerson,
+        remove_data: bool,
+        context: &Data<IbisContext>,
+    ) -> MyResult<()> {
+        let activity = Self::new(moderator, person, remove_data, context)?;
+        send_activity(moderator, activity, all_known_inboxes(context)?, context).await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl ActivityHandler for BlockUser {
+    type DataType = IbisContext;
+    type Error = Error;
+
+    fn id(&self) -> &Url {
+        &self.id
+    }
+
+    fn actor(&self) -> &Url {
+        self.actor.inner()
+    }
+
+    async fn verify(&self, _context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        verify_domains_match(self.actor.inner(), &self.id)?;
+        verify_domains_match(self.actor.inner(), self.target.inner())?;
+        Ok(())
+    }
+
+    async fn receive(self, context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        let instance = self.target.dereference(context).await?;
+        let person = self.object.dereference(context).await?;
+        if self.remove_data {
+            DbComment::remove_for_creator(person.id, instance.id, context)?;
+        }
+        if is_home_instance(&person, &instance) {
+            DbPerson::update_ban(person.id, true, self.summary, self.end_time, context)?;
+        }
+        Ok(())
+    }
+}
+
+/// Only the home instance can ban a user everywhere, bans from other instances only apply to
+/// their own articles.
+pub(super) fn is_home_instance(person: &DbPerson, instance: &DbInstance) -> bool {
+    extract_domain(&person.ap_id) == instance.domain
+}
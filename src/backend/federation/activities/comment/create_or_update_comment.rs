@@ -18,6 +18,7 @@ use activitypub_federation::{
     protocol::{helpers::deserialize_one_or_many, verification::verify_domains_match},
     traits::{ActivityHandler, Object},
 };
+use anyhow::anyhow;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
@@ -77,9 +78,13 @@ impl ActivityHandler for CreateOrUpdateComment {
         self.actor.inner()
     }
 
-    async fn verify(&self, _context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+    async fn verify(&self, context: &Data<Self::DataType>) -> Result<(), Self::Error> {
         verify_domains_match(&self.id, self.object.id.inner())?;
         verify_domains_match(&self.id, self.actor.inner())?;
+        let creator = self.object.attributed_to.dereference(context).await?;
+        if creator.is_banned() {
+            return Err(anyhow!("User is banned").into());
+        }
         Ok(())
     }
 
@@ -4,7 +4,11 @@ use url::Url;
 
 pub mod create_or_update_comment;
 pub mod delete_comment;
+pub mod remove_comment;
 pub mod undo_delete_comment;
+pub mod undo_remove_comment;
+pub mod undo_vote;
+pub mod vote;
 
 /// Parameter is the return value from DbInstance::read_for_comment() for this comment.
 fn generate_comment_activity_to(instance: &DbInstance) -> MyResult<Vec<Url>> {
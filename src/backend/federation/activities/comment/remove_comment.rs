@@ -0,0 +1,106 @@
+use super::generate_comment_activity_to;
+use crate::{
+    backend::{
+        database::{comment::DbCommentUpdateForm, IbisContext},
+        federation::{routes::AnnouncableActivities, send_activity_to_instance},
+        utils::{
+            error::{Error, MyResult},
+            generate_activity_id,
+        },
+    },
+    common::{comment::DbComment, instance::DbInstance, user::DbPerson},
+};
+use activitypub_federation::{
+    config::Data,
+    fetch::object_id::ObjectId,
+    kinds::activity::RemoveType,
+    protocol::{helpers::deserialize_one_or_many, verification::verify_domains_match},
+    traits::ActivityHandler,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Removal of a comment by an admin or moderator of the instance which hosts the article. This is
+/// separate from [DeleteComment](super::delete_comment::DeleteComment) so that the author cannot
+/// revert it.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoveComment {
+    pub(crate) actor: ObjectId<DbPerson>,
+    #[serde(deserialize_with = "deserialize_one_or_many")]
+    pub(crate) to: Vec<Url>,
+    pub(crate) object: ObjectId<DbComment>,
+    /// Reason for the removal
+    pub(crate) summary: Option<String>,
+    #[serde(rename = "type")]
+    pub(crate) kind: RemoveType,
+    pub(crate) id: Url,
+}
+
+impl RemoveComment {
+    pub fn new(
+        comment: &DbComment,
+        moderator: &DbPerson,
+        reason: Option<String>,
+        instance: &DbInstance,
+        context: &Data<IbisContext>,
+    ) -> MyResult<Self> {
+        let id = generate_activity_id(context)?;
+        Ok(RemoveComment {
+            actor: moderator.ap_id.clone(),
+            object: comment.ap_id.clone(),
+            to: generate_comment_activity_to(instance)?,
+            summary: reason,
+            kind: Default::default(),
+            id,
+        })
+    }
+
+    pub async fn send(
+        comment: &DbComment,
+        moderator: &DbPerson,
+        reason: Option<String>,
+        context: &Data<IbisContext>,
+    ) -> MyResult<()> {
+        let instance = DbInstance::read_for_comment(comment.id, context)?;
+        let activity = Self::new(comment, moderator, reason, &instance, context)?;
+        let activity = AnnouncableActivities::RemoveComment(activity);
+        send_activity_to_instance(moderator, activity, &instance, context).await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl ActivityHandler for RemoveComment {
+    type DataType = IbisContext;
+    type Error = Error;
+
+    fn id(&self) -> &Url {
+        &self.id
+    }
+
+    fn actor(&self) -> &Url {
+        self.actor.inner()
+    }
+
+    async fn verify(&self, context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        verify_domains_match(self.actor.inner(), &self.id)?;
+        // Only moderators of the instance hosting the article can remove comments
+        let comment = self.object.dereference(context).await?;
+        let instance = DbInstance::read_for_comment(comment.id, context)?;
+        verify_domains_match(self.actor.inner(), instance.ap_id.inner())?;
+        Ok(())
+    }
+
+    async fn receive(self, context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        let form = DbCommentUpdateForm {
+            removed: Some(true),
+            updated: Some(Utc::now()),
+            ..Default::default()
+        };
+        let comment = self.object.dereference(context).await?;
+        DbComment::update(form, comment.id, context)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,92 @@
+use super::{generate_comment_activity_to, remove_comment::RemoveComment};
+use crate::{
+    backend::{
+        database::{comment::DbCommentUpdateForm, IbisContext},
+        federation::{routes::AnnouncableActivities, send_activity_to_instance},
+        utils::{
+            error::{Error, MyResult},
+            generate_activity_id,
+        },
+    },
+    common::{comment::DbComment, instance::DbInstance, user::DbPerson},
+};
+use activitypub_federation::{
+    config::Data,
+    fetch::object_id::ObjectId,
+    kinds::activity::UndoType,
+    protocol::{
+        helpers::deserialize_one_or_many,
+        verification::{verify_domains_match, verify_urls_match},
+    },
+    traits::ActivityHandler,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UndoRemoveComment {
+    pub(crate) actor: ObjectId<DbPerson>,
+    #[serde(deserialize_with = "deserialize_one_or_many")]
+    pub(crate) to: Vec<Url>,
+    pub(crate) object: RemoveComment,
+    #[serde(rename = "type")]
+    pub(crate) kind: UndoType,
+    pub(crate) id: Url,
+}
+
+impl UndoRemoveComment {
+    pub async fn send(
+        comment: &DbComment,
+        moderator: &DbPerson,
+        context: &Data<IbisContext>,
+    ) -> MyResult<()> {
+        let instance = DbInstance::read_for_comment(comment.id, context)?;
+        let id = generate_activity_id(context)?;
+        let object = RemoveComment::new(comment, moderator, None, &instance, context)?;
+        let activity = UndoRemoveComment {
+            actor: moderator.ap_id.clone(),
+            object,
+            to: generate_comment_activity_to(&instance)?,
+            kind: Default::default(),
+            id,
+        };
+        let activity = AnnouncableActivities::UndoRemoveComment(activity);
+        send_activity_to_instance(moderator, activity, &instance, context).await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl ActivityHandler for UndoRemoveComment {
+    type DataType = IbisContext;
+    type Error = Error;
+
+    fn id(&self) -> &Url {
+        &self.id
+    }
+
+    fn actor(&self) -> &Url {
+        self.actor.inner()
+    }
+
+    async fn verify(&self, context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        // only the moderator who removed the comment can restore it
+        verify_urls_match(self.actor.inner(), self.object.actor.inner())?;
+        verify_domains_match(self.actor.inner(), &self.id)?;
+        self.object.verify(context).await?;
+        Ok(())
+    }
+
+    async fn receive(self, context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        let form = DbCommentUpdateForm {
+            removed: Some(false),
+            updated: Some(Utc::now()),
+            ..Default::default()
+        };
+        let comment = self.object.object.dereference(context).await?;
+        DbComment::update(form, comment.id, context)?;
+        Ok(())
+    }
+}
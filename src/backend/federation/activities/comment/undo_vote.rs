@@ -0,0 +1,102 @@
+use super::{
+    generate_comment_activity_to,
+    vote::{Vote, VoteType},
+};
+use crate::{
+    backend::{
+        database::IbisContext,
+        federation::{
+            activities::announce::AnnounceActivity,
+            routes::AnnouncableActivities,
+            send_activity_to_instance,
+        },
+        utils::{
+            error::{Error, MyResult},
+            generate_activity_id,
+        },
+    },
+    common::{
+        comment::{DbComment, DbCommentVote},
+        instance::DbInstance,
+        user::DbPerson,
+    },
+};
+use activitypub_federation::{
+    config::Data,
+    fetch::object_id::ObjectId,
+    kinds::activity::UndoType,
+    protocol::{
+        helpers::deserialize_one_or_many,
+        verification::{verify_domains_match, verify_urls_match},
+    },
+    traits::ActivityHandler,
+};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UndoVote {
+    pub(crate) actor: ObjectId<DbPerson>,
+    #[serde(deserialize_with = "deserialize_one_or_many")]
+    pub(crate) to: Vec<Url>,
+    pub(crate) object: Vote,
+    #[serde(rename = "type")]
+    pub(crate) kind: UndoType,
+    pub(crate) id: Url,
+}
+
+impl UndoVote {
+    pub async fn send(
+        comment: &DbComment,
+        voter: &DbPerson,
+        kind: VoteType,
+        context: &Data<IbisContext>,
+    ) -> MyResult<()> {
+        let instance = DbInstance::read_for_comment(comment.id, context)?;
+        let id = generate_activity_id(context)?;
+        let object = Vote::new(comment, voter, kind, &instance, context)?;
+        let activity = UndoVote {
+            actor: voter.ap_id.clone(),
+            object,
+            to: generate_comment_activity_to(&instance)?,
+            kind: Default::default(),
+            id,
+        };
+        let activity = AnnouncableActivities::UndoVote(activity);
+        send_activity_to_instance(voter, activity, &instance, context).await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl ActivityHandler for UndoVote {
+    type DataType = IbisContext;
+    type Error = Error;
+
+    fn id(&self) -> &Url {
+        &self.id
+    }
+
+    fn actor(&self) -> &Url {
+        self.actor.inner()
+    }
+
+    async fn verify(&self, _context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        verify_urls_match(self.actor.inner(), self.object.actor.inner())?;
+        verify_domains_match(self.actor.inner(), &self.id)?;
+        Ok(())
+    }
+
+    async fn receive(self, context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        let voter = self.actor.dereference(context).await?;
+        let comment = self.object.object.dereference(context).await?;
+        DbCommentVote::delete(voter.id, comment.id, context)?;
+
+        let instance = DbInstance::read_for_comment(comment.id, context)?;
+        if instance.local {
+            AnnounceActivity::send(AnnouncableActivities::UndoVote(self), context).await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,136 @@
+use super::generate_comment_activity_to;
+use crate::{
+    backend::{
+        database::{comment::DbCommentVoteForm, IbisContext},
+        federation::{
+            activities::announce::AnnounceActivity,
+            routes::AnnouncableActivities,
+            send_activity_to_instance,
+        },
+        utils::{
+            error::{Error, MyResult},
+            generate_activity_id,
+        },
+    },
+    common::{
+        comment::{DbComment, DbCommentVote},
+        instance::DbInstance,
+        user::DbPerson,
+    },
+};
+use activitypub_federation::{
+    config::Data,
+    fetch::object_id::ObjectId,
+    protocol::{helpers::deserialize_one_or_many, verification::verify_domains_match},
+    traits::ActivityHandler,
+};
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub enum VoteType {
+    Like,
+    Dislike,
+}
+
+impl VoteType {
+    pub fn from_score(score: i16) -> Self {
+        if score > 0 {
+            VoteType::Like
+        } else {
+            VoteType::Dislike
+        }
+    }
+
+    pub fn score(&self) -> i16 {
+        match self {
+            VoteType::Like => 1,
+            VoteType::Dislike => -1,
+        }
+    }
+}
+
+/// Upvote or downvote on a comment
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Vote {
+    pub(crate) actor: ObjectId<DbPerson>,
+    #[serde(deserialize_with = "deserialize_one_or_many")]
+    pub(crate) to: Vec<Url>,
+    pub(crate) object: ObjectId<DbComment>,
+    #[serde(rename = "type")]
+    pub(crate) kind: VoteType,
+    pub(crate) id: Url,
+}
+
+impl Vote {
+    pub fn new(
+        comment: &DbComment,
+        voter: &DbPerson,
+        kind: VoteType,
+        instance: &DbInstance,
+        context: &Data<IbisContext>,
+    ) -> MyResult<Self> {
+        let id = generate_activity_id(context)?;
+        Ok(Vote {
+            actor: voter.ap_id.clone(),
+            object: comment.ap_id.clone(),
+            to: generate_comment_activity_to(instance)?,
+            kind,
+            id,
+        })
+    }
+
+    pub async fn send(
+        comment: &DbComment,
+        voter: &DbPerson,
+        kind: VoteType,
+        context: &Data<IbisContext>,
+    ) -> MyResult<()> {
+        let instance = DbInstance::read_for_comment(comment.id, context)?;
+        let activity = Self::new(comment, voter, kind, &instance, context)?;
+        let activity = AnnouncableActivities::Vote(activity);
+        send_activity_to_instance(voter, activity, &instance, context).await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl ActivityHandler for Vote {
+    type DataType = IbisContext;
+    type Error = Error;
+
+    fn id(&self) -> &Url {
+        &self.id
+    }
+
+    fn actor(&self) -> &Url {
+        self.actor.inner()
+    }
+
+    async fn verify(&self, _context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        verify_domains_match(self.actor.inner(), &self.id)?;
+        Ok(())
+    }
+
+    async fn receive(self, context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        let voter = self.actor.dereference(context).await?;
+        if voter.is_banned() {
+            return Err(anyhow!("User is banned").into());
+        }
+        let comment = self.object.dereference(context).await?;
+        let form = DbCommentVoteForm {
+            person_id: voter.id,
+            comment_id: comment.id,
+            score: self.kind.score(),
+        };
+        DbCommentVote::create_or_update(form, context)?;
+
+        let instance = DbInstance::read_for_comment(comment.id, context)?;
+        if instance.local {
+            AnnounceActivity::send(AnnouncableActivities::Vote(self), context).await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,89 @@
+use crate::{
+    backend::{
+        database::IbisContext,
+        federation::{activities::all_known_inboxes, send_activity},
+        utils::{
+            error::{Error, MyResult},
+            generate_activity_id,
+        },
+    },
+    common::user::DbPerson,
+};
+use activitypub_federation::{
+    config::Data,
+    fetch::object_id::ObjectId,
+    kinds::{activity::DeleteType, public},
+    protocol::{
+        helpers::deserialize_one_or_many,
+        verification::{verify_domains_match, verify_urls_match},
+    },
+    traits::ActivityHandler,
+};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Sent when a user account is deleted, so that other instances reassign the user's edits and
+/// comments to their ghost user.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteUser {
+    pub(crate) actor: ObjectId<DbPerson>,
+    #[serde(deserialize_with = "deserialize_one_or_many")]
+    pub(crate) to: Vec<Url>,
+    pub(crate) object: ObjectId<DbPerson>,
+    #[serde(rename = "type")]
+    pub(crate) kind: DeleteType,
+    /// Whether the content of comments written by the user should be wiped
+    pub(crate) remove_data: bool,
+    pub(crate) id: Url,
+}
+
+impl DeleteUser {
+    /// Send to all known instances. Needs to be called before the user is removed from the
+    /// database, so that the account can still be fetched by the receiving instances.
+    pub async fn send(
+        person: &DbPerson,
+        remove_data: bool,
+        context: &Data<IbisContext>,
+    ) -> MyResult<()> {
+        let id = generate_activity_id(context)?;
+        let activity = DeleteUser {
+            actor: person.ap_id.clone(),
+            to: vec![public()],
+            object: person.ap_id.clone(),
+            kind: Default::default(),
+            remove_data,
+            id,
+        };
+        send_activity(person, activity, all_known_inboxes(context)?, context).await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl ActivityHandler for DeleteUser {
+    type DataType = IbisContext;
+    type Error = Error;
+
+    fn id(&self) -> &Url {
+        &self.id
+    }
+
+    fn actor(&self) -> &Url {
+        self.actor.inner()
+    }
+
+    async fn verify(&self, _context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        verify_urls_match(self.actor.inner(), self.object.inner())?;
+        verify_domains_match(self.actor.inner(), &self.id)?;
+        Ok(())
+    }
+
+    async fn receive(self, context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        // Dont dereference, the user is already gone on its home instance
+        if let Ok(person) = DbPerson::read_from_ap_id(&self.object, context) {
+            DbPerson::delete_account(person.id, self.remove_data, context)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,119 @@
+use crate::{
+    backend::{
+        database::{report::DbReportForm, IbisContext},
+        federation::send_activity,
+        utils::error::{Error, MyResult},
+    },
+    common::{
+        article::{DbArticle, DbEdit},
+        comment::DbComment,
+        instance::DbInstance,
+        report::DbReport,
+        user::DbPerson,
+    },
+};
+use activitypub_federation::{
+    config::Data,
+    fetch::object_id::ObjectId,
+    kinds::activity::FlagType,
+    protocol::{helpers::deserialize_one_or_many, verification::verify_domains_match},
+    traits::ActivityHandler,
+};
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Report about an article, edit or comment. Sent to the instance which hosts the article, so
+/// that its moderators can handle it.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Flag {
+    pub(crate) actor: ObjectId<DbPerson>,
+    #[serde(deserialize_with = "deserialize_one_or_many")]
+    pub(crate) to: Vec<Url>,
+    /// Id of the reported article, edit or comment
+    pub(crate) object: Url,
+    /// Reason for the report
+    pub(crate) summary: String,
+    #[serde(rename = "type")]
+    pub(crate) kind: FlagType,
+    pub(crate) id: Url,
+}
+
+impl Flag {
+    pub async fn send(
+        creator: &DbPerson,
+        report: &DbReport,
+        object: Url,
+        instance: &DbInstance,
+        context: &Data<IbisContext>,
+    ) -> MyResult<()> {
+        let flag = Flag {
+            actor: creator.ap_id.clone(),
+            to: vec![instance.ap_id.inner().clone()],
+            object,
+            summary: report.reason.clone(),
+            kind: Default::default(),
+            id: Url::parse(&report.ap_id)?,
+        };
+        send_activity(creator, flag, vec![instance.inbox_url()], context).await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl ActivityHandler for Flag {
+    type DataType = IbisContext;
+    type Error = Error;
+
+    fn id(&self) -> &Url {
+        &self.id
+    }
+
+    fn actor(&self) -> &Url {
+        self.actor.inner()
+    }
+
+    async fn verify(&self, _context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        verify_domains_match(self.actor.inner(), &self.id)?;
+        Ok(())
+    }
+
+    async fn receive(self, context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        let creator = self.actor.dereference(context).await?;
+        if creator.is_banned() {
+            return Err(anyhow!("User is banned").into());
+        }
+        let (article, edit_id, comment_id) =
+            if let Ok(article) = DbArticle::read_from_ap_id(&self.object.clone().into(), context) {
+                (article, None, None)
+            } else if let Ok(edit) = DbEdit::read_from_ap_id(&self.object.clone().into(), context) {
+                (
+                    DbArticle::read(edit.article_id, context)?,
+                    Some(edit.id),
+                    None,
+                )
+            } else {
+                let comment = DbComment::read_from_ap_id(&self.object.into(), context)?;
+                (
+                    DbArticle::read(comment.article_id, context)?,
+                    None,
+                    Some(comment.id),
+                )
+            };
+        if !article.local {
+            return Err(anyhow!("Received report for article on other instance").into());
+        }
+        let form = DbReportForm {
+            creator_id: creator.id,
+            article_id: article.id,
+            edit_id,
+            comment_id,
+            reason: self.summary,
+            ap_id: self.id.to_string(),
+            local: false,
+        };
+        DbReport::create(&form, context)?;
+        Ok(())
+    }
+}
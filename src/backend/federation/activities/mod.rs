@@ -5,7 +5,7 @@ use crate::{
             update_local_article::UpdateLocalArticle,
             update_remote_article::UpdateRemoteArticle,
         },
-        utils::error::Error,
+        utils::error::{Error, MyResult},
     },
     common::{
         article::{DbArticle, DbEdit, EditVersion},
@@ -14,13 +14,19 @@ use crate::{
     },
 };
 use activitypub_federation::config::Data;
+use std::collections::HashSet;
+use url::Url;
 
 pub mod accept;
 pub mod announce;
+pub mod block_user;
 pub mod comment;
 pub mod create_article;
+pub mod delete_user;
+pub mod flag;
 pub mod follow;
 pub mod reject;
+pub mod undo_block_user;
 pub mod update_local_article;
 pub mod update_remote_article;
 
@@ -54,3 +60,20 @@ pub async fn submit_article_update(
     }
     Ok(())
 }
+
+/// Inboxes of all remote instances which follow the local instance or which are known to it. Used
+/// for activities about users, which are relevant for the whole network.
+pub(crate) fn all_known_inboxes(context: &Data<IbisContext>) -> MyResult<Vec<Url>> {
+    let local_instance = DbInstance::read_local(context)?;
+    let mut inboxes: HashSet<Url> = DbInstance::read_followers(local_instance.id, context)?
+        .into_iter()
+        .filter(|f| !f.local)
+        .map(|f| f.inbox_url())
+        .collect();
+    inboxes.extend(
+        DbInstance::list(true, None, context)?
+            .into_iter()
+            .map(|i| i.inbox_url()),
+    );
+    Ok(inboxes.into_iter().collect())
+}
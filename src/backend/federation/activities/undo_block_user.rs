@@ -0,0 +1,85 @@
+use super::block_user::{is_home_instance, BlockUser};
+use crate::{
+    backend::{
+        database::IbisContext,
+        federation::{activities::all_known_inboxes, send_activity},
+        utils::{
+            error::{Error, MyResult},
+            generate_activity_id,
+        },
+    },
+    common::user::DbPerson,
+};
+use activitypub_federation::{
+    config::Data,
+    fetch::object_id::ObjectId,
+    kinds::{activity::UndoType, public},
+    protocol::{
+        helpers::deserialize_one_or_many,
+        verification::{verify_domains_match, verify_urls_match},
+    },
+    traits::ActivityHandler,
+};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Sent when a user is unbanned from the local instance.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UndoBlockUser {
+    pub(crate) actor: ObjectId<DbPerson>,
+    #[serde(deserialize_with = "deserialize_one_or_many")]
+    pub(crate) to: Vec<Url>,
+    pub(crate) object: BlockUser,
+    #[serde(rename = "type")]
+    pub(crate) kind: UndoType,
+    pub(crate) id: Url,
+}
+
+impl UndoBlockUser {
+    pub async fn send(
+        moderator: &DbPerson,
+        person: &DbPerson,
+        context: &Data<IbisContext>,
+    ) -> MyResult<()> {
+        let activity = UndoBlockUser {
+            actor: moderator.ap_id.clone(),
+            to: vec![public()],
+            object: BlockUser::new(moderator, person, false, context)?,
+            kind: Default::default(),
+            id: generate_activity_id(context)?,
+        };
+        send_activity(moderator, activity, all_known_inboxes(context)?, context).await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl ActivityHandler for UndoBlockUser {
+    type DataType = IbisContext;
+    type Error = Error;
+
+    fn id(&self) -> &Url {
+        &self.id
+    }
+
+    fn actor(&self) -> &Url {
+        self.actor.inner()
+    }
+
+    async fn verify(&self, context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        verify_domains_match(self.actor.inner(), &self.id)?;
+        verify_urls_match(self.actor.inner(), self.object.actor.inner())?;
+        self.object.verify(context).await?;
+        Ok(())
+    }
+
+    async fn receive(self, context: &Data<Self::DataType>) -> Result<(), Self::Error> {
+        let instance = self.object.target.dereference(context).await?;
+        let person = self.object.object.dereference(context).await?;
+        if is_home_instance(&person, &instance) {
+            DbPerson::update_ban(person.id, false, None, None, context)?;
+        }
+        Ok(())
+    }
+}
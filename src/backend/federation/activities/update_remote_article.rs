@@ -24,6 +24,7 @@ use activitypub_federation::{
     protocol::helpers::deserialize_one_or_many,
     traits::{ActivityHandler, Object},
 };
+use anyhow::anyhow;
 use diffy::{apply, Patch};
 use serde::{Deserialize, Serialize};
 use url::Url;
@@ -83,6 +84,10 @@ impl ActivityHandler for UpdateRemoteArticle {
     async fn verify(&self, context: &Data<Self::DataType>) -> Result<(), Self::Error> {
         let article = DbArticle::read_from_ap_id(&self.object.object, context)?;
         can_edit_article(&article, false)?;
+        let creator = self.object.attributed_to.dereference(context).await?;
+        if creator.is_banned() {
+            return Err(anyhow!("User is banned").into());
+        }
         Ok(())
     }
 
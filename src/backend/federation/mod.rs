@@ -1,7 +1,11 @@
 use super::utils::error::MyResult;
 use crate::{
-    backend::{database::IbisContext, utils::config::IbisConfig},
-    common::{instance::DbInstance, user::DbPerson},
+    backend::database::{federation_list::FederationListCache, DbPool, IbisContext},
+    common::{
+        instance::{DbInstance, InstanceStatsDay},
+        user::DbPerson,
+        utils::url_domain,
+    },
 };
 use activities::announce::AnnounceActivity;
 use activitypub_federation::{
@@ -12,9 +16,10 @@ use activitypub_federation::{
     traits::{ActivityHandler, Actor},
 };
 use async_trait::async_trait;
+use log::warn;
 use routes::AnnouncableActivities;
 use serde::Serialize;
-use std::fmt::Debug;
+use std::{fmt::Debug, sync::Arc};
 use url::Url;
 
 pub mod activities;
@@ -32,7 +37,11 @@ where
     <Activity as ActivityHandler>::Error: From<activitypub_federation::error::Error>,
 {
     let activity = WithContext::new_default(activity);
+    let sent = recipients.len() as i32;
     queue_activity(&activity, actor, recipients, context).await?;
+    InstanceStatsDay::count_activities(0, sent, context)
+        .inspect_err(|e| warn!("Failed to count sent activities: {e}"))
+        .ok();
     Ok(())
 }
 
@@ -52,29 +61,14 @@ pub async fn send_activity_to_instance(
 }
 
 #[derive(Clone)]
-pub struct VerifyUrlData(pub IbisConfig);
+pub struct VerifyUrlData(pub DbPool, pub Arc<FederationListCache>);
 
 #[async_trait]
 impl UrlVerifier for VerifyUrlData {
-    /// Check domain against allowlist and blocklist from config file.
+    /// Check domain against allowlist and blocklist which are stored in the database.
     async fn verify(&self, url: &Url) -> Result<(), ActivityPubError> {
-        let domain = url.domain().expect("url has domain");
-        if let Some(allowlist) = &self.0.federation.allowlist {
-            let allowlist = allowlist.split(',').collect::<Vec<_>>();
-            if !allowlist.contains(&domain) {
-                return Err(ActivityPubError::Other(format!(
-                    "Domain {domain} is not allowed"
-                )));
-            }
-        }
-        if let Some(blocklist) = &self.0.federation.blocklist {
-            let blocklist = blocklist.split(',').collect::<Vec<_>>();
-            if blocklist.contains(&domain) {
-                return Err(ActivityPubError::Other(format!(
-                    "Domain {domain} is blocked"
-                )));
-            }
-        }
-        Ok(())
+        self.1
+            .check(&url_domain(url), &self.0)
+            .map_err(|e| ActivityPubError::Other(e.to_string()))
     }
 }
@@ -2,7 +2,10 @@ use crate::{
     backend::{
         database::{article::DbArticleForm, IbisContext},
         federation::objects::edits_collection::DbEditCollection,
-        utils::{error::Error, validate::validate_article_title},
+        utils::{
+            error::Error,
+            validate::{validate_article_language, validate_article_title},
+        },
     },
     common::{
         article::{DbArticle, EditVersion},
@@ -36,6 +39,9 @@ pub struct ApubArticle {
     content: String,
     name: String,
     protected: bool,
+    /// ISO 639-1 code
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    language: Option<String>,
 }
 
 #[async_trait::async_trait]
@@ -64,6 +70,7 @@ impl Object for DbArticle {
             content: self.text,
             name: self.title,
             protected: self.protected,
+            language: self.language,
         })
     }
 
@@ -90,6 +97,8 @@ impl Object for DbArticle {
             instance_id: instance.id,
             protected: json.protected,
             approved: true,
+            // ignore languages which we dont know
+            language: validate_article_language(json.language).ok().flatten(),
         };
         form.title = validate_article_title(&form.title)?;
         let article = DbArticle::create_or_update(form, context)?;
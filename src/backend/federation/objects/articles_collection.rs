@@ -48,7 +48,7 @@ impl Collection for DbArticleCollection {
         _owner: &Self::Owner,
         context: &Data<Self::DataType>,
     ) -> Result<Self::Kind, Self::Error> {
-        let local_articles = DbArticle::read_all(Some(true), None, context)?;
+        let local_articles = DbArticle::read_all(Some(true), None, None, None, None, context)?;
         let articles = try_join_all(
             local_articles
                 .into_iter()
@@ -48,7 +48,7 @@ impl Collection for DbInstanceCollection {
         _owner: &Self::Owner,
         context: &Data<Self::DataType>,
     ) -> Result<Self::Kind, Self::Error> {
-        let instances = DbInstance::list(true, context)?;
+        let instances = DbInstance::list(true, None, context)?;
         let instances = future::try_join_all(
             instances
                 .into_iter()
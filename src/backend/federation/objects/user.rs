@@ -8,7 +8,6 @@ use crate::{
 use activitypub_federation::{
     config::Data,
     fetch::object_id::ObjectId,
-    kinds::actor::PersonType,
     protocol::{public_key::PublicKey, verification::verify_domains_match},
     traits::{Actor, Object},
 };
@@ -17,11 +16,18 @@ use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 use url::Url;
 
+/// Bot accounts use the `Service` type, like in other Fediverse platforms.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub enum UserTypes {
+    Person,
+    Service,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ApubUser {
     #[serde(rename = "type")]
-    kind: PersonType,
+    kind: UserTypes,
     id: ObjectId<DbPerson>,
     preferred_username: String,
     /// displayname
@@ -49,8 +55,13 @@ impl Object for DbPerson {
     }
 
     async fn into_json(self, _context: &Data<Self::DataType>) -> Result<Self::Kind, Self::Error> {
+        let kind = if self.bot {
+            UserTypes::Service
+        } else {
+            UserTypes::Person
+        };
         Ok(ApubUser {
-            kind: Default::default(),
+            kind,
             id: __self.ap_id.clone(),
             preferred_username: __self.username.clone(),
             inbox: Url::parse(&__self.inbox_url)?,
@@ -83,6 +94,7 @@ impl Object for DbPerson {
             local: false,
             display_name: json.name,
             bio: json.summary,
+            bot: json.kind == UserTypes::Service,
         };
         DbPerson::create(&form, context)
     }
@@ -2,7 +2,11 @@ use super::{
     activities::comment::{
         create_or_update_comment::CreateOrUpdateComment,
         delete_comment::DeleteComment,
+        remove_comment::RemoveComment,
         undo_delete_comment::UndoDeleteComment,
+        undo_remove_comment::UndoRemoveComment,
+        undo_vote::UndoVote,
+        vote::Vote,
     },
     objects::{
         comment::ApubComment,
@@ -16,9 +20,13 @@ use crate::{
             activities::{
                 accept::Accept,
                 announce::AnnounceActivity,
+                block_user::BlockUser,
                 create_article::CreateArticle,
+                delete_user::DeleteUser,
+                flag::Flag,
                 follow::Follow,
                 reject::RejectEdit,
+                undo_block_user::UndoBlockUser,
                 update_local_article::UpdateLocalArticle,
                 update_remote_article::UpdateRemoteArticle,
             },
@@ -35,7 +43,7 @@ use crate::{
     common::{
         article::DbArticle,
         comment::DbComment,
-        instance::DbInstance,
+        instance::{DbInstance, InstanceStatsDay},
         newtypes::CommentId,
         user::DbPerson,
     },
@@ -57,6 +65,7 @@ use axum::{
 };
 use axum_macros::debug_handler;
 use chrono::{DateTime, Utc};
+use log::warn;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
@@ -148,6 +157,11 @@ pub enum InboxActivities {
     UpdateLocalArticle(UpdateLocalArticle),
     UpdateRemoteArticle(UpdateRemoteArticle),
     RejectEdit(RejectEdit),
+    // Needs to be before `DeleteComment` which has the same fields, except `remove_data`
+    DeleteUser(DeleteUser),
+    BlockUser(BlockUser),
+    UndoBlockUser(UndoBlockUser),
+    Flag(Flag),
     AnnounceActivity(AnnounceActivity),
     AnnouncableActivities(AnnouncableActivities),
 }
@@ -159,6 +173,10 @@ pub enum AnnouncableActivities {
     CreateOrUpdateComment(CreateOrUpdateComment),
     DeleteComment(DeleteComment),
     UndoDeleteComment(UndoDeleteComment),
+    RemoveComment(RemoveComment),
+    UndoRemoveComment(UndoRemoveComment),
+    Vote(Vote),
+    UndoVote(UndoVote),
 }
 
 #[debug_handler]
@@ -166,8 +184,17 @@ pub async fn http_post_inbox(
     context: Data<IbisContext>,
     activity_data: ActivityData,
 ) -> impl IntoResponse {
-    receive_activity::<WithContext<InboxActivities>, UserOrInstance, _>(activity_data, &context)
-        .await
+    let res = receive_activity::<WithContext<InboxActivities>, UserOrInstance, _>(
+        activity_data,
+        &context,
+    )
+    .await;
+    if res.is_ok() {
+        InstanceStatsDay::count_activities(1, 0, &context)
+            .inspect_err(|e| warn!("Failed to count received activity: {e}"))
+            .ok();
+    }
+    res
 }
 
 #[derive(Clone, Debug)]
@@ -10,7 +10,7 @@ use crate::{
     },
     common::{
         article::{DbArticle, EditVersion},
-        instance::DbInstance,
+        instance::{DbFederationListEntry, DbInstance},
         user::DbPerson,
         utils::http_protocol_str,
         MAIN_PAGE_NAME,
@@ -58,10 +58,20 @@ pub async fn start(
         .get()?
         .run_pending_migrations(MIGRATIONS)
         .expect("run migrations");
-    let context = IbisContext { db_pool, config };
+    DbFederationListEntry::import_config(&config, &db_pool)?;
+    let context = IbisContext {
+        db_pool,
+        config,
+        rate_limit: Default::default(),
+        federation_list: Default::default(),
+        view_limit: Default::default(),
+    };
     let data = FederationConfig::builder()
         .domain(context.config.federation.domain.clone())
-        .url_verifier(Box::new(VerifyUrlData(context.config.clone())))
+        .url_verifier(Box::new(VerifyUrlData(
+            context.db_pool.clone(),
+            context.federation_list.clone(),
+        )))
         .app_data(context)
         .http_fetch_limit(1000)
         .debug(cfg!(debug_assertions))
@@ -127,6 +137,7 @@ async fn setup(context: &Data<IbisContext>) -> Result<(), Error> {
         local: true,
         protected: true,
         approved: true,
+        language: None,
     };
     let article = DbArticle::create(form, context)?;
     // also create an article so its included in most recently edited list
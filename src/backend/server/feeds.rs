@@ -0,0 +1,329 @@
+use crate::{
+    backend::{
+        database::{edit::ViewEditParams, IbisContext},
+        utils::{error::MyResult, pagination::Pagination},
+    },
+    common::{
+        article::{DbArticle, DbEdit, EditView, RecentChangesParams},
+        user::DbPerson,
+        utils::{article_path, extract_domain, http_protocol_str},
+    },
+};
+use activitypub_federation::config::Data;
+use anyhow::anyhow;
+use axum::{
+    extract::{Path, Query},
+    http::header::CONTENT_TYPE,
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+/// Number of entries in each feed
+const FEED_LENGTH: i64 = 50;
+/// Maximum number of diff lines which are included in an entry
+const DIFF_EXCERPT_LINES: usize = 20;
+
+pub fn config() -> Router<()> {
+    Router::new()
+        .route("/feed/recent_changes", get(recent_changes_feed))
+        .route("/feed/new_articles", get(new_articles_feed))
+        .route("/feed/article/:title", get(article_history_feed))
+        .route("/feed/user/:name", get(user_contributions_feed))
+}
+
+#[derive(Deserialize, Default, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+enum FeedFormat {
+    #[default]
+    Atom,
+    Rss,
+}
+
+#[derive(Deserialize)]
+struct FeedParams {
+    #[serde(default)]
+    format: FeedFormat,
+}
+
+async fn recent_changes_feed(
+    Query(params): Query<FeedParams>,
+    context: Data<IbisContext>,
+) -> MyResult<Response> {
+    let edits = DbEdit::recent_changes(
+        &Default::default(),
+        feed_pagination(),
+        &None,
+        false,
+        &context,
+    )?;
+    let feed = Feed {
+        title: format!("Recent changes - {}", context.domain()),
+        feed_path: "/feed/recent_changes".to_string(),
+        html_path: "/recent_changes".to_string(),
+        edits,
+    };
+    Ok(feed.render(params.format, &context))
+}
+
+async fn new_articles_feed(
+    Query(params): Query<FeedParams>,
+    context: Data<IbisContext>,
+) -> MyResult<Response> {
+    let recent_changes = RecentChangesParams {
+        new_articles: Some(true),
+        ..Default::default()
+    };
+    let edits = DbEdit::recent_changes(&recent_changes, feed_pagination(), &None, false, &context)?;
+    let feed = Feed {
+        title: format!("New articles - {}", context.domain()),
+        feed_path: "/feed/new_articles".to_string(),
+        html_path: "/articles".to_string(),
+        edits,
+    };
+    Ok(feed.render(params.format, &context))
+}
+
+/// Edit history of a single article, the title can be given as `title@domain` for remote
+/// articles.
+async fn article_history_feed(
+    Path(title): Path<String>,
+    Query(params): Query<FeedParams>,
+    context: Data<IbisContext>,
+) -> MyResult<Response> {
+    let (title, domain) = split_name(&title);
+    let article = DbArticle::read_view_title(title, domain, &context)?.article;
+    // feeds are public, so they must not expose articles which are waiting for approval
+    if !article.approved {
+        return Err(anyhow!("Article not found").into());
+    }
+    let edits = DbEdit::view(
+        ViewEditParams::ArticleId(article.id),
+        false,
+        feed_pagination(),
+        &None,
+        &context,
+    )?;
+    let path = article_path(&article);
+    let feed = Feed {
+        title: format!("History of {}", article.title.replace('_', " ")),
+        feed_path: format!("/feed{path}"),
+        html_path: format!("{path}/history"),
+        edits,
+    };
+    Ok(feed.render(params.format, &context))
+}
+
+/// Edits by a single user, the name can be given as `name@domain` for remote users.
+async fn user_contributions_feed(
+    Path(name): Path<String>,
+    Query(params): Query<FeedParams>,
+    context: Data<IbisContext>,
+) -> MyResult<Response> {
+    let (username, domain) = split_name(&name);
+    let person = DbPerson::read_from_name(username, &domain, &context)?;
+    let mut edits = DbEdit::view(
+        ViewEditParams::PersonId(person.id),
+        false,
+        feed_pagination(),
+        &None,
+        &context,
+    )?;
+    edits.retain(|e| e.article.approved);
+    let feed = Feed {
+        title: format!("Contributions of {name}"),
+        feed_path: format!("/feed/user/{name}"),
+        html_path: format!("/user/{name}"),
+        edits,
+    };
+    Ok(feed.render(params.format, &context))
+}
+
+fn feed_pagination() -> Pagination {
+    Pagination {
+        offset: 0,
+        limit: FEED_LENGTH,
+    }
+}
+
+fn split_name(name: &str) -> (&str, Option<String>) {
+    match name.split_once('@') {
+        Some((name, domain)) => (name, Some(domain.to_string())),
+        None => (name, None),
+    }
+}
+
+struct Feed {
+    title: String,
+    feed_path: String,
+    html_path: String,
+    /// Newest first
+    edits: Vec<EditView>,
+}
+
+/// Data for a single feed entry, not yet escaped.
+struct FeedEntry {
+    id: String,
+    title: String,
+    link: String,
+    published: DateTime<Utc>,
+    author: String,
+    /// Html with edit summary and diff excerpt
+    content: String,
+}
+
+impl FeedEntry {
+    fn new(edit: &EditView, base_url: &str) -> Self {
+        let author = if edit.creator.local {
+            edit.creator.username.clone()
+        } else {
+            format!(
+                "{}@{}",
+                edit.creator.username,
+                extract_domain(&edit.creator.ap_id)
+            )
+        };
+        let content = format!(
+            "<p>{}</p><pre>{}</pre>",
+            escape_xml(&edit.edit.summary),
+            escape_xml(&diff_excerpt(&edit.edit.diff))
+        );
+        FeedEntry {
+            id: edit.edit.ap_id.inner().to_string(),
+            title: format!(
+                "{}: {}",
+                edit.article.title.replace('_', " "),
+                edit.edit.summary
+            ),
+            link: format!(
+                "{base_url}{}/diff/{}",
+                article_path(&edit.article),
+                edit.edit.hash.0
+            ),
+            published: edit.edit.published,
+            author,
+            content,
+        }
+    }
+}
+
+impl Feed {
+    fn render(self, format: FeedFormat, context: &Data<IbisContext>) -> Response {
+        let base_url = format!("{}://{}", http_protocol_str(), context.domain());
+        let entries: Vec<_> = self
+            .edits
+            .iter()
+            .map(|e| FeedEntry::new(e, &base_url))
+            .collect();
+        let updated = entries
+            .first()
+            .map(|e| e.published)
+            .unwrap_or_else(Utc::now);
+        let (content_type, xml) = match format {
+            FeedFormat::Atom => (
+                "application/atom+xml; charset=utf-8",
+                self.atom(&entries, updated, &base_url),
+            ),
+            FeedFormat::Rss => (
+                "application/rss+xml; charset=utf-8",
+                self.rss(&entries, updated, &base_url),
+            ),
+        };
+        ([(CONTENT_TYPE, content_type)], xml).into_response()
+    }
+
+    fn atom(&self, entries: &[FeedEntry], updated: DateTime<Utc>, base_url: &str) -> String {
+        let mut xml = format!(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+<id>{base_url}{feed_path}</id>
+<title>{title}</title>
+<updated>{updated}</updated>
+<link rel="self" href="{base_url}{feed_path}"/>
+<link rel="alternate" type="text/html" href="{base_url}{html_path}"/>
+"#,
+            feed_path = escape_xml(&self.feed_path),
+            html_path = escape_xml(&self.html_path),
+            title = escape_xml(&self.title),
+            updated = updated.to_rfc3339(),
+        );
+        for entry in entries {
+            xml.push_str(&format!(
+                r#"<entry>
+<id>{id}</id>
+<title>{title}</title>
+<link rel="alternate" type="text/html" href="{link}"/>
+<updated>{published}</updated>
+<author><name>{author}</name></author>
+<content type="html">{content}</content>
+</entry>
+"#,
+                id = escape_xml(&entry.id),
+                title = escape_xml(&entry.title),
+                link = escape_xml(&entry.link),
+                published = entry.published.to_rfc3339(),
+                author = escape_xml(&entry.author),
+                content = escape_xml(&entry.content),
+            ));
+        }
+        xml.push_str("</feed>\n");
+        xml
+    }
+
+    fn rss(&self, entries: &[FeedEntry], updated: DateTime<Utc>, base_url: &str) -> String {
+        let mut xml = format!(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+<rss version="2.0" xmlns:dc="http://purl.org/dc/elements/1.1/">
+<channel>
+<title>{title}</title>
+<link>{base_url}{html_path}</link>
+<description>{title}</description>
+<lastBuildDate>{updated}</lastBuildDate>
+"#,
+            html_path = escape_xml(&self.html_path),
+            title = escape_xml(&self.title),
+            updated = updated.to_rfc2822(),
+        );
+        for entry in entries {
+            xml.push_str(&format!(
+                r#"<item>
+<title>{title}</title>
+<link>{link}</link>
+<guid isPermaLink="false">{id}</guid>
+<pubDate>{published}</pubDate>
+<dc:creator>{author}</dc:creator>
+<description>{content}</description>
+</item>
+"#,
+                id = escape_xml(&entry.id),
+                title = escape_xml(&entry.title),
+                link = escape_xml(&entry.link),
+                published = entry.published.to_rfc2822(),
+                author = escape_xml(&entry.author),
+                content = escape_xml(&entry.content),
+            ));
+        }
+        xml.push_str("</channel>\n</rss>\n");
+        xml
+    }
+}
+
+/// First lines of the diff, without the file header.
+fn diff_excerpt(diff: &str) -> String {
+    let mut lines = diff.lines().skip_while(|l| !l.starts_with("@@"));
+    let mut excerpt: Vec<_> = lines.by_ref().take(DIFF_EXCERPT_LINES).collect();
+    if lines.next().is_some() {
+        excerpt.push("...");
+    }
+    excerpt.join("\n")
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
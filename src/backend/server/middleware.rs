@@ -1,17 +1,24 @@
 use crate::{
     backend::{api::user::validate, database::IbisContext},
-    common::{Auth, AUTH_COOKIE},
+    common::{user::DbApiToken, Auth, AUTH_COOKIE},
 };
 use axum::{body::Body, extract::State, http::Request, middleware::Next, response::Response};
 use axum_macros::debug_middleware;
-use http::{header::COOKIE, HeaderValue};
+use http::{
+    header::{AUTHORIZATION, COOKIE},
+    HeaderValue,
+};
 use std::{collections::HashSet, sync::Arc};
 
 pub(super) const FEDERATION_ROUTES_PREFIX: &str = "/federation_routes";
 
 /// Checks all headers and cookies (including duplicates) for first valid auth token.
 /// We need to extract cookies manually because CookieJar ignores duplicates.
-/// If user is authenticated sets extensions `Auth` and `LocalUserView`.
+/// If user is authenticated sets extensions `Auth` and `LocalUserView`. Banned users are treated
+/// as logged out.
+///
+/// Alternatively an api token can be passed in `Authorization: Bearer` header. In this case
+/// extensions `DbApiToken` and `LocalUserView` are set.
 #[debug_middleware]
 pub(super) async fn auth_middleware(
     State(context): State<Arc<IbisContext>>,
@@ -33,10 +40,27 @@ pub(super) async fn auth_middleware(
         .filter_map(|h| h.to_str().ok());
     let auth: HashSet<_> = headers.chain(cookies).map(|s| s.to_string()).collect();
 
+    let bearer = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .map(|h| h.trim().to_string());
+
     for auth in auth {
         if let Ok(local_user) = validate(&auth, &context).await {
-            request.extensions_mut().insert(Auth(Some(auth)));
-            request.extensions_mut().insert(local_user);
+            if !local_user.person.is_banned() {
+                request.extensions_mut().insert(Auth(Some(auth)));
+                request.extensions_mut().insert(local_user);
+            }
+        }
+    }
+    if let Some(bearer) = bearer {
+        if let Ok((api_token, local_user)) = DbApiToken::validate(&bearer, &context) {
+            if !local_user.person.is_banned() {
+                request.extensions_mut().insert(api_token);
+                request.extensions_mut().insert(local_user);
+            }
         }
     }
     next.run(request).await
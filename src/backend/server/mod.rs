@@ -1,7 +1,7 @@
 use super::{database::IbisContext, utils::error::MyResult};
 use crate::{
     backend::{api::api_routes, federation::routes::federation_routes},
-    common::Auth,
+    common::{Auth, UserAgent},
     frontend::app::{shell, App},
 };
 use activitypub_federation::config::{FederationConfig, FederationMiddleware};
@@ -9,7 +9,7 @@ use assets::file_and_error_handler;
 use axum::{
     body::Body,
     extract::State,
-    http::Request,
+    http::{header::USER_AGENT, Request},
     middleware::from_fn_with_state,
     response::{IntoResponse, Response},
     routing::get,
@@ -27,6 +27,7 @@ use tower_http::{compression::CompressionLayer, cors::CorsLayer};
 use tower_layer::Layer;
 
 mod assets;
+mod feeds;
 mod middleware;
 mod nodeinfo;
 
@@ -50,6 +51,7 @@ pub(super) async fn start_server(
         .nest(FEDERATION_ROUTES_PREFIX, federation_routes())
         .nest("/api/v1", api_routes())
         .nest("", nodeinfo::config())
+        .nest("", feeds::config())
         .layer(FederationMiddleware::new(context))
         .layer(CorsLayer::permissive())
         .layer(CompressionLayer::new())
@@ -69,17 +71,23 @@ pub(super) async fn start_server(
     Ok(())
 }
 
-/// Make auth token available in hydrate mode
+/// Make auth token and user agent available in hydrate mode
 async fn leptos_routes_handler(
     auth: Option<Extension<Auth>>,
     State(leptos_options): State<LeptosOptions>,
     request: Request<Body>,
 ) -> Response {
+    let user_agent = request
+        .headers()
+        .get(USER_AGENT)
+        .and_then(|h| h.to_str().ok())
+        .map(|h| h.to_string());
     let handler = leptos_axum::render_app_async_with_context(
         move || {
             if let Some(auth) = &auth {
                 provide_context(auth.0.clone());
             }
+            provide_context(UserAgent(user_agent.clone()));
         },
         move || shell(leptos_options.clone()),
     );
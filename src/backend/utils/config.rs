@@ -14,6 +14,10 @@ pub struct IbisConfig {
     pub setup: IbisConfigSetup,
     pub federation: IbisConfigFederation,
     pub options: Options,
+    /// Maximum number of write actions (such as edits or comments) per user and minute
+    pub rate_limit: IbisConfigRateLimit,
+    /// OpenID Connect providers which can be used for login in addition to local passwords
+    pub oidc: Vec<IbisConfigOidc>,
 }
 
 impl IbisConfig {
@@ -63,12 +67,46 @@ pub struct IbisConfigFederation {
     #[doku(example = "example.com")]
     pub domain: String,
     /// Comma separated list of instances which are allowed for federation. If set, federation
-    /// with other domains is blocked
+    /// with other domains is blocked. Imported into the database on first startup, afterwards it
+    /// can be changed in the instance settings.
     #[default(None)]
     #[doku(example = "good.com,friends.org")]
     pub allowlist: Option<String>,
-    /// Comma separated list of instances which are blocked for federation
+    /// Comma separated list of instances which are blocked for federation. Imported into the
+    /// database on first startup.
     #[default(None)]
     #[doku(example = "evil.com,bad.org")]
     pub blocklist: Option<String>,
 }
+
+#[derive(Debug, Deserialize, PartialEq, Eq, Clone, Document, SmartDefault)]
+#[serde(default)]
+#[serde(deny_unknown_fields)]
+pub struct IbisConfigRateLimit {
+    #[default(60)]
+    #[doku(example = "60")]
+    pub actions_per_minute: u32,
+    /// Higher limit for bot accounts, which are marked by admins
+    #[default(600)]
+    #[doku(example = "600")]
+    pub bot_actions_per_minute: u32,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Eq, Clone, Document)]
+#[serde(deny_unknown_fields)]
+pub struct IbisConfigOidc {
+    /// Identifier which is used in urls and to link accounts. Must not be changed after users
+    /// have logged in with this provider
+    #[doku(example = "example")]
+    pub name: String,
+    /// Shown on the login button
+    #[doku(example = "Example Corp")]
+    pub display_name: String,
+    /// Provider details are read from `<issuer_url>/.well-known/openid-configuration`
+    #[doku(example = "https://auth.example.com")]
+    pub issuer_url: String,
+    #[doku(example = "ibis")]
+    pub client_id: String,
+    #[doku(example = "my-client-secret")]
+    pub client_secret: String,
+}
@@ -17,8 +17,12 @@ use url::{ParseError, Url};
 
 pub mod config;
 pub mod error;
+pub mod pagination;
+pub mod rate_limit;
 pub(super) mod scheduled_tasks;
+pub(super) mod totp;
 pub(super) mod validate;
+pub mod view_limit;
 
 pub(super) fn generate_activity_id(context: &Data<IbisContext>) -> Result<Url, ParseError> {
     let domain = &context.config.federation.domain;
@@ -58,6 +62,32 @@ pub(super) fn generate_article_version(
     Err(anyhow!("failed to generate article version").into())
 }
 
+/// Substrings of user agents which are used by crawlers, link previews and scripts
+const BOT_USER_AGENTS: [&str; 12] = [
+    "bot",
+    "crawl",
+    "spider",
+    "slurp",
+    "archiver",
+    "facebookexternalhit",
+    "preview",
+    "headless",
+    "curl",
+    "wget",
+    "python",
+    "scrapy",
+];
+
+/// Returns true if the user agent belongs to an automated client. Requests without user agent
+/// are treated as regular readers, as many api clients don't set it.
+pub(super) fn is_bot_user_agent(user_agent: Option<&str>) -> bool {
+    let Some(user_agent) = user_agent else {
+        return false;
+    };
+    let user_agent = user_agent.to_lowercase();
+    BOT_USER_AGENTS.iter().any(|b| user_agent.contains(b))
+}
+
 /// Use a single static keypair during testing which is signficantly faster than
 /// generating dozens of keys from scratch.
 pub fn generate_keypair() -> MyResult<Keypair> {
@@ -0,0 +1,62 @@
+use crate::{
+    backend::utils::error::MyResult,
+    common::pagination::{Paginated, PaginationCursor},
+};
+
+/// Number of items returned per page, if no limit is given
+pub const PAGE_LIMIT_DEFAULT: i64 = 50;
+pub const PAGE_LIMIT_MAX: i64 = 200;
+
+/// Offset and limit for a list query, parsed from the `limit` and `cursor` parameters.
+#[derive(Clone, Copy, Debug)]
+pub struct Pagination {
+    pub offset: i64,
+    pub limit: i64,
+}
+
+impl Pagination {
+    pub fn new(limit: Option<i64>, cursor: Option<&PaginationCursor>) -> MyResult<Self> {
+        let limit = limit.unwrap_or(PAGE_LIMIT_DEFAULT).clamp(1, PAGE_LIMIT_MAX);
+        let offset = match cursor {
+            Some(cursor) => decode_cursor(cursor)?,
+            None => 0,
+        };
+        Ok(Self { offset, limit })
+    }
+
+    /// Database queries read one item more than the limit, to find out if there is a next page.
+    pub fn query_limit(&self) -> i64 {
+        self.limit + 1
+    }
+
+    /// Build the page from items which were read with `offset` and `query_limit()`.
+    pub fn page<T>(&self, mut items: Vec<T>) -> Paginated<T> {
+        let has_next = items.len() as i64 > self.limit;
+        items.truncate(self.limit as usize);
+        Paginated {
+            items,
+            next_cursor: has_next.then(|| encode_cursor(self.offset + self.limit)),
+        }
+    }
+
+    /// Build the page from items which were read without offset, for example when several
+    /// queries with a limit of `offset + query_limit()` are merged.
+    pub fn page_from_all<T>(&self, items: Vec<T>) -> Paginated<T> {
+        let items = items
+            .into_iter()
+            .skip(self.offset as usize)
+            .take(self.query_limit() as usize)
+            .collect();
+        self.page(items)
+    }
+}
+
+/// Cursors currently contain only the offset, but clients must not rely on that.
+pub fn encode_cursor(offset: i64) -> PaginationCursor {
+    PaginationCursor(hex::encode(offset.to_string()))
+}
+
+pub fn decode_cursor(cursor: &PaginationCursor) -> MyResult<i64> {
+    let offset: i64 = String::from_utf8(hex::decode(&cursor.0)?)?.parse()?;
+    Ok(offset.max(0))
+}
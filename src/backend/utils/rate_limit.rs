@@ -0,0 +1,29 @@
+use crate::common::newtypes::PersonId;
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+const WINDOW: Duration = Duration::from_secs(60);
+
+/// Limits the number of write actions per user, using a fixed window of one minute.
+#[derive(Default)]
+pub struct RateLimit {
+    windows: Mutex<HashMap<PersonId, (Instant, u32)>>,
+}
+
+impl RateLimit {
+    /// Returns false if the user has exceeded the limit for the current window.
+    pub fn check(&self, person_id: PersonId, per_minute: u32) -> bool {
+        let Ok(mut windows) = self.windows.lock() else {
+            return true;
+        };
+        let now = Instant::now();
+        // Forget users whose window has expired, so that the map only contains active users
+        windows.retain(|_, (start, _)| now.duration_since(*start) <= WINDOW);
+        let (_, count) = windows.entry(person_id).or_insert((now, 0));
+        *count += 1;
+        *count <= per_minute
+    }
+}
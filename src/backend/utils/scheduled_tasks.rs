@@ -1,4 +1,8 @@
-use crate::backend::{database::DbPool, utils::error::MyResult};
+use crate::{
+    backend::{database::DbPool, utils::error::MyResult},
+    common::instance::InstanceStatsDay,
+};
+use chrono::{Days, Utc};
 use clokwerk::{Scheduler, TimeUnits};
 use diesel::{sql_query, RunQueryDsl};
 use log::{error, info};
@@ -8,8 +12,10 @@ pub fn start(pool: DbPool) {
     let mut scheduler = Scheduler::new();
 
     active_counts(&pool).inspect_err(|e| error!("{e}")).ok();
+    stats_history(&pool).inspect_err(|e| error!("{e}")).ok();
     scheduler.every(1.hour()).run(move || {
         active_counts(&pool).inspect_err(|e| error!("{e}")).ok();
+        stats_history(&pool).inspect_err(|e| error!("{e}")).ok();
     });
 
     let _ = scheduler.watch_thread(Duration::from_secs(60));
@@ -27,3 +33,14 @@ fn active_counts(pool: &DbPool) -> MyResult<()> {
     info!("Done with active user count");
     Ok(())
 }
+
+/// Update daily stats of today, and of yesterday so that it is complete after midnight.
+fn stats_history(pool: &DbPool) -> MyResult<()> {
+    info!("Updating instance stats history");
+    let mut conn = pool.get()?;
+    let today = Utc::now().date_naive();
+    InstanceStatsDay::update(today - Days::new(1), &mut conn)?;
+    InstanceStatsDay::update(today, &mut conn)?;
+    info!("Done with instance stats history");
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use super::error::MyResult;
+use crate::{
+    backend::database::IbisContext,
+    common::user::{DbLocalUser, LocalUserView},
+};
+use anyhow::anyhow;
+use chrono::{TimeDelta, Utc};
+use rand::{distributions::Alphanumeric, thread_rng, Rng};
+use sha2::{Digest, Sha256};
+use totp_rs::{Algorithm, Secret, TOTP};
+
+const RECOVERY_CODE_COUNT: usize = 10;
+/// Tokens of this many time steps before and after the current one are accepted, to allow for
+/// clock drift.
+const TOTP_SKEW_STEPS: i64 = 1;
+/// Number of attempts to enter a token, after which further attempts are rejected until the
+/// window expires. A successful attempt resets the counter.
+const TOTP_MAX_ATTEMPTS: i32 = 5;
+const TOTP_ATTEMPTS_WINDOW: TimeDelta = TimeDelta::minutes(15);
+
+pub(crate) fn generate_totp_secret() -> String {
+    Secret::generate_secret().to_encoded().to_string()
+}
+
+/// Uses the default parameters which are supported by all common authenticator apps. Skew is
+/// handled by [check_totp_token], so that it knows the time step of the token.
+pub(crate) fn build_totp(secret: &str, username: &str, domain: &str) -> MyResult<TOTP> {
+    // issuer must not contain `:`, so strip the port
+    let issuer = domain.split(':').next().unwrap_or(domain).to_string();
+    Ok(TOTP::new(
+        Algorithm::SHA1,
+        6,
+        0,
+        30,
+        Secret::Encoded(secret.to_string()).to_bytes()?,
+        Some(issuer),
+        username.to_string(),
+    )?)
+}
+
+/// Generate new recovery codes, returns the plaintext codes and their hashes for storage.
+pub(crate) fn generate_recovery_codes() -> (Vec<String>, Vec<String>) {
+    let codes: Vec<String> = (0..RECOVERY_CODE_COUNT)
+        .map(|_| {
+            thread_rng()
+                .sample_iter(&Alphanumeric)
+                .take(12)
+                .map(char::from)
+                .collect::<String>()
+                .to_lowercase()
+        })
+        .collect();
+    let hashes = codes.iter().map(|c| hash_recovery_code(c)).collect();
+    (codes, hashes)
+}
+
+fn hash_recovery_code(code: &str) -> String {
+    let mut sha256 = Sha256::new();
+    sha256.update(code.trim().to_lowercase());
+    hex::encode(sha256.finalize())
+}
+
+/// Check the token from the authenticator app. Alternatively a recovery code can be given,
+/// which is invalidated after use. Each token is only accepted once, and the number of attempts
+/// is limited to prevent guessing.
+pub(crate) fn check_totp_token(
+    user: &LocalUserView,
+    token: &str,
+    context: &IbisContext,
+) -> MyResult<()> {
+    let Some(secret) = &user.local_user.totp_secret else {
+        return Err(anyhow!("Two factor authentication is not set up").into());
+    };
+    let id = user.local_user.id;
+    // count the attempt before checking, so that parallel requests can't exceed the limit
+    let attempts = DbLocalUser::add_totp_attempt(id, TOTP_ATTEMPTS_WINDOW, context)?;
+    if attempts > TOTP_MAX_ATTEMPTS {
+        return Err(anyhow!("Too many failed attempts, try again later").into());
+    }
+    let totp = build_totp(
+        secret,
+        &user.person.username,
+        &context.config.federation.domain,
+    )?;
+    let token = token.trim();
+    let step_size = totp.step as i64;
+    let current_step = Utc::now().timestamp() / step_size;
+    let step = (current_step - TOTP_SKEW_STEPS..=current_step + TOTP_SKEW_STEPS)
+        .find(|step| totp.check(token, (step * step_size) as u64));
+    let valid = match step {
+        Some(step) => DbLocalUser::update_totp_last_step(id, step, context)?,
+        None => DbLocalUser::use_recovery_code(id, &hash_recovery_code(token), context)?,
+    };
+    if !valid {
+        return Err(anyhow!("Invalid two factor authentication token").into());
+    }
+    DbLocalUser::reset_totp_attempts(id, context)?;
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use super::error::MyResult;
+use crate::common::article::ARTICLE_LANGUAGES;
 use anyhow::anyhow;
 use regex::Regex;
 use std::sync::LazyLock;
@@ -14,6 +15,21 @@ pub fn validate_article_title(title: &str) -> MyResult<String> {
     Ok(title)
 }
 
+/// Returns `None` if no language is given.
+pub fn validate_article_language(language: Option<String>) -> MyResult<Option<String>> {
+    let Some(language) = language else {
+        return Ok(None);
+    };
+    let language = language.trim();
+    if language.is_empty() {
+        return Ok(None);
+    }
+    if !ARTICLE_LANGUAGES.iter().any(|(code, _)| *code == language) {
+        return Err(anyhow!("Invalid language").into());
+    }
+    Ok(Some(language.to_string()))
+}
+
 pub fn validate_user_name(name: &str) -> MyResult<()> {
     #[allow(clippy::expect_used)]
     static VALID_ACTOR_NAME_REGEX: LazyLock<Regex> =
@@ -0,0 +1,55 @@
+use crate::common::newtypes::{ArticleId, PersonId};
+use chrono::{NaiveDate, Utc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+const ANONYMOUS_WINDOW: Duration = Duration::from_secs(60);
+/// Maximum number of counted views per article and minute from readers who are not logged in
+const ANONYMOUS_VIEWS_PER_MINUTE: u32 = 10;
+
+/// Decides which article views are counted, so that view counts can't be inflated by reloading
+/// the page. Logged in users are counted once per article and day. Anonymous readers can't be
+/// told apart, so only a limited number of their views is counted per article and minute. This is
+/// only kept in memory, no information about readers is stored in the database.
+#[derive(Default)]
+pub struct ViewLimit {
+    state: Mutex<ViewLimitState>,
+}
+
+#[derive(Default)]
+struct ViewLimitState {
+    day: NaiveDate,
+    /// Users whose view of the article was already counted on `day`
+    counted: HashSet<(ArticleId, PersonId)>,
+    anonymous: HashMap<ArticleId, (Instant, u32)>,
+}
+
+impl ViewLimit {
+    /// Returns true if the view should be counted.
+    pub fn check(&self, article_id: ArticleId, person_id: Option<PersonId>) -> bool {
+        let Ok(mut state) = self.state.lock() else {
+            return false;
+        };
+        let today = Utc::now().date_naive();
+        if state.day != today {
+            state.day = today;
+            state.counted.clear();
+        }
+        match person_id {
+            Some(person_id) => state.counted.insert((article_id, person_id)),
+            None => {
+                let now = Instant::now();
+                // Forget articles whose window has expired, so that the map stays small
+                state
+                    .anonymous
+                    .retain(|_, (start, _)| now.duration_since(*start) <= ANONYMOUS_WINDOW);
+                let (_, count) = state.anonymous.entry(article_id).or_insert((now, 0));
+                *count += 1;
+                *count <= ANONYMOUS_VIEWS_PER_MINUTE
+            }
+        }
+    }
+}
@@ -1,10 +1,12 @@
 use super::{
-    comment::DbCommentView,
     instance::DbInstance,
     newtypes::{ArticleId, ConflictId, EditId, InstanceId, PersonId},
+    pagination::{Paginated, PaginationCursor},
     user::DbPerson,
 };
 use chrono::{DateTime, Utc};
+#[cfg(feature = "ssr")]
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use uuid::Uuid;
@@ -12,41 +14,58 @@ use uuid::Uuid;
 use {
     crate::backend::database::schema::{article, edit},
     activitypub_federation::fetch::object_id::ObjectId,
-    diesel::{Identifiable, Queryable, Selectable},
+    diesel::{Identifiable, Queryable, QueryableByName, Selectable},
 };
 
 /// Should be an enum Title/Id but fails due to https://github.com/nox/serde_urlencoded/issues/66
 #[derive(Deserialize, Serialize, Clone, Debug, Default)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
 pub struct GetArticleParams {
     pub title: Option<String>,
     pub domain: Option<String>,
     pub id: Option<ArticleId>,
+    /// Count the request as page view, only set when the article is shown to the reader. Logged
+    /// in users are counted once per article and day, and views without login are limited per
+    /// article and minute.
+    pub record_view: Option<bool>,
 }
 
 #[derive(Deserialize, Serialize, Clone, Default, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
 pub struct ListArticlesParams {
     pub only_local: Option<bool>,
     pub instance_id: Option<InstanceId>,
+    /// Ignore edits by bot accounts when determining recently edited articles
+    pub hide_bots: Option<bool>,
+    /// Order by number of views in the last seven days, and leave out articles without views
+    pub most_viewed: Option<bool>,
+    pub limit: Option<i64>,
+    pub cursor: Option<PaginationCursor>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
-#[cfg_attr(feature = "ssr", derive(Queryable))]
+#[cfg_attr(feature = "ssr", derive(Queryable, JsonSchema))]
 #[cfg_attr(feature = "ssr", diesel(table_name = article, check_for_backend(diesel::pg::Pg)))]
 pub struct DbArticleView {
     pub article: DbArticle,
     pub instance: DbInstance,
-    pub comments: Vec<DbCommentView>,
+    /// Number of comments which are not deleted or removed. Use `/comment/list` to read them.
+    pub comment_count: i64,
     pub latest_version: EditVersion,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
-#[cfg_attr(feature = "ssr", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(
+    feature = "ssr",
+    derive(Queryable, Selectable, Identifiable, JsonSchema)
+)]
 #[cfg_attr(feature = "ssr", diesel(table_name = article, check_for_backend(diesel::pg::Pg), belongs_to(DbInstance, foreign_key = instance_id)))]
 pub struct DbArticle {
     pub id: ArticleId,
     pub title: String,
     pub text: String,
     #[cfg(feature = "ssr")]
+    #[schemars(with = "url::Url")]
     pub ap_id: ObjectId<DbArticle>,
     #[cfg(not(feature = "ssr"))]
     pub ap_id: String,
@@ -55,16 +74,41 @@ pub struct DbArticle {
     pub protected: bool,
     pub approved: bool,
     pub published: DateTime<Utc>,
+    /// ISO 639-1 code, one of [ARTICLE_LANGUAGES]
+    pub language: Option<String>,
 }
 
+/// Languages which can be set for articles. This determines stemming and stop words for search.
+pub const ARTICLE_LANGUAGES: &[(&str, &str)] = &[
+    ("da", "Danish"),
+    ("de", "German"),
+    ("en", "English"),
+    ("es", "Spanish"),
+    ("fi", "Finnish"),
+    ("fr", "French"),
+    ("hu", "Hungarian"),
+    ("it", "Italian"),
+    ("nl", "Dutch"),
+    ("no", "Norwegian"),
+    ("pt", "Portuguese"),
+    ("ro", "Romanian"),
+    ("ru", "Russian"),
+    ("sv", "Swedish"),
+    ("tr", "Turkish"),
+];
+
 #[derive(Deserialize, Serialize, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
 pub struct CreateArticleParams {
     pub title: String,
     pub text: String,
     pub summary: String,
+    /// ISO 639-1 code, one of [ARTICLE_LANGUAGES]
+    pub language: Option<String>,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
 pub struct EditArticleParams {
     /// Id of the article to edit
     pub article_id: ArticleId,
@@ -81,31 +125,133 @@ pub struct EditArticleParams {
 }
 
 #[derive(Deserialize, Serialize, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
 pub struct ProtectArticleParams {
     pub article_id: ArticleId,
     pub protected: bool,
+    /// Shown in the moderation log
+    pub reason: Option<String>,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
 pub struct ForkArticleParams {
     pub article_id: ArticleId,
     pub new_title: String,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
 pub struct ApproveArticleParams {
     pub article_id: ArticleId,
     pub approve: bool,
+    /// Shown in the moderation log
+    pub reason: Option<String>,
 }
 
-#[derive(Deserialize, Serialize, Clone, Debug)]
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
 pub struct SearchArticleParams {
+    /// Supports quoted phrases, `or` and `-` for negation
+    pub query: String,
+    pub limit: Option<i64>,
+    pub cursor: Option<PaginationCursor>,
+    /// Also search on known remote instances for articles which are not fetched yet. They are
+    /// returned separately as `remote`. Only applies to the first page, and not if filtering by
+    /// instance or author.
+    pub federated: Option<bool>,
+    /// Only articles which belong to this instance
+    pub instance_id: Option<InstanceId>,
+    /// Only articles edited by this user, given as `name` for local users or `name@domain`
+    pub author: Option<String>,
+    /// Only articles which were last edited in this time range
+    pub edited_after: Option<DateTime<Utc>>,
+    pub edited_before: Option<DateTime<Utc>>,
+    /// If set, only local (true) or only remote (false) articles
+    pub local: Option<bool>,
+    pub protected: Option<bool>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct SearchSuggestParams {
     pub query: String,
+    pub limit: Option<i64>,
+}
+
+/// Article whose title starts with or is similar to the query.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(QueryableByName, JsonSchema))]
+#[cfg_attr(feature = "ssr", diesel(table_name = article, check_for_backend(diesel::pg::Pg)))]
+pub struct ArticleSuggestion {
+    pub id: ArticleId,
+    pub title: String,
+    #[cfg(feature = "ssr")]
+    #[schemars(with = "url::Url")]
+    pub ap_id: ObjectId<DbArticle>,
+    #[cfg(not(feature = "ssr"))]
+    pub ap_id: String,
+    pub local: bool,
+}
+
+/// Article matching a search query, ordered by relevance. Doesn't include the full text.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(QueryableByName, JsonSchema))]
+#[cfg_attr(feature = "ssr", diesel(table_name = article, check_for_backend(diesel::pg::Pg)))]
+pub struct SearchArticleResult {
+    pub id: ArticleId,
+    pub title: String,
+    #[cfg(feature = "ssr")]
+    #[schemars(with = "url::Url")]
+    pub ap_id: ObjectId<DbArticle>,
+    #[cfg(not(feature = "ssr"))]
+    pub ap_id: String,
+    pub instance_id: InstanceId,
+    pub local: bool,
+    /// Parts of the text which match the query, with matching words wrapped in `<b></b>`. Other
+    /// html in the text is not escaped.
+    #[cfg_attr(feature = "ssr", diesel(sql_type = diesel::sql_types::Text))]
+    pub snippet: String,
+    #[cfg_attr(feature = "ssr", diesel(sql_type = diesel::sql_types::Float4))]
+    pub rank: f32,
+    /// Domain of the instance where the article was created
+    #[cfg_attr(feature = "ssr", diesel(sql_type = diesel::sql_types::Text))]
+    #[serde(default)]
+    pub instance_domain: String,
+}
+
+/// Article found by federated search on another instance, which is not fetched yet. It needs to
+/// be resolved by `ap_id` before opening it.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct RemoteSearchResult {
+    pub title: String,
+    #[cfg(feature = "ssr")]
+    #[schemars(with = "url::Url")]
+    pub ap_id: ObjectId<DbArticle>,
+    #[cfg(not(feature = "ssr"))]
+    pub ap_id: String,
+    /// Domain of the instance where the article was created
+    pub instance_domain: String,
+    /// Same as [SearchArticleResult::snippet]
+    pub snippet: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct SearchArticleResponse {
+    /// Articles in the local database, ordered by relevance
+    #[serde(flatten)]
+    pub articles: Paginated<SearchArticleResult>,
+    /// Results of federated search, at most `limit`. Ordered by the relevance which each instance
+    /// reports, so they are not ranked against local results.
+    #[serde(default)]
+    pub remote: Vec<RemoteSearchResult>,
 }
 
 /// Represents a single change to the article.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
-#[cfg_attr(feature = "ssr", derive(Queryable, Selectable))]
+#[cfg_attr(feature = "ssr", derive(Queryable, Selectable, JsonSchema))]
 #[cfg_attr(feature = "ssr", diesel(table_name = edit, check_for_backend(diesel::pg::Pg)))]
 pub struct DbEdit {
     // TODO: we could use hash as primary key, but that gives errors on forking because
@@ -116,6 +262,7 @@ pub struct DbEdit {
     /// UUID built from sha224 hash of diff
     pub hash: EditVersion,
     #[cfg(feature = "ssr")]
+    #[schemars(with = "url::Url")]
     pub ap_id: ObjectId<DbEdit>,
     #[cfg(not(feature = "ssr"))]
     pub ap_id: String,
@@ -129,13 +276,17 @@ pub struct DbEdit {
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug, Default)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
 pub struct GetEditList {
     pub article_id: Option<ArticleId>,
     pub person_id: Option<PersonId>,
+    pub hide_bots: Option<bool>,
+    pub limit: Option<i64>,
+    pub cursor: Option<PaginationCursor>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
-#[cfg_attr(feature = "ssr", derive(Queryable))]
+#[cfg_attr(feature = "ssr", derive(Queryable, JsonSchema))]
 #[cfg_attr(feature = "ssr", diesel(check_for_backend(diesel::pg::Pg)))]
 pub struct EditView {
     pub edit: DbEdit,
@@ -143,10 +294,61 @@ pub struct EditView {
     pub creator: DbPerson,
 }
 
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct RecentChangesParams {
+    /// If set, only edits of local (true) or remote (false) articles
+    pub local: Option<bool>,
+    pub hide_bots: Option<bool>,
+    /// If set, only pending (true) or only accepted (false) edits. Pending edits are only
+    /// visible to their creator and to users who can approve articles.
+    pub pending: Option<bool>,
+    /// Only the first edit of each article
+    pub new_articles: Option<bool>,
+    pub limit: Option<i64>,
+    pub cursor: Option<PaginationCursor>,
+}
+
+/// Edit from the list of recent changes on all articles.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct RecentChangeView {
+    pub edit: DbEdit,
+    pub article: DbArticle,
+    pub creator: DbPerson,
+    /// Change of the article text size in bytes
+    pub byte_delta: i64,
+}
+
+impl DbEdit {
+    /// Number of bytes added by this edit minus the number of bytes removed.
+    pub fn byte_delta(&self) -> i64 {
+        let (added, removed) = diff_byte_counts(&self.diff);
+        added - removed
+    }
+}
+
+/// Number of bytes in lines which are added and removed by a diff.
+pub fn diff_byte_counts(diff: &str) -> (i64, i64) {
+    diff.lines()
+        .skip_while(|line| !line.starts_with("@@"))
+        // each changed line has one char prefix instead of the line break
+        .fold((0, 0), |(added, removed), line| {
+            match line.as_bytes().first() {
+                Some(b'+') => (added + line.len() as i64, removed),
+                Some(b'-') => (added, removed + line.len() as i64),
+                _ => (added, removed),
+            }
+        })
+}
+
 /// The version hash of a specific edit. Generated by taking an SHA256 hash of the diff
 /// and using the first 16 bytes so that it fits into UUID.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
-#[cfg_attr(feature = "ssr", derive(diesel_derive_newtype::DieselNewType))]
+#[cfg_attr(
+    feature = "ssr",
+    derive(diesel_derive_newtype::DieselNewType, JsonSchema)
+)]
 pub struct EditVersion(pub(crate) Uuid);
 
 impl EditVersion {
@@ -171,11 +373,13 @@ impl Default for EditVersion {
 }
 
 #[derive(Deserialize, Serialize, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
 pub struct DeleteConflictParams {
     pub conflict_id: ConflictId,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
 pub struct ApiConflict {
     pub id: ConflictId,
     pub hash: EditVersion,
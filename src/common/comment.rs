@@ -1,18 +1,24 @@
 use super::{
     newtypes::{ArticleId, CommentId, PersonId},
+    pagination::PaginationCursor,
     user::DbPerson,
 };
 use chrono::{DateTime, Utc};
+#[cfg(feature = "ssr")]
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "ssr")]
 use {
-    crate::backend::database::schema::comment,
+    crate::backend::database::schema::{comment, comment_vote},
     activitypub_federation::fetch::object_id::ObjectId,
     diesel::{Identifiable, Queryable, Selectable},
 };
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
-#[cfg_attr(feature = "ssr", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(
+    feature = "ssr",
+    derive(Queryable, Selectable, Identifiable, JsonSchema)
+)]
 #[cfg_attr(feature = "ssr", diesel(table_name = comment, check_for_backend(diesel::pg::Pg), belongs_to(DbArticle, foreign_key = instance_id)))]
 pub struct DbComment {
     pub id: CommentId,
@@ -22,6 +28,7 @@ pub struct DbComment {
     pub content: String,
     pub depth: i32,
     #[cfg(feature = "ssr")]
+    #[schemars(with = "url::Url")]
     pub ap_id: ObjectId<DbComment>,
     #[cfg(not(feature = "ssr"))]
     pub ap_id: String,
@@ -29,15 +36,48 @@ pub struct DbComment {
     pub deleted: bool,
     pub published: DateTime<Utc>,
     pub updated: Option<DateTime<Utc>>,
+    /// Removed by an admin or moderator. Unlike `deleted` this cannot be reverted by the author.
+    pub removed: bool,
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
 pub struct DbCommentView {
     pub comment: DbComment,
     pub creator: DbPerson,
+    pub upvotes: i64,
+    pub downvotes: i64,
+    /// Vote of the logged in user, either 1 or -1
+    pub my_vote: Option<i16>,
+}
+
+impl DbCommentView {
+    pub fn score(&self) -> i64 {
+        self.upvotes - self.downvotes
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(Queryable, Selectable, JsonSchema))]
+#[cfg_attr(feature = "ssr", diesel(table_name = comment_vote, check_for_backend(diesel::pg::Pg)))]
+pub struct DbCommentVote {
+    pub person_id: PersonId,
+    pub comment_id: CommentId,
+    pub score: i16,
+    pub published: DateTime<Utc>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub enum CommentSort {
+    #[default]
+    New,
+    /// Highest score (upvotes minus downvotes) first
+    Top,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
 pub struct CreateCommentParams {
     pub content: String,
     pub article_id: ArticleId,
@@ -45,6 +85,7 @@ pub struct CreateCommentParams {
 }
 
 #[derive(Deserialize, Serialize, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
 pub struct EditCommentParams {
     pub id: CommentId,
     pub content: Option<String>,
@@ -52,6 +93,60 @@ pub struct EditCommentParams {
 }
 
 #[derive(Deserialize, Serialize, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
 pub struct DeleteCommentParams {
     pub id: CommentId,
 }
+
+#[derive(Deserialize, Serialize, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct RemoveCommentParams {
+    pub id: CommentId,
+    /// Set to false to restore a removed comment
+    pub removed: bool,
+    pub reason: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct VoteCommentParams {
+    pub comment_id: CommentId,
+    /// 1 for upvote, -1 for downvote, 0 to remove the vote
+    pub score: i16,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct ListCommentsParams {
+    pub article_id: ArticleId,
+    /// Only list replies to this comment
+    pub parent_id: Option<CommentId>,
+    pub sort: Option<CommentSort>,
+    /// Maximum number of comments returned on each level of the tree
+    pub limit: Option<i64>,
+    /// Number of reply levels to include below the listed comments
+    pub max_depth: Option<i32>,
+    /// Continue listing after a previous page, taken from [CommentTree::next_cursor] or
+    /// [CommentTreeNode::more_replies]. Must be used with the same `parent_id` and `sort`.
+    pub cursor: Option<PaginationCursor>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct CommentTree {
+    pub comments: Vec<CommentTreeNode>,
+    /// Set if there are more comments on the top level
+    pub next_cursor: Option<PaginationCursor>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct CommentTreeNode {
+    pub comment: DbCommentView,
+    pub children: Vec<CommentTreeNode>,
+    /// Number of direct replies, including those which are not in `children`
+    pub reply_count: i64,
+    /// Set if not all replies are in `children`. Load them by passing this as cursor, with this
+    /// comment as `parent_id`.
+    pub more_replies: Option<PaginationCursor>,
+}
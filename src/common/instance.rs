@@ -1,15 +1,18 @@
 use super::{
     newtypes::InstanceId,
-    user::{DbPerson, LocalUserView},
+    pagination::PaginationCursor,
+    user::{DbPerson, LocalUserView, OidcProvider},
 };
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
+#[cfg(feature = "ssr")]
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use smart_default::SmartDefault;
 use url::Url;
 #[cfg(feature = "ssr")]
 use {
     crate::backend::{
-        database::schema::instance,
+        database::schema::{federation_list, instance, instance_stats_history},
         federation::objects::articles_collection::DbArticleCollection,
         federation::objects::instance_collection::DbInstanceCollection,
     },
@@ -19,17 +22,22 @@ use {
 };
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
-#[cfg_attr(feature = "ssr", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(
+    feature = "ssr",
+    derive(Queryable, Selectable, Identifiable, JsonSchema)
+)]
 #[cfg_attr(feature = "ssr", diesel(table_name = instance, check_for_backend(diesel::pg::Pg)))]
 pub struct DbInstance {
     pub id: InstanceId,
     pub domain: String,
     #[cfg(feature = "ssr")]
+    #[schemars(with = "Url")]
     pub ap_id: ObjectId<DbInstance>,
     #[cfg(not(feature = "ssr"))]
     pub ap_id: String,
     pub topic: Option<String>,
     #[cfg(feature = "ssr")]
+    #[schemars(with = "Option<Url>")]
     pub articles_url: Option<CollectionId<DbArticleCollection>>,
     #[cfg(not(feature = "ssr"))]
     pub articles_url: String,
@@ -41,6 +49,7 @@ pub struct DbInstance {
     pub last_refreshed_at: DateTime<Utc>,
     pub local: bool,
     #[cfg(feature = "ssr")]
+    #[schemars(with = "Option<Url>")]
     pub instances_url: Option<CollectionId<DbInstanceCollection>>,
     pub name: Option<String>,
 }
@@ -52,7 +61,7 @@ impl DbInstance {
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
-#[cfg_attr(feature = "ssr", derive(Queryable))]
+#[cfg_attr(feature = "ssr", derive(Queryable, JsonSchema))]
 #[cfg_attr(feature = "ssr", diesel(table_name = article, check_for_backend(diesel::pg::Pg)))]
 pub struct InstanceView {
     pub instance: DbInstance,
@@ -62,7 +71,7 @@ pub struct InstanceView {
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, SmartDefault)]
 #[serde(default)]
 #[serde(deny_unknown_fields)]
-#[cfg_attr(feature = "ssr", derive(Queryable, Document))]
+#[cfg_attr(feature = "ssr", derive(Queryable, Document, JsonSchema))]
 #[cfg_attr(feature = "ssr", diesel(check_for_backend(diesel::pg::Pg)))]
 pub struct Options {
     /// Whether users can create new accounts
@@ -73,28 +82,127 @@ pub struct Options {
     #[default = false]
     #[cfg_attr(feature = "ssr", doku(example = "false"))]
     pub article_approval: bool,
+    /// Whether admins and moderators need to enable two-factor authentication before they can
+    /// perform privileged actions
+    #[default = false]
+    #[cfg_attr(feature = "ssr", doku(example = "false"))]
+    pub admin_totp_required: bool,
+    /// Whether new accounts can be created with username and password. If disabled, new users
+    /// can only sign up via OpenID Connect
+    #[default = true]
+    #[cfg_attr(feature = "ssr", doku(example = "true"))]
+    pub password_registration: bool,
+    /// Whether names of admins and moderators are hidden in the public moderation log. They
+    /// remain visible for other admins and moderators
+    #[default = false]
+    #[cfg_attr(feature = "ssr", doku(example = "false"))]
+    pub modlog_hide_moderators: bool,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Default)]
-#[cfg_attr(feature = "ssr", derive(Queryable))]
+#[cfg_attr(feature = "ssr", derive(Queryable, JsonSchema))]
 #[cfg_attr(feature = "ssr", diesel(check_for_backend(diesel::pg::Pg)))]
 pub struct SiteView {
     pub my_profile: Option<LocalUserView>,
     pub config: Options,
+    pub oidc_providers: Vec<OidcProvider>,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
 pub struct GetInstanceParams {
     pub id: Option<InstanceId>,
 }
 
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct ListInstancesParams {
+    pub limit: Option<i64>,
+    pub cursor: Option<PaginationCursor>,
+}
+
 #[derive(Deserialize, Serialize, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
 pub struct FollowInstanceParams {
     pub id: InstanceId,
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
 pub struct UpdateInstanceParams {
     pub name: Option<String>,
     pub topic: Option<String>,
 }
+
+/// Domain which is explicitly allowed or blocked for federation. If any domain is allowed,
+/// federation with all other domains is blocked.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(
+    feature = "ssr",
+    derive(Queryable, Selectable, Identifiable, JsonSchema)
+)]
+#[cfg_attr(feature = "ssr", diesel(table_name = federation_list, primary_key(domain), check_for_backend(diesel::pg::Pg)))]
+pub struct DbFederationListEntry {
+    pub domain: String,
+    pub blocked: bool,
+    pub reason: Option<String>,
+    pub published: DateTime<Utc>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct UpdateFederationListParams {
+    pub domain: String,
+    pub blocked: bool,
+    pub reason: Option<String>,
+    /// Delete articles of a blocked instance, and comments written by its users
+    pub purge: bool,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct RemoveFederationListParams {
+    pub domain: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct GetInstanceStatsParams {
+    /// Number of days to return, including today. Defaults to 30, at most 365.
+    pub days: Option<i64>,
+}
+
+/// Statistics of the local instance for a single day, in UTC.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(Queryable, Selectable, JsonSchema))]
+#[cfg_attr(feature = "ssr", diesel(table_name = instance_stats_history, check_for_backend(diesel::pg::Pg)))]
+pub struct InstanceStatsDay {
+    pub day: NaiveDate,
+    /// Accepted edits by local users
+    pub edits: i32,
+    /// New local articles
+    pub articles: i32,
+    pub new_users: i32,
+    /// Local comments which are not deleted or removed
+    pub comments: i32,
+    pub activities_received: i32,
+    /// Counts each recipient inbox separately
+    pub activities_sent: i32,
+    /// Local users with at least one accepted edit
+    pub active_editors: i32,
+}
+
+impl InstanceStatsDay {
+    pub fn empty(day: NaiveDate) -> Self {
+        InstanceStatsDay {
+            day,
+            edits: 0,
+            articles: 0,
+            new_users: 0,
+            comments: 0,
+            activities_received: 0,
+            activities_sent: 0,
+            active_editors: 0,
+        }
+    }
+}
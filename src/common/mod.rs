@@ -1,13 +1,20 @@
 pub mod article;
 pub mod comment;
 pub mod instance;
+pub mod modlog;
 pub mod newtypes;
+pub mod pagination;
+pub mod report;
 pub mod user;
 pub mod utils;
 pub mod validation;
 
 use article::{ApiConflict, DbArticle};
 use chrono::{DateTime, Utc};
+use pagination::PaginationCursor;
+use report::ReportView;
+#[cfg(feature = "ssr")]
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
@@ -18,7 +25,13 @@ pub static AUTH_COOKIE: &str = "auth";
 #[derive(Clone, Debug)]
 pub struct Auth(pub Option<String>);
 
+/// User agent of the browser during server side rendering, so that it can be forwarded to the
+/// api.
+#[derive(Clone, Debug)]
+pub struct UserAgent(pub Option<String>);
+
 #[derive(Deserialize, Serialize, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
 pub struct SuccessResponse {
     success: bool,
 }
@@ -30,14 +43,24 @@ impl Default for SuccessResponse {
 }
 
 #[derive(Deserialize, Serialize, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
 pub struct ResolveObjectParams {
     pub id: Url,
 }
 
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct ListNotificationsParams {
+    pub limit: Option<i64>,
+    pub cursor: Option<PaginationCursor>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
 pub enum Notification {
     EditConflict(ApiConflict),
     ArticleApprovalRequired(DbArticle),
+    Report(Box<ReportView>),
 }
 
 impl Notification {
@@ -45,6 +68,7 @@ impl Notification {
         match self {
             Notification::EditConflict(api_conflict) => &api_conflict.published,
             Notification::ArticleApprovalRequired(db_article) => &db_article.published,
+            Notification::Report(report) => &report.report.published,
         }
     }
 }
@@ -0,0 +1,80 @@
+use super::{
+    newtypes::{ArticleId, InstanceId, PersonId},
+    pagination::PaginationCursor,
+    user::DbPerson,
+};
+use chrono::{DateTime, Utc};
+#[cfg(feature = "ssr")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ssr")]
+use {
+    crate::backend::database::schema::modlog,
+    diesel::{
+        deserialize::FromSqlRow,
+        expression::AsExpression,
+        Identifiable,
+        Queryable,
+        Selectable,
+    },
+};
+
+/// Privileged action performed by an admin or moderator.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "ssr", derive(AsExpression, FromSqlRow, JsonSchema))]
+#[cfg_attr(feature = "ssr", diesel(sql_type = diesel::sql_types::Text))]
+pub enum ModlogAction {
+    ProtectArticle,
+    UnprotectArticle,
+    ApproveArticle,
+    RejectArticle,
+    UpdateInstance,
+    BanUser,
+    UnbanUser,
+    DeleteUser,
+    ResolveReport,
+    DismissReport,
+    RemoveComment,
+    RestoreComment,
+    UpdateUserRole,
+    BlockInstance,
+    AllowInstance,
+    RemoveFederationListEntry,
+}
+
+/// Entry in the moderation log. The target name is stored separately so that entries stay
+/// readable after the target is deleted.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(
+    feature = "ssr",
+    derive(Queryable, Selectable, Identifiable, JsonSchema)
+)]
+#[cfg_attr(feature = "ssr", diesel(table_name = modlog, check_for_backend(diesel::pg::Pg)))]
+pub struct DbModlog {
+    pub id: i32,
+    /// Empty if moderator names are hidden
+    #[cfg_attr(feature = "ssr", diesel(deserialize_as = PersonId))]
+    pub actor_id: Option<PersonId>,
+    pub action: ModlogAction,
+    pub target_article_id: Option<ArticleId>,
+    pub target_person_id: Option<PersonId>,
+    pub target_instance_id: Option<InstanceId>,
+    pub target_name: String,
+    pub reason: Option<String>,
+    pub published: DateTime<Utc>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct ModlogView {
+    pub modlog: DbModlog,
+    /// Empty if moderator names are hidden
+    pub actor: Option<DbPerson>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct GetModlogParams {
+    pub limit: Option<i64>,
+    pub cursor: Option<PaginationCursor>,
+}
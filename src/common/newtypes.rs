@@ -1,27 +1,41 @@
 #[cfg(feature = "ssr")]
 use diesel_derive_newtype::DieselNewType;
+#[cfg(feature = "ssr")]
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Default, Serialize, Deserialize)]
-#[cfg_attr(feature = "ssr", derive(DieselNewType))]
+#[cfg_attr(feature = "ssr", derive(DieselNewType, JsonSchema))]
 pub struct PersonId(pub i32);
 
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Default, Serialize, Deserialize)]
-#[cfg_attr(feature = "ssr", derive(DieselNewType))]
+#[cfg_attr(feature = "ssr", derive(DieselNewType, JsonSchema))]
 pub struct ArticleId(pub i32);
 
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Default, Serialize, Deserialize)]
-#[cfg_attr(feature = "ssr", derive(DieselNewType))]
+#[cfg_attr(feature = "ssr", derive(DieselNewType, JsonSchema))]
 pub struct EditId(pub i32);
 
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Default, Serialize, Deserialize)]
-#[cfg_attr(feature = "ssr", derive(DieselNewType))]
+#[cfg_attr(feature = "ssr", derive(DieselNewType, JsonSchema))]
 pub struct InstanceId(pub i32);
 
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Default, Serialize, Deserialize)]
-#[cfg_attr(feature = "ssr", derive(DieselNewType))]
+#[cfg_attr(feature = "ssr", derive(DieselNewType, JsonSchema))]
 pub struct ConflictId(pub i32);
 
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Default, Serialize, Deserialize)]
-#[cfg_attr(feature = "ssr", derive(DieselNewType))]
+#[cfg_attr(feature = "ssr", derive(DieselNewType, JsonSchema))]
 pub struct CommentId(pub i32);
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(DieselNewType, JsonSchema))]
+pub struct LocalUserId(pub i32);
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(DieselNewType, JsonSchema))]
+pub struct ApiTokenId(pub i32);
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssr", derive(DieselNewType, JsonSchema))]
+pub struct ReportId(pub i32);
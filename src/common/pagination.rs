@@ -0,0 +1,28 @@
+#[cfg(feature = "ssr")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Opaque position in a paginated list. Only pass on values which were returned as
+/// `next_cursor`, and use them together with the same filter parameters.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+#[serde(transparent)]
+pub struct PaginationCursor(pub String);
+
+/// Single page of a list endpoint.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct Paginated<T> {
+    pub items: Vec<T>,
+    /// Pass this as `cursor` to get the next page. Empty if this is the last page.
+    pub next_cursor: Option<PaginationCursor>,
+}
+
+impl<T> Default for Paginated<T> {
+    fn default() -> Self {
+        Self {
+            items: vec![],
+            next_cursor: None,
+        }
+    }
+}
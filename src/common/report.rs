@@ -0,0 +1,95 @@
+use super::{
+    article::{DbArticle, DbEdit},
+    comment::DbComment,
+    newtypes::{ArticleId, CommentId, EditId, PersonId, ReportId},
+    pagination::PaginationCursor,
+    user::DbPerson,
+};
+use chrono::{DateTime, Utc};
+#[cfg(feature = "ssr")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "ssr")]
+use {
+    crate::backend::database::schema::report,
+    diesel::{
+        deserialize::FromSqlRow,
+        expression::AsExpression,
+        Identifiable,
+        Queryable,
+        Selectable,
+    },
+};
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "ssr", derive(AsExpression, FromSqlRow, JsonSchema))]
+#[cfg_attr(feature = "ssr", diesel(sql_type = diesel::sql_types::Text))]
+pub enum ReportStatus {
+    #[default]
+    Open,
+    /// The reported content was handled, eg by reverting an edit or removing a comment
+    Resolved,
+    /// The report was invalid and no action was taken
+    Dismissed,
+}
+
+/// Report about an article, a single edit or a comment. Reports about remote content are also
+/// sent to the instance which hosts the article.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(
+    feature = "ssr",
+    derive(Queryable, Selectable, Identifiable, JsonSchema)
+)]
+#[cfg_attr(feature = "ssr", diesel(table_name = report, check_for_backend(diesel::pg::Pg)))]
+pub struct DbReport {
+    pub id: ReportId,
+    pub creator_id: PersonId,
+    pub article_id: ArticleId,
+    pub edit_id: Option<EditId>,
+    pub comment_id: Option<CommentId>,
+    pub reason: String,
+    pub status: ReportStatus,
+    pub resolver_id: Option<PersonId>,
+    pub ap_id: String,
+    pub local: bool,
+    pub published: DateTime<Utc>,
+    pub updated: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct ReportView {
+    pub report: DbReport,
+    pub creator: DbPerson,
+    pub article: DbArticle,
+    pub edit: Option<DbEdit>,
+    pub comment: Option<DbComment>,
+}
+
+/// Report an article. If `edit_id` or `comment_id` is set, the report is about that edit or
+/// comment of the article instead.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct CreateReportParams {
+    pub article_id: ArticleId,
+    pub edit_id: Option<EditId>,
+    pub comment_id: Option<CommentId>,
+    pub reason: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct ListReportsParams {
+    /// Also return reports which were already resolved or dismissed
+    pub include_closed: Option<bool>,
+    pub limit: Option<i64>,
+    pub cursor: Option<PaginationCursor>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct ResolveReportParams {
+    pub report_id: ReportId,
+    /// Dismiss the report instead of marking it as resolved
+    pub dismiss: bool,
+}
@@ -1,31 +1,43 @@
 use super::{
+    article::DbArticle,
+    comment::DbComment,
     instance::DbInstance,
-    newtypes::{InstanceId, PersonId},
+    newtypes::{ApiTokenId, LocalUserId, PersonId},
+    pagination::PaginationCursor,
 };
 use chrono::{DateTime, Utc};
+#[cfg(feature = "ssr")]
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use url::Url;
 #[cfg(feature = "ssr")]
 use {
-    crate::backend::database::schema::{local_user, person},
+    crate::backend::database::schema::{api_token, local_user, person},
     activitypub_federation::fetch::object_id::ObjectId,
     diesel::{Identifiable, Queryable, Selectable},
 };
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
 pub struct RegisterUserParams {
     pub username: String,
     pub password: String,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
 pub struct LoginUserParams {
     pub username: String,
     pub password: String,
+    /// Token from authenticator app or recovery code, only required if 2FA is enabled
+    pub totp_token: Option<String>,
 }
 
+/// Returned as login error if the account has 2FA enabled but no token was provided
+pub const TOTP_REQUIRED_ERROR: &str = "totp_token_required";
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
-#[cfg_attr(feature = "ssr", derive(Queryable))]
+#[cfg_attr(feature = "ssr", derive(Queryable, JsonSchema))]
 #[cfg_attr(feature = "ssr", diesel(check_for_backend(diesel::pg::Pg)))]
 pub struct LocalUserView {
     pub person: DbPerson,
@@ -35,24 +47,113 @@ pub struct LocalUserView {
 
 /// A user with account registered on local instance.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
-#[cfg_attr(feature = "ssr", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(
+    feature = "ssr",
+    derive(Queryable, Selectable, Identifiable, JsonSchema)
+)]
 #[cfg_attr(feature = "ssr", diesel(table_name = local_user, check_for_backend(diesel::pg::Pg)))]
 pub struct DbLocalUser {
-    pub id: InstanceId,
+    pub id: LocalUserId,
     #[serde(skip)]
     pub password_encrypted: String,
     pub person_id: PersonId,
     pub admin: bool,
+    #[serde(skip)]
+    pub totp_secret: Option<String>,
+    pub totp_enabled: bool,
+    pub moderator: bool,
+    /// Permissions which were granted individually, in addition to those of the role
+    pub perm_protect_article: bool,
+    pub perm_approve_article: bool,
+    pub perm_remove_comment: bool,
+    pub perm_ban: bool,
+    pub perm_manage_federation: bool,
+    pub perm_manage_users: bool,
+    pub perm_handle_reports: bool,
+}
+
+impl DbLocalUser {
+    pub fn role(&self) -> UserRole {
+        if self.admin {
+            UserRole::Admin
+        } else if self.moderator {
+            UserRole::Moderator
+        } else {
+            UserRole::User
+        }
+    }
+
+    pub fn has_permission(&self, permission: Permission) -> bool {
+        match self.role() {
+            UserRole::Admin => return true,
+            UserRole::Moderator if MODERATOR_PERMISSIONS.contains(&permission) => return true,
+            _ => {}
+        }
+        match permission {
+            Permission::ProtectArticle => self.perm_protect_article,
+            Permission::ApproveArticle => self.perm_approve_article,
+            Permission::RemoveComment => self.perm_remove_comment,
+            Permission::Ban => self.perm_ban,
+            Permission::ManageFederation => self.perm_manage_federation,
+            Permission::ManageUsers => self.perm_manage_users,
+            Permission::HandleReports => self.perm_handle_reports,
+        }
+    }
+}
+
+/// Roles are ordered by rank, so that users can only moderate users with a lower role.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub enum UserRole {
+    #[default]
+    User,
+    /// Has all permissions in [MODERATOR_PERMISSIONS]
+    Moderator,
+    /// Has all permissions, and can change the role of other users
+    Admin,
 }
 
+/// Actions which are not available to regular users. Each of them can also be granted
+/// individually, without changing the role of the user.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub enum Permission {
+    /// Protect articles, and edit protected articles
+    ProtectArticle,
+    /// Approve or reject new articles, if article approval is enabled
+    ApproveArticle,
+    /// Remove comments written by other users
+    RemoveComment,
+    /// Ban users from the local instance
+    Ban,
+    /// Block and unblock remote instances
+    ManageFederation,
+    /// Delete accounts of other users
+    ManageUsers,
+    /// Receive reports about articles, edits and comments, and resolve them
+    HandleReports,
+}
+
+pub const MODERATOR_PERMISSIONS: [Permission; 5] = [
+    Permission::ProtectArticle,
+    Permission::ApproveArticle,
+    Permission::RemoveComment,
+    Permission::Ban,
+    Permission::HandleReports,
+];
+
 /// Federation related data from a local or remote user.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
-#[cfg_attr(feature = "ssr", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(
+    feature = "ssr",
+    derive(Queryable, Selectable, Identifiable, JsonSchema)
+)]
 #[cfg_attr(feature = "ssr", diesel(table_name = person, check_for_backend(diesel::pg::Pg)))]
 pub struct DbPerson {
     pub id: PersonId,
     pub username: String,
     #[cfg(feature = "ssr")]
+    #[schemars(with = "Url")]
     pub ap_id: ObjectId<DbPerson>,
     #[cfg(not(feature = "ssr"))]
     pub ap_id: String,
@@ -66,23 +167,256 @@ pub struct DbPerson {
     pub local: bool,
     pub display_name: Option<String>,
     pub bio: Option<String>,
+    /// Automated account, its edits can be hidden from edit lists
+    pub bot: bool,
+    /// Banned from the local instance, or from its home instance if it is a remote user
+    pub banned: bool,
+    pub ban_reason: Option<String>,
+    /// Ban is permanent if this is empty
+    pub ban_expires: Option<DateTime<Utc>>,
 }
 
 impl DbPerson {
+    pub fn is_banned(&self) -> bool {
+        self.banned && self.ban_expires.is_none_or(|e| e > Utc::now())
+    }
+
     pub fn inbox_url(&self) -> Url {
         Url::parse(&self.inbox_url).expect("can parse inbox url")
     }
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
 pub struct GetUserParams {
     pub name: String,
     pub domain: Option<String>,
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct GetUserStatsParams {
+    pub person_id: PersonId,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct UserContributionsParams {
+    pub person_id: PersonId,
+    pub limit: Option<i64>,
+    pub cursor: Option<PaginationCursor>,
+}
+
+/// Totals over all edits and comments of a user. Pending edits and deleted or removed comments
+/// are not counted.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct UserStats {
+    pub edit_count: i64,
+    /// Sum of bytes in lines added by the edits
+    pub bytes_added: i64,
+    /// Sum of bytes in lines removed by the edits
+    pub bytes_removed: i64,
+    pub first_edit: Option<DateTime<Utc>>,
+    pub last_edit: Option<DateTime<Utc>>,
+    pub comment_count: i64,
+    /// Number of articles where the first edit is by this user
+    pub article_count: i64,
+    /// Articles with the most edits by this user
+    pub top_articles: Vec<UserTopArticle>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct UserTopArticle {
+    pub article: DbArticle,
+    pub edit_count: i64,
+}
+
+/// Comment written by a user, together with the article where it was posted.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct UserCommentView {
+    pub comment: DbComment,
+    pub article: DbArticle,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
 pub struct UpdateUserParams {
     pub person_id: PersonId,
     pub display_name: Option<String>,
     pub bio: Option<String>,
 }
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct BanUserParams {
+    pub person_id: PersonId,
+    /// Set to false to unban the user
+    pub ban: bool,
+    pub reason: Option<String>,
+    /// Ban is permanent if this is empty
+    pub expires: Option<DateTime<Utc>>,
+    /// Remove comments of the user, and revert the latest edits of the user on local articles
+    pub remove_content: bool,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct GetUserRoleParams {
+    pub person_id: PersonId,
+}
+
+/// Change the role and individual permissions of a local user, only for admins.
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct UpdateUserRoleParams {
+    pub person_id: PersonId,
+    pub role: UserRole,
+    pub perm_protect_article: bool,
+    pub perm_approve_article: bool,
+    pub perm_remove_comment: bool,
+    pub perm_ban: bool,
+    pub perm_manage_federation: bool,
+    pub perm_manage_users: bool,
+    pub perm_handle_reports: bool,
+    /// Mark the account as bot. Bot edits can be hidden, and bots have a higher rate limit.
+    pub bot: bool,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct DeleteAccountParams {
+    /// Current password of the account, to confirm the deletion. Can be empty after logging in
+    /// again with a linked OpenID Connect provider.
+    pub password: String,
+    /// Also remove the content of all comments written by the user
+    pub delete_comments: bool,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct DeleteUserParams {
+    pub person_id: PersonId,
+    /// Also remove the content of all comments written by the user
+    pub delete_comments: bool,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct TotpSetupResponse {
+    /// Base32 encoded secret, for manual entry in authenticator app
+    pub secret: String,
+    /// otpauth:// url which can be opened directly or shown as QR code
+    pub otpauth_url: String,
+}
+
+/// Second step of an OpenID Connect login, for accounts with two factor authentication
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct OidcTotpParams {
+    /// Token from authenticator app or recovery code
+    pub totp_token: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct EnableTotpParams {
+    /// Current token from authenticator app, to confirm that setup was successful
+    pub totp_token: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct DisableTotpParams {
+    /// Current password of the account, can be empty after logging in again with a linked
+    /// OpenID Connect provider
+    pub password: String,
+    /// Current token from authenticator app or recovery code
+    pub totp_token: String,
+}
+
+/// Single-use codes to login if the authenticator app is lost. These are only shown once.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct TotpRecoveryCodes {
+    pub codes: Vec<String>,
+}
+
+/// OpenID Connect provider which can be used for login
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct OidcProvider {
+    pub name: String,
+    pub display_name: String,
+}
+
+/// Permissions which can be granted to an api token
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub enum ApiTokenScope {
+    /// Read data, including private data such as notifications
+    Read,
+    /// Create and edit articles, follow instances
+    Edit,
+    Comment,
+    /// Moderation and admin actions, only has an effect if the user has the permission
+    Admin,
+}
+
+/// Personal token to access the api without login, sent as `Authorization: Bearer` header.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(
+    feature = "ssr",
+    derive(Queryable, Selectable, Identifiable, JsonSchema)
+)]
+#[cfg_attr(feature = "ssr", diesel(table_name = api_token, check_for_backend(diesel::pg::Pg)))]
+pub struct DbApiToken {
+    pub id: ApiTokenId,
+    pub local_user_id: LocalUserId,
+    pub name: String,
+    #[serde(skip)]
+    pub token_hash: String,
+    pub scope_read: bool,
+    pub scope_edit: bool,
+    pub scope_comment: bool,
+    pub scope_admin: bool,
+    pub published: DateTime<Utc>,
+    pub last_used: Option<DateTime<Utc>>,
+}
+
+impl DbApiToken {
+    pub fn has_scope(&self, scope: ApiTokenScope) -> bool {
+        match scope {
+            ApiTokenScope::Read => self.scope_read,
+            ApiTokenScope::Edit => self.scope_edit,
+            ApiTokenScope::Comment => self.scope_comment,
+            ApiTokenScope::Admin => self.scope_admin,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct CreateApiTokenParams {
+    pub name: String,
+    pub scope_read: bool,
+    pub scope_edit: bool,
+    pub scope_comment: bool,
+    pub scope_admin: bool,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct CreateApiTokenResponse {
+    pub api_token: DbApiToken,
+    /// The actual token which needs to be passed in requests. It is only shown once.
+    pub token: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[cfg_attr(feature = "ssr", derive(JsonSchema))]
+pub struct DeleteApiTokenParams {
+    pub id: ApiTokenId,
+}
@@ -1,24 +1,40 @@
+use super::article::DbArticle;
+
+/// Domain of the url including the port, if any. Instances and federation list entries are
+/// stored in this format, so it must be used whenever a url is compared with them.
+pub fn url_domain(url: &url::Url) -> String {
+    let mut port = String::new();
+    if let Some(port_) = url.port() {
+        port = format!(":{port_}");
+    }
+    format!("{}{port}", url.host_str().expect("has domain"))
+}
+
 #[cfg(feature = "ssr")]
 pub fn extract_domain<T>(url: &activitypub_federation::fetch::object_id::ObjectId<T>) -> String
 where
     T: activitypub_federation::traits::Object + Send + 'static,
     for<'de2> <T as activitypub_federation::traits::Object>::Kind: serde::Deserialize<'de2>,
 {
-    let mut port = String::new();
-    if let Some(port_) = url.inner().port() {
-        port = format!(":{port_}");
-    }
-    format!("{}{port}", url.inner().host_str().expect("has domain"))
+    url_domain(url.inner())
 }
 
 #[cfg(not(feature = "ssr"))]
 pub fn extract_domain(url: &String) -> String {
-    let url = url::Url::parse(url).unwrap();
-    let mut port = String::new();
-    if let Some(port_) = url.port() {
-        port = format!(":{port_}");
+    url_domain(&url::Url::parse(url).unwrap())
+}
+
+/// Path of the article page, with the domain for remote articles.
+pub fn article_path(article: &DbArticle) -> String {
+    if article.local {
+        format!("/article/{}", article.title)
+    } else {
+        format!(
+            "/article/{}@{}",
+            article.title,
+            extract_domain(&article.ap_id)
+        )
     }
-    format!("{}{port}", url.host_str().unwrap())
 }
 
 pub fn http_protocol_str() -> &'static str {
@@ -14,8 +14,11 @@ use crate::common::{
         GetEditList,
         ListArticlesParams,
         ProtectArticleParams,
+        RecentChangeView,
+        RecentChangesParams,
     },
     newtypes::{ArticleId, ConflictId},
+    pagination::Paginated,
     ResolveObjectParams,
 };
 use http::Method;
@@ -35,7 +38,7 @@ impl ApiClient {
         self.get("/api/v1/article", Some(data)).await
     }
 
-    pub async fn list_articles(&self, data: ListArticlesParams) -> Option<Vec<DbArticle>> {
+    pub async fn list_articles(&self, data: ListArticlesParams) -> Option<Paginated<DbArticle>> {
         Some(self.get("/api/v1/article/list", Some(data)).await.unwrap())
     }
 
@@ -71,13 +74,41 @@ impl ApiClient {
             article_id: Some(article_id),
             ..Default::default()
         };
-        self.get("/api/v1/edit/list", Some(data)).await
+        self.list_all_edits(data).await
+    }
+
+    pub async fn list_edits(&self, params: GetEditList) -> Option<Paginated<EditView>> {
+        self.get("/api/v1/edit/list", Some(params)).await
+    }
+
+    pub async fn recent_changes(
+        &self,
+        params: &RecentChangesParams,
+    ) -> Option<Paginated<RecentChangeView>> {
+        self.get("/api/v1/recent_changes", Some(params)).await
+    }
+
+    /// Follow the pagination cursor to read all edits matching the params. Unlike the api
+    /// endpoint, these are returned in chronological order.
+    pub(super) async fn list_all_edits(&self, mut params: GetEditList) -> Option<Vec<EditView>> {
+        let mut edits = vec![];
+        loop {
+            let page = self.list_edits(params.clone()).await?;
+            edits.extend(page.items);
+            match page.next_cursor {
+                Some(cursor) => params.cursor = Some(cursor),
+                None => break,
+            }
+        }
+        edits.reverse();
+        Some(edits)
     }
 
     pub async fn approve_article(&self, article_id: ArticleId, approve: bool) -> Option<()> {
         let params = ApproveArticleParams {
             article_id,
             approve,
+            reason: None,
         };
         result_to_option(self.post("/api/v1/article/approve", Some(&params)).await)
     }
@@ -106,6 +137,7 @@ impl ApiClient {
             title: None,
             domain: None,
             id: Some(params.article_id),
+            record_view: None,
         })
         .await
     }
@@ -1,5 +1,13 @@
 use super::ApiClient;
-use crate::common::comment::{CreateCommentParams, DbCommentView, EditCommentParams};
+use crate::common::comment::{
+    CommentTree,
+    CreateCommentParams,
+    DbCommentView,
+    EditCommentParams,
+    ListCommentsParams,
+    RemoveCommentParams,
+    VoteCommentParams,
+};
 use leptos::prelude::ServerFnError;
 
 impl ApiClient {
@@ -16,4 +24,22 @@ impl ApiClient {
     ) -> Result<DbCommentView, ServerFnError> {
         self.patch("/api/v1/comment", Some(&params)).await
     }
+
+    pub async fn list_comments(&self, params: &ListCommentsParams) -> Option<CommentTree> {
+        self.get("/api/v1/comment/list", Some(params)).await
+    }
+
+    pub async fn remove_comment(
+        &self,
+        params: &RemoveCommentParams,
+    ) -> Result<DbCommentView, ServerFnError> {
+        self.post("/api/v1/comment/remove", Some(&params)).await
+    }
+
+    pub async fn vote_comment(
+        &self,
+        params: &VoteCommentParams,
+    ) -> Result<DbCommentView, ServerFnError> {
+        self.post("/api/v1/comment/vote", Some(&params)).await
+    }
 }
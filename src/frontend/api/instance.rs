@@ -1,14 +1,23 @@
 use super::{result_to_option, ApiClient};
 use crate::common::{
-    article::{DbArticle, SearchArticleParams},
+    article::{ArticleSuggestion, SearchArticleParams, SearchArticleResponse, SearchSuggestParams},
     instance::{
+        DbFederationListEntry,
         DbInstance,
         FollowInstanceParams,
         GetInstanceParams,
+        GetInstanceStatsParams,
+        InstanceStatsDay,
         InstanceView,
+        ListInstancesParams,
+        RemoveFederationListParams,
         SiteView,
+        UpdateFederationListParams,
         UpdateInstanceParams,
     },
+    modlog::{GetModlogParams, ModlogView},
+    pagination::Paginated,
+    ListNotificationsParams,
     Notification,
     ResolveObjectParams,
     SuccessResponse,
@@ -26,8 +35,18 @@ impl ApiClient {
         self.get("/api/v1/instance", Some(&params)).await
     }
 
-    pub async fn list_instances(&self) -> Option<Vec<DbInstance>> {
-        self.get("/api/v1/instance/list", None::<i32>).await
+    pub async fn list_instances(
+        &self,
+        params: &ListInstancesParams,
+    ) -> Option<Paginated<DbInstance>> {
+        self.get("/api/v1/instance/list", Some(params)).await
+    }
+
+    pub async fn instance_stats(
+        &self,
+        params: &GetInstanceStatsParams,
+    ) -> Option<Vec<InstanceStatsDay>> {
+        self.get("/api/v1/instance/stats", Some(params)).await
     }
 
     pub async fn update_local_instance(
@@ -37,11 +56,57 @@ impl ApiClient {
         self.patch("/api/v1/instance", Some(params)).await
     }
 
-    pub async fn notifications_list(&self) -> Option<Vec<Notification>> {
-        self.get("/api/v1/user/notifications/list", None::<()>)
+    pub async fn list_modlog(&self, params: &GetModlogParams) -> Option<Paginated<ModlogView>> {
+        self.get("/api/v1/modlog", Some(params)).await
+    }
+
+    pub async fn list_federation_list(&self) -> Option<Vec<DbFederationListEntry>> {
+        self.get("/api/v1/instance/federation_list", None::<()>)
             .await
     }
 
+    pub async fn update_federation_list(
+        &self,
+        params: &UpdateFederationListParams,
+    ) -> Result<DbFederationListEntry, ServerFnError> {
+        self.post("/api/v1/instance/federation_list", Some(params))
+            .await
+    }
+
+    pub async fn remove_federation_list(
+        &self,
+        params: &RemoveFederationListParams,
+    ) -> Result<SuccessResponse, ServerFnError> {
+        self.send(
+            Method::DELETE,
+            "/api/v1/instance/federation_list",
+            Some(params),
+        )
+        .await
+    }
+
+    pub async fn notifications_list(
+        &self,
+        params: &ListNotificationsParams,
+    ) -> Option<Paginated<Notification>> {
+        self.get("/api/v1/user/notifications/list", Some(params))
+            .await
+    }
+
+    /// Follow the pagination cursor to read all notifications.
+    pub async fn notifications_list_all(&self) -> Option<Vec<Notification>> {
+        let mut params = ListNotificationsParams::default();
+        let mut notifications = vec![];
+        loop {
+            let page = self.notifications_list(&params).await?;
+            notifications.extend(page.items);
+            match page.next_cursor {
+                Some(cursor) => params.cursor = Some(cursor),
+                None => return Some(notifications),
+            }
+        }
+    }
+
     pub async fn notifications_count(&self) -> Option<usize> {
         self.get("/api/v1/user/notifications/count", None::<()>)
             .await
@@ -49,10 +114,17 @@ impl ApiClient {
     pub async fn search(
         &self,
         params: &SearchArticleParams,
-    ) -> Result<Vec<DbArticle>, ServerFnError> {
+    ) -> Result<SearchArticleResponse, ServerFnError> {
         self.send(Method::GET, "/api/v1/search", Some(params)).await
     }
 
+    pub async fn search_suggest(
+        &self,
+        params: &SearchSuggestParams,
+    ) -> Option<Vec<ArticleSuggestion>> {
+        self.get("/api/v1/search/suggest", Some(params)).await
+    }
+
     pub async fn resolve_instance(&self, id: Url) -> Result<DbInstance, ServerFnError> {
         let resolve_object = ResolveObjectParams { id };
         self.send(
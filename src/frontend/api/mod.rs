@@ -7,6 +7,7 @@ use std::{fmt::Debug, sync::LazyLock};
 pub mod article;
 pub mod comment;
 pub mod instance;
+pub mod report;
 pub mod user;
 
 pub static CLIENT: LazyLock<ApiClient> = LazyLock::new(|| {
@@ -87,9 +88,9 @@ impl ApiClient {
         P: Serialize + Debug,
         T: for<'de> Deserialize<'de>,
     {
-        use crate::common::{Auth, AUTH_COOKIE};
+        use crate::common::{Auth, UserAgent, AUTH_COOKIE};
         use leptos::prelude::use_context;
-        use reqwest::header::HeaderName;
+        use reqwest::header::{HeaderName, USER_AGENT};
         let mut req = self
             .client
             .request(method.clone(), self.request_endpoint(path));
@@ -102,6 +103,9 @@ impl ApiClient {
         if let Some(Auth(Some(auth))) = auth {
             req = req.header(HeaderName::from_static(AUTH_COOKIE), auth);
         }
+        if let Some(UserAgent(Some(user_agent))) = use_context::<UserAgent>() {
+            req = req.header(USER_AGENT, user_agent);
+        }
         let res = req.send().await?;
         let status = res.status();
         let url = res.url().to_string();
@@ -0,0 +1,26 @@
+use super::ApiClient;
+use crate::common::{
+    pagination::Paginated,
+    report::{CreateReportParams, DbReport, ListReportsParams, ReportView, ResolveReportParams},
+};
+use leptos::prelude::ServerFnError;
+
+impl ApiClient {
+    pub async fn create_report(
+        &self,
+        params: &CreateReportParams,
+    ) -> Result<DbReport, ServerFnError> {
+        self.post("/api/v1/report", Some(params)).await
+    }
+
+    pub async fn list_reports(&self, params: &ListReportsParams) -> Option<Paginated<ReportView>> {
+        self.get("/api/v1/report/list", Some(params)).await
+    }
+
+    pub async fn resolve_report(
+        &self,
+        params: &ResolveReportParams,
+    ) -> Result<DbReport, ServerFnError> {
+        self.post("/api/v1/report/resolve", Some(params)).await
+    }
+}
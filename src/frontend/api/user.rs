@@ -1,17 +1,38 @@
 use super::{result_to_option, ApiClient};
 use crate::common::{
-    article::{EditView, GetEditList},
+    article::{DbArticle, EditView, GetEditList},
     newtypes::PersonId,
+    pagination::Paginated,
     user::{
+        BanUserParams,
+        CreateApiTokenParams,
+        CreateApiTokenResponse,
+        DbApiToken,
+        DbLocalUser,
         DbPerson,
+        DeleteAccountParams,
+        DeleteApiTokenParams,
+        DeleteUserParams,
+        DisableTotpParams,
+        EnableTotpParams,
         GetUserParams,
+        GetUserRoleParams,
+        GetUserStatsParams,
         LocalUserView,
         LoginUserParams,
+        OidcTotpParams,
         RegisterUserParams,
+        TotpRecoveryCodes,
+        TotpSetupResponse,
         UpdateUserParams,
+        UpdateUserRoleParams,
+        UserCommentView,
+        UserContributionsParams,
+        UserStats,
     },
     SuccessResponse,
 };
+use http::Method;
 use leptos::prelude::ServerFnError;
 
 impl ApiClient {
@@ -26,6 +47,10 @@ impl ApiClient {
         self.post("/api/v1/account/login", Some(&params)).await
     }
 
+    pub async fn oidc_totp(&self, params: &OidcTotpParams) -> Result<LocalUserView, ServerFnError> {
+        self.post("/api/v1/account/oidc/totp", Some(params)).await
+    }
+
     pub async fn logout(&self) -> Option<SuccessResponse> {
         result_to_option(self.post("/api/v1/account/logout", None::<()>).await)
     }
@@ -34,6 +59,25 @@ impl ApiClient {
         self.get("/api/v1/user", Some(data)).await
     }
 
+    pub async fn user_stats(&self, person_id: PersonId) -> Option<UserStats> {
+        let params = GetUserStatsParams { person_id };
+        self.get("/api/v1/user/stats", Some(params)).await
+    }
+
+    pub async fn user_comments(
+        &self,
+        params: &UserContributionsParams,
+    ) -> Option<Paginated<UserCommentView>> {
+        self.get("/api/v1/user/comments", Some(params)).await
+    }
+
+    pub async fn user_articles(
+        &self,
+        params: &UserContributionsParams,
+    ) -> Option<Paginated<DbArticle>> {
+        self.get("/api/v1/user/articles", Some(params)).await
+    }
+
     pub async fn update_user_profile(
         &self,
         data: UpdateUserParams,
@@ -41,11 +85,79 @@ impl ApiClient {
         self.post("/api/v1/account/update", Some(data)).await
     }
 
+    pub async fn delete_account(
+        &self,
+        params: &DeleteAccountParams,
+    ) -> Result<SuccessResponse, ServerFnError> {
+        self.post("/api/v1/account/delete", Some(params)).await
+    }
+
+    pub async fn delete_user(
+        &self,
+        params: &DeleteUserParams,
+    ) -> Result<SuccessResponse, ServerFnError> {
+        self.post("/api/v1/user/delete", Some(params)).await
+    }
+
+    pub async fn ban_user(&self, params: &BanUserParams) -> Result<DbPerson, ServerFnError> {
+        self.post("/api/v1/user/ban", Some(params)).await
+    }
+
+    pub async fn get_user_role(&self, person_id: PersonId) -> Option<DbLocalUser> {
+        let params = GetUserRoleParams { person_id };
+        self.get("/api/v1/user/role", Some(params)).await
+    }
+
+    pub async fn update_user_role(
+        &self,
+        params: &UpdateUserRoleParams,
+    ) -> Result<DbLocalUser, ServerFnError> {
+        self.post("/api/v1/user/role", Some(params)).await
+    }
+
+    pub async fn setup_totp(&self) -> Result<TotpSetupResponse, ServerFnError> {
+        self.post("/api/v1/account/totp/setup", None::<()>).await
+    }
+
+    pub async fn enable_totp(
+        &self,
+        params: &EnableTotpParams,
+    ) -> Result<TotpRecoveryCodes, ServerFnError> {
+        self.post("/api/v1/account/totp/enable", Some(params)).await
+    }
+
+    pub async fn disable_totp(
+        &self,
+        params: &DisableTotpParams,
+    ) -> Result<SuccessResponse, ServerFnError> {
+        self.post("/api/v1/account/totp/disable", Some(params))
+            .await
+    }
+
+    pub async fn create_api_token(
+        &self,
+        params: &CreateApiTokenParams,
+    ) -> Result<CreateApiTokenResponse, ServerFnError> {
+        self.post("/api/v1/account/token", Some(params)).await
+    }
+
+    pub async fn list_api_tokens(&self) -> Option<Vec<DbApiToken>> {
+        self.get("/api/v1/account/token/list", None::<()>).await
+    }
+
+    pub async fn delete_api_token(
+        &self,
+        params: &DeleteApiTokenParams,
+    ) -> Result<SuccessResponse, ServerFnError> {
+        self.send(Method::DELETE, "/api/v1/account/token", Some(params))
+            .await
+    }
+
     pub async fn get_person_edits(&self, person_id: PersonId) -> Option<Vec<EditView>> {
         let data = GetEditList {
             person_id: Some(person_id),
             ..Default::default()
         };
-        self.get("/api/v1/edit/list", Some(data)).await
+        self.list_all_edits(data).await
     }
 }
@@ -15,8 +15,12 @@ use crate::frontend::{
         instance::{
             details::InstanceDetails,
             list::ListInstances,
+            modlog::Modlog,
+            recent_changes::RecentChanges,
+            reports::Reports,
             search::Search,
             settings::InstanceSettings,
+            stats::InstanceStats,
         },
         user::{
             edit_profile::UserEditProfile,
@@ -106,6 +110,8 @@ pub fn App() -> impl IntoView {
                         <Route path=path!("/articles") view=ListArticles />
                         <Route path=path!("/instances") view=ListInstances />
                         <Route path=path!("/instance/:hostname") view=InstanceDetails />
+                        <Route path=path!("/modlog") view=Modlog />
+                        <Route path=path!("/recent_changes") view=RecentChanges />
                         <Route path=path!("/user/:name") view=UserProfile />
                         <Route path=path!("/login") view=Login />
                         <Route path=path!("/register") view=Register />
@@ -113,6 +119,8 @@ pub fn App() -> impl IntoView {
                         <IbisProtectedRoute path=path!("/edit_profile") view=UserEditProfile />
                         <IbisProtectedRoute path=path!("/notifications") view=Notifications />
                         <IbisProtectedRoute path=path!("/settings") view=InstanceSettings />
+                        <IbisProtectedRoute path=path!("/reports") view=Reports />
+                        <IbisProtectedRoute path=path!("/stats") view=InstanceStats />
                     </Routes>
                 </main>
             </Router>
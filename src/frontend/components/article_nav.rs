@@ -1,8 +1,13 @@
 use crate::{
-    common::{article::DbArticleView, validation::can_edit_article},
+    common::{
+        article::DbArticleView,
+        user::Permission,
+        utils::article_path,
+        validation::can_edit_article,
+    },
     frontend::utils::{
-        formatting::{article_path, article_title},
-        resources::{is_admin, is_logged_in},
+        formatting::article_title,
+        resources::{has_permission, is_logged_in},
     },
 };
 use leptos::prelude::*;
@@ -31,6 +36,11 @@ pub fn ArticleNav(article: Resource<DbArticleView>, active_tab: ActiveTab) -> im
                         let article_link = article_path(&article_.article);
                         let article_link_ = article_link.clone();
                         let protected = article_.article.protected;
+                        let discussion_label = if article_.comment_count > 0 {
+                            format!("Discussion ({})", article_.comment_count)
+                        } else {
+                            "Discussion".to_string()
+                        };
                         view! {
                             <Title text=page_title(&active_tab, &title) />
                             <div role="tablist" class="tabs tabs-lifted">
@@ -42,7 +52,7 @@ pub fn ArticleNav(article: Resource<DbArticleView>, active_tab: ActiveTab) -> im
                                     {..}
                                     class=tab_classes.discussion
                                 >
-                                    "Discussion"
+                                    {discussion_label}
                                 </A>
                                 <A
                                     href=format!("{article_link}/history")
@@ -53,7 +63,11 @@ pub fn ArticleNav(article: Resource<DbArticleView>, active_tab: ActiveTab) -> im
                                 </A>
                                 <Show when=move || {
                                     is_logged_in()
-                                        && can_edit_article(&article_.article, is_admin()).is_ok()
+                                        && can_edit_article(
+                                                &article_.article,
+                                                has_permission(Permission::ProtectArticle),
+                                            )
+                                            .is_ok()
                                 }>
                                     <A
                                         href=format!("{article_link}/edit")
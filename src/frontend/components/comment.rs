@@ -1,16 +1,26 @@
 use crate::{
     common::{
         article::DbArticleView,
-        comment::{DbComment, DbCommentView, EditCommentParams},
+        comment::{
+            DbComment,
+            DbCommentView,
+            EditCommentParams,
+            RemoveCommentParams,
+            VoteCommentParams,
+        },
         newtypes::CommentId,
+        user::Permission,
     },
     frontend::{
         api::CLIENT,
-        components::comment_editor::{CommentEditorView, EditParams},
+        components::{
+            comment_editor::{CommentEditorView, EditParams},
+            report_button::ReportButton,
+        },
         markdown::render_comment_markdown,
         utils::{
             formatting::{time_ago, user_link},
-            resources::{site, DefaultResource},
+            resources::{has_permission, is_logged_in, site, DefaultResource},
         },
     },
 };
@@ -26,6 +36,7 @@ pub fn CommentView(
     let comment_change_signal = signal(comment.comment.clone());
     let render_comment = move || render_content(comment_change_signal.0.get());
     let delete_restore_label = move || delete_restore_label(comment_change_signal.0.get());
+    let remove_restore_label = move || remove_restore_label(comment_change_signal.0.get());
 
     // css class is not included because its dynamically generated, need to use raw css instead of class
     let margin = comment.comment.depth * 2;
@@ -50,6 +61,48 @@ pub fn CommentView(
         comment_change_signal.1.set(comment.comment);
     });
 
+    let (votes, set_votes) = signal((comment.upvotes, comment.downvotes, comment.my_vote));
+    let vote_action = Action::new(move |score: &i16| {
+        // Clicking the same vote again removes it
+        let score = if votes.get_untracked().2 == Some(*score) {
+            0
+        } else {
+            *score
+        };
+        let params = VoteCommentParams {
+            comment_id: comment.comment.id,
+            score,
+        };
+        async move {
+            let comment = CLIENT.vote_comment(&params).await.unwrap();
+            set_votes.set((comment.upvotes, comment.downvotes, comment.my_vote));
+        }
+    });
+    let vote_class = move |score: i16| {
+        if votes.get().2 == Some(score) {
+            "link font-bold"
+        } else {
+            "link"
+        }
+    };
+
+    let (show_remove_form, set_show_remove_form) = signal(false);
+    let (remove_reason, set_remove_reason) = signal(String::new());
+    let remove_restore_comment_action = Action::new(move |_: &()| async move {
+        let params = RemoveCommentParams {
+            id: comment.comment.id,
+            removed: !comment_change_signal.0.get_untracked().removed,
+            reason: Some(remove_reason.get_untracked()),
+        };
+        let comment = CLIENT.remove_comment(&params).await.unwrap();
+        comment_change_signal.1.set(comment.comment);
+        set_show_remove_form.set(false);
+    });
+    let can_remove = move || {
+        has_permission(Permission::RemoveComment)
+            && article.get().map(|a| a.article.local).unwrap_or(false)
+    };
+
     let is_creator = site().with_default(|site| site.my_profile.as_ref().map(|p| p.person.id))
         == Some(comment.comment.creator_id);
 
@@ -63,6 +116,38 @@ pub fn CommentView(
             <div class="py-2">
                 <div class="flex text-xs">
                     <span class="grow">{user_link(&comment.creator)}</span>
+                    <span class="mr-2">
+                        <Show
+                            when=is_logged_in
+                            fallback=move || {
+                                view! {
+                                    <span>
+                                        {move || format!("▲ {} ▼ {}", votes.get().0, votes.get().1)}
+                                    </span>
+                                }
+                            }
+                        >
+                            <a
+                                class=move || vote_class(1)
+                                on:click=move |_| {
+                                    vote_action.dispatch(1);
+                                }
+                            >
+                                "▲ "
+                                {move || votes.get().0}
+                            </a>
+                            " "
+                            <a
+                                class=move || vote_class(-1)
+                                on:click=move |_| {
+                                    vote_action.dispatch(-1);
+                                }
+                            >
+                                "▼ "
+                                {move || votes.get().1}
+                            </a>
+                        </Show>
+                    </span>
                     <a href=comment_link class="link">
                         {time_ago(comment.comment.published)}
                     </a>
@@ -82,7 +167,9 @@ pub fn CommentView(
                 >
                     <div class="my-2 prose prose-slate" inner_html=render_comment></div>
                     <div class="text-xs">
-                        <Show when=move || !comment.comment.deleted>
+                        <Show when=move || {
+                            !comment.comment.deleted && !comment_change_signal.0.get().removed
+                        }>
                             <a class="link" on:click=move |_| show_editor.1.set(comment.comment.id)>
                                 Reply
                             </a>
@@ -92,7 +179,17 @@ pub fn CommentView(
                             Fedilink
                         </a>
                         " | "
-                        <Show when=move || is_creator && !comment_change_signal.0.get().deleted>
+                        <Show when=move || !is_creator && is_logged_in()>
+                            <ReportButton
+                                article_id=comment.comment.article_id
+                                comment_id=comment.comment.id
+                            />
+                            " | "
+                        </Show>
+                        <Show when=move || {
+                            let comment = comment_change_signal.0.get();
+                            is_creator && !comment.deleted && !comment.removed
+                        }>
                             <a
                                 class="link"
                                 on:click=move |_| {
@@ -103,7 +200,16 @@ pub fn CommentView(
                             </a>
                             " | "
                         </Show>
-                        <Show when=move || is_creator>
+                        <Show when=can_remove>
+                            <a
+                                class="link"
+                                on:click=move |_| set_show_remove_form.update(|s| *s = !*s)
+                            >
+                                {remove_restore_label}
+                            </a>
+                            " | "
+                        </Show>
+                        <Show when=move || is_creator && !comment_change_signal.0.get().removed>
                             <a
                                 class="link"
                                 on:click=move |_| {
@@ -113,6 +219,24 @@ pub fn CommentView(
                                 {delete_restore_label}
                             </a>
                         </Show>
+                        <Show when=move || show_remove_form.get()>
+                            <div class="flex gap-2 items-center my-2">
+                                <input
+                                    type="text"
+                                    class="input input-sm input-bordered"
+                                    placeholder="Reason"
+                                    on:input=move |ev| set_remove_reason.set(event_target_value(&ev))
+                                />
+                                <button
+                                    class="btn btn-sm btn-error"
+                                    on:click=move |_| {
+                                        remove_restore_comment_action.dispatch(());
+                                    }
+                                >
+                                    {remove_restore_label}
+                                </button>
+                            </div>
+                        </Show>
                         <Show when=move || show_editor.0.get() == comment.comment.id>
                             <CommentEditorView
                                 article=article
@@ -129,7 +253,9 @@ pub fn CommentView(
 }
 
 fn render_content(comment: DbComment) -> String {
-    let content = if comment.deleted {
+    let content = if comment.removed {
+        "*removed by moderator*"
+    } else if comment.deleted {
         "*deleted*"
     } else {
         &comment.content
@@ -144,3 +270,11 @@ fn delete_restore_label(comment: DbComment) -> &'static str {
         "Delete"
     }
 }
+
+fn remove_restore_label(comment: DbComment) -> &'static str {
+    if comment.removed {
+        "Restore as moderator"
+    } else {
+        "Remove"
+    }
+}
@@ -28,6 +28,7 @@ pub fn EditList(edits: Vec<EditView>, for_article: bool) -> impl IntoView {
                                     {date}
                                     " by "
                                     {user_link(&edit.creator)}
+                                    {edit.creator.bot.then_some(" (bot)")}
                                 },
                             )
                         } else {
@@ -0,0 +1,22 @@
+use leptos::prelude::*;
+use leptos_meta::Link;
+
+/// Advertise the Atom and RSS feeds which are served at `path`, so that browsers and feed readers
+/// can find them.
+#[component]
+pub fn FeedLinks(title: String, path: String) -> impl IntoView {
+    view! {
+        <Link
+            rel="alternate"
+            type_="application/atom+xml"
+            title=format!("{title} (Atom)")
+            href=path.clone()
+        />
+        <Link
+            rel="alternate"
+            type_="application/rss+xml"
+            title=format!("{title} (RSS)")
+            href=format!("{path}?format=rss")
+        />
+    }
+}
@@ -5,6 +5,10 @@ pub mod comment_editor;
 pub mod connect;
 pub mod credentials;
 pub mod edit_list;
+pub mod feed_links;
 pub mod instance_follow_button;
 pub mod nav;
+pub mod pagination;
 pub mod protected_route;
+pub mod report_button;
+pub mod title_suggestions;
@@ -1,12 +1,16 @@
-use crate::frontend::{
-    api::CLIENT,
-    utils::{
-        dark_mode::DarkMode,
-        formatting::instance_title,
-        resources::{is_admin, is_logged_in, site, DefaultResource},
+use crate::{
+    common::user::Permission,
+    frontend::{
+        api::CLIENT,
+        components::title_suggestions::TitleSuggestions,
+        utils::{
+            dark_mode::DarkMode,
+            formatting::instance_title,
+            resources::{has_permission, is_admin, is_logged_in, site, DefaultResource},
+        },
     },
 };
-use leptos::{component, prelude::*, view, IntoView, *};
+use leptos::{component, prelude::*, view, IntoView};
 use leptos_router::hooks::use_navigate;
 
 #[component]
@@ -53,6 +57,12 @@ pub fn Nav() -> impl IntoView {
                             <li>
                                 <a href="/articles">"Articles"</a>
                             </li>
+                            <li>
+                                <a href="/recent_changes">"Recent Changes"</a>
+                            </li>
+                            <li>
+                                <a href="/modlog">"Moderation Log"</a>
+                            </li>
                             <Show when=is_logged_in>
                                 <li>
                                     <a href="/create-article">"Create Article"</a>
@@ -66,11 +76,23 @@ pub fn Nav() -> impl IntoView {
                                     </a>
                                 </li>
                             </Show>
-                            <Show when=is_admin>
+                            <Show when=move || has_permission(Permission::HandleReports)>
+                                <li>
+                                    <a href="/reports">"Reports"</a>
+                                </li>
+                            </Show>
+                            <Show when=move || {
+                                is_admin() || has_permission(Permission::ManageFederation)
+                            }>
                                 <li>
                                     <a href="/settings">"Settings"</a>
                                 </li>
                             </Show>
+                            <Show when=is_admin>
+                                <li>
+                                    <a href="/stats">"Statistics"</a>
+                                </li>
+                            </Show>
                             <li>
                                 <form
                                     class="p-1 m-0 form-control"
@@ -90,12 +112,14 @@ pub fn Nav() -> impl IntoView {
                                         type="text"
                                         class="w-full rounded input input-secondary input-bordered input-xs"
                                         placeholder="Search"
+                                        list="search-suggestions"
                                         prop:value=search_query
-                                        on:keyup=move |ev: ev::KeyboardEvent| {
+                                        on:input=move |ev| {
                                             let val = event_target_value(&ev);
                                             set_search_query.update(|v| *v = val);
                                         }
                                     />
+                                    <TitleSuggestions id="search-suggestions" query=search_query />
 
                                     <button class="btn btn-xs btn-secondary">Go</button>
                                 </form>
@@ -110,7 +134,11 @@ pub fn Nav() -> impl IntoView {
                                         <a href="/login">"Login"</a>
                                     </li>
                                     <Show when=move || {
-                                        site().with_default(|s| s.config.registration_open)
+                                        site()
+                                            .with_default(|s| {
+                                                s.config.registration_open
+                                                    && s.config.password_registration
+                                            })
                                     }>
                                         <li>
                                             <a href="/register">"Register"</a>
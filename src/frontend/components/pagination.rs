@@ -0,0 +1,74 @@
+use crate::common::pagination::PaginationCursor;
+use leptos::prelude::*;
+
+/// Cursor of the page which is currently shown, and cursors of the previous pages so that the
+/// user can navigate back.
+#[derive(Clone, Copy)]
+pub struct PaginationState {
+    cursor: RwSignal<Option<PaginationCursor>>,
+    previous: RwSignal<Vec<Option<PaginationCursor>>>,
+}
+
+impl Default for PaginationState {
+    fn default() -> Self {
+        Self {
+            cursor: RwSignal::new(None),
+            previous: RwSignal::new(vec![]),
+        }
+    }
+}
+
+impl PaginationState {
+    pub fn cursor(&self) -> Option<PaginationCursor> {
+        self.cursor.get()
+    }
+
+    /// Go back to the first page, needs to be called when filter params change.
+    pub fn reset(&self) {
+        self.previous.set(vec![]);
+        self.cursor.set(None);
+    }
+
+    fn next(&self, next_cursor: PaginationCursor) {
+        let current = self.cursor.get_untracked();
+        self.previous.update(|p| p.push(current));
+        self.cursor.set(Some(next_cursor));
+    }
+
+    fn previous(&self) {
+        let mut previous = None;
+        self.previous.update(|p| previous = p.pop().flatten());
+        self.cursor.set(previous);
+    }
+}
+
+#[component]
+pub fn PaginationButtons(
+    state: PaginationState,
+    next_cursor: Option<PaginationCursor>,
+) -> impl IntoView {
+    let has_previous = move || !state.previous.get().is_empty();
+    let has_next = next_cursor.is_some();
+    view! {
+        <div class="my-4 join">
+            <button
+                class="join-item btn btn-sm"
+                disabled=move || !has_previous()
+                on:click=move |_| state.previous()
+            >
+                "Previous"
+            </button>
+            <button
+                class="join-item btn btn-sm"
+                disabled=!has_next
+                on:click=move |_| {
+                    if let Some(next_cursor) = next_cursor.clone() {
+                        state.next(next_cursor);
+                    }
+                }
+            >
+                "Next"
+            </button>
+        </div>
+    }
+}
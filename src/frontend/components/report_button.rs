@@ -0,0 +1,64 @@
+use crate::{
+    common::{
+        newtypes::{ArticleId, CommentId, EditId},
+        report::CreateReportParams,
+    },
+    frontend::{api::CLIENT, utils::resources::is_logged_in},
+};
+use leptos::prelude::*;
+
+/// Link which opens a small form to report an article, edit or comment to the moderators.
+#[component]
+pub fn ReportButton(
+    article_id: ArticleId,
+    #[prop(optional)] edit_id: Option<EditId>,
+    #[prop(optional)] comment_id: Option<CommentId>,
+) -> impl IntoView {
+    let (show_form, set_show_form) = signal(false);
+    let (reason, set_reason) = signal(String::new());
+    let (message, set_message) = signal(None::<String>);
+    let report_action = Action::new(move |reason: &String| {
+        let params = CreateReportParams {
+            article_id,
+            edit_id,
+            comment_id,
+            reason: reason.clone(),
+        };
+        async move {
+            match CLIENT.create_report(&params).await {
+                Ok(_) => {
+                    set_show_form.set(false);
+                    set_message.set(Some("Reported".to_string()));
+                }
+                Err(err) => set_message.set(Some(err.to_string())),
+            }
+        }
+    });
+
+    view! {
+        <Show when=is_logged_in>
+            <a class="link" on:click=move |_| set_show_form.update(|s| *s = !*s)>
+                Report
+            </a>
+            {move || message.get().map(|m| view! { <span class="ml-2">{m}</span> })}
+            <Show when=move || show_form.get()>
+                <div class="flex gap-2 items-center my-2">
+                    <input
+                        type="text"
+                        class="input input-sm input-bordered"
+                        placeholder="Reason"
+                        on:input=move |ev| set_reason.set(event_target_value(&ev))
+                    />
+                    <button
+                        class="btn btn-sm btn-error"
+                        on:click=move |_| {
+                            report_action.dispatch(reason.get());
+                        }
+                    >
+                        Submit report
+                    </button>
+                </div>
+            </Show>
+        </Show>
+    }
+}
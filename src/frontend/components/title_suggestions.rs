@@ -0,0 +1,38 @@
+use crate::{common::article::SearchSuggestParams, frontend::api::CLIENT};
+use leptos::prelude::*;
+use leptos_use::signal_debounced;
+
+/// Datalist with titles of existing articles which match the query. Use it for autocomplete by
+/// setting the `list` attribute of an input to the same id.
+#[component]
+pub fn TitleSuggestions(id: &'static str, query: ReadSignal<String>) -> impl IntoView {
+    let query = signal_debounced(query, 300.0);
+    let suggestions = Resource::new(
+        move || query.get(),
+        |query: String| async move {
+            if query.trim().chars().count() < 2 {
+                return vec![];
+            }
+            let params = SearchSuggestParams {
+                query,
+                ..Default::default()
+            };
+            CLIENT.search_suggest(&params).await.unwrap_or_default()
+        },
+    );
+
+    view! {
+        <datalist id=id>
+            <Transition>
+                {move || {
+                    suggestions
+                        .get()
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|s| view! { <option value=s.title.replace('_', " ") /> })
+                        .collect_view()
+                }}
+            </Transition>
+        </datalist>
+    }
+}
@@ -5,7 +5,7 @@ pub mod app;
 mod markdown;
 mod components;
 mod pages;
-mod utils;
+pub(crate) mod utils;
 
 #[cfg(feature = "hydrate")]
 #[wasm_bindgen::prelude::wasm_bindgen]
@@ -2,12 +2,17 @@ use crate::{
     common::{
         article::{ForkArticleParams, ProtectArticleParams},
         newtypes::ArticleId,
+        user::Permission,
+        utils::article_path,
     },
     frontend::{
         api::CLIENT,
-        components::article_nav::{ActiveTab, ArticleNav},
+        components::{
+            article_nav::{ActiveTab, ArticleNav},
+            report_button::ReportButton,
+        },
         pages::article_resource,
-        utils::{formatting::article_path, resources::is_admin},
+        utils::resources::has_permission,
         DbArticle,
     },
 };
@@ -16,7 +21,7 @@ use leptos_router::components::Redirect;
 
 #[component]
 pub fn ArticleActions() -> impl IntoView {
-    let article = article_resource();
+    let article = article_resource(false);
     let (new_title, set_new_title) = signal(String::new());
     let (fork_response, set_fork_response) = signal(Option::<DbArticle>::None);
     let (error, set_error) = signal(None::<String>);
@@ -40,6 +45,7 @@ pub fn ArticleActions() -> impl IntoView {
         let params = ProtectArticleParams {
             article_id: *id,
             protected: !protected,
+            reason: None,
         };
         async move {
             set_error.update(|e| *e = None);
@@ -69,7 +75,9 @@ pub fn ArticleActions() -> impl IntoView {
                                         .map(|err| {
                                             view! { <p class="alert">{err}</p> }
                                         })
-                                }} <Show when=move || { is_admin() && article.article.local }>
+                                }} <Show when=move || {
+                                    has_permission(Permission::ProtectArticle) && article.article.local
+                                }>
                                     <button
                                         class="btn btn-secondary"
                                         on:click=move |_| {
@@ -79,7 +87,7 @@ pub fn ArticleActions() -> impl IntoView {
                                     >
                                         Toggle Article Protection
                                     </button>
-                                    <p>"Protect a local article so that only admins can edit it"</p>
+                                    <p>"Protect a local article so that only moderators can edit it"</p>
                                 </Show> <Show when=move || !article.article.local>
                                     <input
                                         class="input"
@@ -104,7 +112,9 @@ pub fn ArticleActions() -> impl IntoView {
                                         "You can fork a remote article to the local instance. This is useful if the original
                                         instance is dead, or if there are disagreements how the article should be written."
                                     </p>
-                                </Show>
+                                </Show> <div class="mt-4">
+                                    <ReportButton article_id=article.article.id />
+                                </div>
                             </div>
                         }
                     })
@@ -1,8 +1,8 @@
 use crate::{
-    common::article::CreateArticleParams,
+    common::article::{CreateArticleParams, ARTICLE_LANGUAGES},
     frontend::{
         api::CLIENT,
-        components::article_editor::EditorView,
+        components::{article_editor::EditorView, title_suggestions::TitleSuggestions},
         utils::resources::{is_admin, site, DefaultResource},
     },
 };
@@ -21,6 +21,7 @@ pub fn CreateArticle() -> impl IntoView {
         trigger_resize: _,
     } = use_textarea_autosize(textarea_ref);
     let (summary, set_summary) = signal(String::new());
+    let (language, set_language) = signal(String::new());
     let (create_response, set_create_response) = signal(None::<()>);
     let (create_error, set_create_error) = signal(None::<String>);
     let (wait_for_response, set_wait_for_response) = signal(false);
@@ -31,10 +32,12 @@ pub fn CreateArticle() -> impl IntoView {
         let text = text.clone();
         let summary = summary.clone();
         async move {
+            let language = language.get_untracked();
             let params = CreateArticleParams {
                 title,
                 text,
                 summary,
+                language: (!language.is_empty()).then_some(language),
             };
             set_wait_for_response.update(|w| *w = true);
             let res = CLIENT.create_article(&params).await;
@@ -76,15 +79,30 @@ pub fn CreateArticle() -> impl IntoView {
                             type="text"
                             required
                             placeholder="Title"
+                            list="title-suggestions"
                             prop:disabled=move || wait_for_response.get()
-                            on:keyup=move |ev| {
+                            on:input=move |ev| {
                                 let val = event_target_value(&ev);
                                 set_title.update(|v| *v = val);
                             }
                         />
+                        <TitleSuggestions id="title-suggestions" query=title />
 
                         <EditorView textarea_ref content set_content />
 
+                        <select
+                            class="my-2 select select-bordered"
+                            on:change=move |ev| set_language.set(event_target_value(&ev))
+                        >
+                            <option value="">"Language (unspecified)"</option>
+                            {ARTICLE_LANGUAGES
+                                .iter()
+                                .map(|(code, name)| {
+                                    view! { <option value=*code>{*name}</option> }
+                                })
+                                .collect_view()}
+                        </select>
+
                         {move || {
                             create_error
                                 .get()
@@ -1,5 +1,8 @@
 use crate::frontend::{
-    components::article_nav::{ActiveTab, ArticleNav},
+    components::{
+        article_nav::{ActiveTab, ArticleNav},
+        report_button::ReportButton,
+    },
     pages::{article_edits_resource, article_resource},
     utils::formatting::{article_title, render_date_time, user_link},
 };
@@ -10,7 +13,7 @@ use leptos_router::hooks::use_params_map;
 #[component]
 pub fn EditDiff() -> impl IntoView {
     let params = use_params_map();
-    let article = article_resource();
+    let article = article_resource(false);
     let edits = article_edits_resource(article);
 
     view! {
@@ -28,11 +31,8 @@ pub fn EditDiff() -> impl IntoView {
                     render_date_time(edit.edit.published),
                 );
                 let pending = edit.edit.pending;
-                let title = format!(
-                    "Diff {} — {}",
-                    edit.edit.summary,
-                    article_title(&article.await.article),
-                );
+                let article = article.await.article;
+                let title = format!("Diff {} — {}", edit.edit.summary, article_title(&article));
                 view! {
                     <Title text=title />
                     <div class="flex w-full">
@@ -43,7 +43,10 @@ pub fn EditDiff() -> impl IntoView {
                             </span>
                         </Show>
                     </div>
-                    <p>"by " {user_link(&edit.creator)}</p>
+                    <p>
+                        "by " {user_link(&edit.creator)} " | "
+                        <ReportButton article_id=article.id edit_id=edit.edit.id />
+                    </p>
                     <div class="p-2 my-2 bg-gray-200 rounded">
                         <pre class="text-wrap">
                             <code>{edit.edit.diff.clone()}</code>
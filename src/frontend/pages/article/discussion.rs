@@ -1,6 +1,11 @@
 use crate::{
-    common::{comment::DbCommentView, newtypes::CommentId},
+    common::{
+        comment::{CommentSort, CommentTree, CommentTreeNode, DbCommentView, ListCommentsParams},
+        newtypes::CommentId,
+        pagination::PaginationCursor,
+    },
     frontend::{
+        api::CLIENT,
         components::{
             article_nav::{ActiveTab, ArticleNav},
             comment::CommentView,
@@ -10,26 +15,113 @@ use crate::{
     },
 };
 use leptos::prelude::*;
-use std::collections::HashMap;
 
 #[component]
 pub fn ArticleDiscussion() -> impl IntoView {
-    let article = article_resource();
+    let article = article_resource(false);
 
     let show_editor = signal(CommentId(-1));
+    let (sort, set_sort) = signal(CommentSort::default());
+    // Comments which were loaded with "load more", replaces the resource value once set
+    let (loaded, set_loaded) = signal(None::<CommentTree>);
+    let comments = Resource::new(
+        move || (article.get(), sort.get()),
+        move |(article, sort)| async move {
+            set_loaded.set(None);
+            let article_id = article?.article.id;
+            let params = ListCommentsParams {
+                article_id,
+                sort: Some(sort),
+                ..Default::default()
+            };
+            CLIENT.list_comments(&params).await
+        },
+    );
+    let tree = move || loaded.get().or_else(|| comments.get().flatten());
+
+    let load_more_action = Action::new(
+        move |(parent_id, cursor): &(Option<CommentId>, PaginationCursor)| {
+            let parent_id = *parent_id;
+            let cursor = cursor.clone();
+            async move {
+                let Some(mut current) = tree() else {
+                    return;
+                };
+                let params = ListCommentsParams {
+                    article_id: article.await.article.id,
+                    parent_id,
+                    sort: Some(sort.get_untracked()),
+                    cursor: Some(cursor),
+                    ..Default::default()
+                };
+                let Some(more) = CLIENT.list_comments(&params).await else {
+                    return;
+                };
+                match parent_id {
+                    None => {
+                        current.comments.extend(more.comments);
+                        current.next_cursor = more.next_cursor;
+                    }
+                    Some(parent_id) => {
+                        if let Some(parent) = find_node(&mut current.comments, parent_id) {
+                            parent.children.extend(more.comments);
+                            parent.more_replies = more.next_cursor;
+                        }
+                    }
+                }
+                set_loaded.set(Some(current));
+            }
+        },
+    );
 
     view! {
         <ArticleNav article=article active_tab=ActiveTab::Discussion />
         <Suspense fallback=|| view! { "Loading..." }>
             <CommentEditorView article=article />
+            <select
+                class="my-2 select select-bordered select-sm"
+                on:change=move |ev| {
+                    let sort = match event_target_value(&ev).as_str() {
+                        "Top" => CommentSort::Top,
+                        _ => CommentSort::New,
+                    };
+                    set_sort.set(sort);
+                }
+            >
+                <option value="New">New</option>
+                <option value="Top">Top</option>
+            </select>
             <div>
                 <For
-                    each=move || {
-                        article.get().map(|a| build_comments_tree(a.comments)).unwrap_or_default()
-                    }
-                    key=|comment| comment.comment.id
-                    children=move |comment: DbCommentView| {
-                        view! { <CommentView article comment show_editor /> }
+                    each=move || tree().map(flatten_tree).unwrap_or_default()
+                    key=|item| item.key()
+                    children=move |item: CommentItem| {
+                        match item {
+                            CommentItem::Comment(comment) => {
+                                view! { <CommentView article comment=*comment show_editor /> }.into_any()
+                            }
+                            CommentItem::LoadMore { parent_id, cursor, depth } => {
+                                let style_ = format!("margin-left: {}rem;", depth * 2);
+                                let label = if parent_id.is_some() {
+                                    "Load more replies"
+                                } else {
+                                    "Load more comments"
+                                };
+                                view! {
+                                    <div style=style_ class="py-2 text-xs">
+                                        <a
+                                            class="link"
+                                            on:click=move |_| {
+                                                load_more_action.dispatch((parent_id, cursor.clone()));
+                                            }
+                                        >
+                                            {label}
+                                        </a>
+                                    </div>
+                                }
+                                    .into_any()
+                            }
+                        }
                     }
                 />
             </div>
@@ -37,49 +129,64 @@ pub fn ArticleDiscussion() -> impl IntoView {
     }
 }
 
+/// Entry in the flat list which is rendered for the comment tree.
 #[derive(Clone)]
-struct CommentNode {
-    view: DbCommentView,
-    children: Vec<CommentNode>,
+enum CommentItem {
+    Comment(Box<DbCommentView>),
+    LoadMore {
+        parent_id: Option<CommentId>,
+        cursor: PaginationCursor,
+        depth: i32,
+    },
 }
 
-impl CommentNode {
-    fn new(view: DbCommentView) -> Self {
-        Self {
-            view,
-            children: vec![],
+impl CommentItem {
+    fn key(&self) -> String {
+        match self {
+            CommentItem::Comment(c) => c.comment.id.0.to_string(),
+            CommentItem::LoadMore {
+                parent_id, cursor, ..
+            } => format!("more-{parent_id:?}-{}", cursor.0),
         }
     }
-    /// Visit the tree depth-first to build flat array from tree.
-    fn flatten(self) -> Vec<DbCommentView> {
-        let mut res = vec![self.view];
-        for c in self.children {
-            res.append(&mut c.flatten());
+}
+
+/// Visit the tree depth-first to build flat array with correct order for rendering.
+fn flatten_tree(tree: CommentTree) -> Vec<CommentItem> {
+    fn flatten_level(
+        nodes: Vec<CommentTreeNode>,
+        parent_id: Option<CommentId>,
+        next_cursor: Option<PaginationCursor>,
+        depth: i32,
+        res: &mut Vec<CommentItem>,
+    ) {
+        for node in nodes {
+            let id = node.comment.comment.id;
+            let depth = node.comment.comment.depth;
+            res.push(CommentItem::Comment(Box::new(node.comment)));
+            flatten_level(node.children, Some(id), node.more_replies, depth + 1, res);
+        }
+        if let Some(cursor) = next_cursor {
+            res.push(CommentItem::LoadMore {
+                parent_id,
+                cursor,
+                depth,
+            });
         }
-        res
     }
+    let mut res = vec![];
+    flatten_level(tree.comments, None, tree.next_cursor, 0, &mut res);
+    res
 }
 
-fn build_comments_tree(comments: Vec<DbCommentView>) -> Vec<DbCommentView> {
-    // First create a map of CommentId -> CommentView
-    let mut map: HashMap<CommentId, CommentNode> = comments
-        .iter()
-        .map(|v| (v.comment.id, CommentNode::new(v.clone())))
-        .collect();
-
-    // Move top-level comments directly into tree vec. For comments having parent_id, move them
-    // `children` of respective parent. This preserves existing order.
-    let mut tree = Vec::<CommentNode>::new();
-    for view in comments {
-        let child = map.get(&view.comment.id).unwrap().clone();
-        if let Some(parent_id) = &view.comment.parent_id {
-            let parent = map.get_mut(parent_id).unwrap();
-            parent.children.push(child);
-        } else {
-            tree.push(child);
+fn find_node(nodes: &mut [CommentTreeNode], id: CommentId) -> Option<&mut CommentTreeNode> {
+    for node in nodes {
+        if node.comment.comment.id == id {
+            return Some(node);
+        }
+        if let Some(found) = find_node(&mut node.children, id) {
+            return Some(found);
         }
     }
-
-    // Now convert it back to flat array with correct order for rendering
-    tree.into_iter().flat_map(|t| t.flatten()).collect()
+    None
 }
@@ -30,7 +30,7 @@ const CONFLICT_MESSAGE: &str = "There was an edit conflict. Resolve it manually
 
 #[component]
 pub fn EditArticle() -> impl IntoView {
-    let article = article_resource();
+    let article = article_resource(false);
     let (edit_response, set_edit_response) = signal(EditResponse::None);
     let (edit_error, set_edit_error) = signal(None::<String>);
 
@@ -40,7 +40,7 @@ pub fn EditArticle() -> impl IntoView {
             let conflict_id = ConflictId(conflict_id.parse().unwrap());
             async move {
                 let conflict = CLIENT
-                    .notifications_list()
+                    .notifications_list_all()
                     .await
                     .unwrap()
                     .into_iter()
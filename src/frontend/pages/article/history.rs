@@ -1,26 +1,80 @@
-use crate::frontend::{
-    components::{
-        article_nav::{ActiveTab, ArticleNav},
-        edit_list::EditList,
+use crate::{
+    common::{article::GetEditList, MAIN_PAGE_NAME},
+    frontend::{
+        api::CLIENT,
+        components::{
+            article_nav::{ActiveTab, ArticleNav},
+            edit_list::EditList,
+            feed_links::FeedLinks,
+            pagination::{PaginationButtons, PaginationState},
+        },
+        pages::article_resource,
     },
-    pages::{article_edits_resource, article_resource},
 };
 use leptos::prelude::*;
+use leptos_router::hooks::use_params_map;
 
 #[component]
 pub fn ArticleHistory() -> impl IntoView {
-    let article = article_resource();
+    let article = article_resource(false);
+    let params = use_params_map();
+    let title = move || {
+        params
+            .get()
+            .get("title")
+            .unwrap_or(MAIN_PAGE_NAME.to_string())
+    };
+    let (hide_bots, set_hide_bots) = signal(false);
+    let pagination = PaginationState::default();
+    let edits = Resource::new(
+        move || (article.get(), hide_bots.get(), pagination.cursor()),
+        move |(_, hide_bots, cursor)| async move {
+            let params = GetEditList {
+                article_id: Some(article.await.article.id),
+                hide_bots: Some(hide_bots),
+                cursor,
+                ..Default::default()
+            };
+            CLIENT.list_edits(params).await.unwrap_or_default()
+        },
+    );
 
     view! {
         <ArticleNav article=article active_tab=ActiveTab::History />
+        {move || {
+            let title = title();
+            view! {
+                <FeedLinks
+                    title=format!("History of {}", title.replace('_', " "))
+                    path=format!("/feed/article/{title}")
+                />
+            }
+        }}
+        <label class="flex gap-2 items-center my-2">
+            <input
+                type="checkbox"
+                class="checkbox"
+                prop:checked=hide_bots
+                on:change=move |ev| {
+                    pagination.reset();
+                    set_hide_bots.set(event_target_checked(&ev));
+                }
+            />
+            "Hide bot edits"
+        </label>
         <Suspense fallback=|| {
             view! { "Loading..." }
         }>
             {move || {
-                article_edits_resource(article)
+                edits
                     .get()
                     .map(|edits| {
-                        view! { <EditList edits=edits for_article=true /> }
+                        // api returns newest edits first, but the list expects chronological order
+                        let items = edits.items.into_iter().rev().collect();
+                        view! {
+                            <EditList edits=items for_article=true />
+                            <PaginationButtons state=pagination next_cursor=edits.next_cursor />
+                        }
                     })
             }}
 
@@ -1,12 +1,13 @@
 use crate::{
-    common::article::ListArticlesParams,
+    common::{article::ListArticlesParams, utils::article_path},
     frontend::{
         api::CLIENT,
-        components::connect::ConnectView,
-        utils::{
-            formatting::{article_path, article_title},
-            resources::DefaultResource,
+        components::{
+            connect::ConnectView,
+            feed_links::FeedLinks,
+            pagination::{PaginationButtons, PaginationState},
         },
+        utils::{formatting::article_title, resources::DefaultResource},
     },
 };
 use leptos::prelude::*;
@@ -15,13 +16,18 @@ use leptos_meta::Title;
 #[component]
 pub fn ListArticles() -> impl IntoView {
     let (only_local, set_only_local) = signal(false);
+    let (hide_bots, set_hide_bots) = signal(false);
+    let pagination = PaginationState::default();
     let articles = Resource::new(
-        move || only_local.get(),
-        |only_local| async move {
+        move || (only_local.get(), hide_bots.get(), pagination.cursor()),
+        |(only_local, hide_bots, cursor)| async move {
             CLIENT
                 .list_articles(ListArticlesParams {
                     only_local: Some(only_local),
                     instance_id: None,
+                    hide_bots: Some(hide_bots),
+                    cursor,
+                    ..Default::default()
                 })
                 .await
         },
@@ -51,6 +57,7 @@ pub fn ListArticles() -> impl IntoView {
 
     view! {
         <Title text="Recently edited Articles" />
+        <FeedLinks title="New articles".to_string() path="/feed/new_articles".to_string() />
         <h1 class="my-4 font-serif text-4xl font-bold">"Recently edited Articles"</h1>
         <Suspense fallback=|| view! { "Loading..." }>
             <div class="divide-x">
@@ -59,6 +66,7 @@ pub fn ListArticles() -> impl IntoView {
                     value="Only Local"
                     class=move || only_local_class.get()
                     on:click=move |_| {
+                        pagination.reset();
                         set_only_local.set(true);
                     }
                 />
@@ -67,19 +75,33 @@ pub fn ListArticles() -> impl IntoView {
                     value="All"
                     class=move || all_class.get()
                     on:click=move |_| {
+                        pagination.reset();
                         set_only_local.set(false);
                     }
                 />
             </div>
+            <label class="flex gap-2 items-center mt-2">
+                <input
+                    type="checkbox"
+                    class="checkbox"
+                    prop:checked=hide_bots
+                    on:change=move |ev| {
+                        pagination.reset();
+                        set_hide_bots.set(event_target_checked(&ev));
+                    }
+                />
+                "Hide bot edits"
+            </label>
             <Show
                 when=move || {
-                    articles.get_default().unwrap_or_default().len() > 1 || only_local.get()
+                    let count = articles.get_default().unwrap_or_default().items.len();
+                    count > 1 || only_local.get() || pagination.cursor().is_some()
                 }
                 fallback=move || view! { <ConnectView res=articles /> }
             >
                 <ul class="my-4 list-none">
                     <For
-                        each=move || articles.get_default().unwrap_or_default()
+                        each=move || articles.get_default().unwrap_or_default().items
                         key=|article| article.id
                         let:article
                     >
@@ -95,6 +117,10 @@ pub fn ListArticles() -> impl IntoView {
                     </For>
 
                 </ul>
+                {move || {
+                    let next_cursor = articles.get_default().unwrap_or_default().next_cursor;
+                    view! { <PaginationButtons state=pagination next_cursor /> }
+                }}
             </Show>
         </Suspense>
     }
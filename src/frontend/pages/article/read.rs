@@ -1,16 +1,26 @@
-use crate::frontend::{
-    components::article_nav::{ActiveTab, ArticleNav},
-    markdown::render_article_markdown,
-    pages::article_resource,
+use crate::{
+    common::article::ListArticlesParams,
+    frontend::{
+        api::CLIENT,
+        components::article_nav::{ActiveTab, ArticleNav},
+        markdown::render_article_markdown,
+        pages::article_resource,
+        utils::formatting::article_link,
+    },
 };
 use leptos::prelude::*;
-use leptos_router::hooks::use_query_map;
+use leptos_router::hooks::{use_params_map, use_query_map};
+
+/// Number of articles listed as most viewed on the main page
+const MOST_VIEWED_COUNT: i64 = 5;
 
 #[component]
 pub fn ReadArticle() -> impl IntoView {
-    let article = article_resource();
+    let article = article_resource(true);
     let query = use_query_map();
     let edit_successful = query.get_untracked().get("edit_successful").is_some();
+    let params = use_params_map();
+    let is_main_page = move || params.get().get("title").is_none();
 
     view! {
         <ArticleNav article=article active_tab=ActiveTab::Read />
@@ -29,7 +39,9 @@ pub fn ReadArticle() -> impl IntoView {
                             ></div>
                         }
                     })
-            }} <Show when=move || edit_successful>
+            }} <Show when=is_main_page>
+                <MostViewed />
+            </Show> <Show when=move || edit_successful>
                 <div class="toast toast-center">
                     <div class="alert alert-success">Edit successful</div>
                 </div>
@@ -37,3 +49,41 @@ pub fn ReadArticle() -> impl IntoView {
         </Suspense>
     }
 }
+
+#[component]
+fn MostViewed() -> impl IntoView {
+    let articles = Resource::new(
+        || (),
+        |_| async move {
+            let params = ListArticlesParams {
+                most_viewed: Some(true),
+                limit: Some(MOST_VIEWED_COUNT),
+                ..Default::default()
+            };
+            CLIENT.list_articles(params).await.unwrap_or_default()
+        },
+    );
+    view! {
+        <Suspense>
+            {move || {
+                articles
+                    .get()
+                    .filter(|a| !a.items.is_empty())
+                    .map(|articles| {
+                        view! {
+                            <h2 class="mt-6 font-serif text-xl font-bold">
+                                "Most viewed this week"
+                            </h2>
+                            <ul class="list-disc list-inside">
+                                {articles
+                                    .items
+                                    .iter()
+                                    .map(|a| view! { <li>{article_link(a)}</li> })
+                                    .collect_view()}
+                            </ul>
+                        }
+                    })
+            }}
+        </Suspense>
+    }
+}
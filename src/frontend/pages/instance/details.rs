@@ -1,14 +1,16 @@
 use crate::{
-    common::{article::ListArticlesParams, instance::DbInstance, utils::http_protocol_str},
+    common::{
+        article::ListArticlesParams,
+        instance::DbInstance,
+        utils::{article_path, http_protocol_str},
+    },
     frontend::{
         api::CLIENT,
-        components::instance_follow_button::InstanceFollowButton,
-        utils::formatting::{
-            article_path,
-            article_title,
-            instance_title_with_domain,
-            instance_updated,
+        components::{
+            instance_follow_button::InstanceFollowButton,
+            pagination::{PaginationButtons, PaginationState},
         },
+        utils::formatting::{article_title, instance_title_with_domain, instance_updated},
     },
 };
 use leptos::prelude::*;
@@ -33,13 +35,15 @@ pub fn InstanceDetails() -> impl IntoView {
                 instance_profile
                     .get()
                     .map(|instance: DbInstance| {
+                        let pagination = PaginationState::default();
                         let articles = Resource::new(
-                            move || instance.id,
-                            |instance_id| async move {
+                            move || (instance.id, pagination.cursor()),
+                            |(instance_id, cursor)| async move {
                                 CLIENT
                                     .list_articles(ListArticlesParams {
-                                        only_local: None,
                                         instance_id: Some(instance_id),
+                                        cursor,
+                                        ..Default::default()
                                     })
                                     .await
                                     .unwrap()
@@ -65,7 +69,8 @@ pub fn InstanceDetails() -> impl IntoView {
                                             articles
                                                 .get()
                                                 .map(|a| {
-                                                    a.into_iter()
+                                                    a.items
+                                                        .into_iter()
                                                         .map(|a| {
                                                             view! {
                                                                 <li>
@@ -80,6 +85,20 @@ pub fn InstanceDetails() -> impl IntoView {
                                         }}
                                     </Suspense>
                                 </ul>
+                                <Suspense>
+                                    {move || {
+                                        articles
+                                            .get()
+                                            .map(|a| {
+                                                view! {
+                                                    <PaginationButtons
+                                                        state=pagination
+                                                        next_cursor=a.next_cursor
+                                                    />
+                                                }
+                                            })
+                                    }}
+                                </Suspense>
                             </div>
                         }
                     })
@@ -1,16 +1,29 @@
-use crate::frontend::{
-    api::CLIENT,
-    components::connect::ConnectView,
-    utils::formatting::{instance_title_with_domain, instance_updated},
+use crate::{
+    common::instance::ListInstancesParams,
+    frontend::{
+        api::CLIENT,
+        components::{
+            connect::ConnectView,
+            pagination::{PaginationButtons, PaginationState},
+        },
+        utils::formatting::{instance_title_with_domain, instance_updated},
+    },
 };
 use leptos::prelude::*;
 use leptos_meta::Title;
 
 #[component]
 pub fn ListInstances() -> impl IntoView {
+    let pagination = PaginationState::default();
     let instances = Resource::new(
-        move || (),
-        |_| async move { CLIENT.list_instances().await.unwrap() },
+        move || pagination.cursor(),
+        |cursor| async move {
+            let params = ListInstancesParams {
+                cursor,
+                ..Default::default()
+            };
+            CLIENT.list_instances(&params).await.unwrap()
+        },
     );
 
     view! {
@@ -18,7 +31,7 @@ pub fn ListInstances() -> impl IntoView {
         <h1 class="my-4 font-serif text-4xl font-bold">Instances</h1>
         <Suspense fallback=|| view! { "Loading..." }>
             <Show
-                when=move || { !instances.get().unwrap_or_default().is_empty() }
+                when=move || { !instances.get().unwrap_or_default().items.is_empty() }
                 fallback=move || view! { <ConnectView res=instances /> }
             >
                 <ul class="my-4 list-none">
@@ -26,7 +39,8 @@ pub fn ListInstances() -> impl IntoView {
                         instances
                             .get()
                             .map(|a| {
-                                a.into_iter()
+                                a.items
+                                    .into_iter()
                                     .map(|ref i| {
                                         view! {
                                             <li>
@@ -52,6 +66,10 @@ pub fn ListInstances() -> impl IntoView {
                     }}
 
                 </ul>
+                {move || {
+                    let next_cursor = instances.get().and_then(|i| i.next_cursor);
+                    view! { <PaginationButtons state=pagination next_cursor /> }
+                }}
             </Show>
         </Suspense>
     }
@@ -1,4 +1,8 @@
 pub mod details;
 pub mod list;
+pub mod modlog;
+pub mod recent_changes;
+pub mod reports;
 pub mod search;
 pub mod settings;
+pub mod stats;
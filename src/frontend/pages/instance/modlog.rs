@@ -0,0 +1,92 @@
+use crate::{
+    common::modlog::{GetModlogParams, ModlogAction, ModlogView},
+    frontend::{
+        api::CLIENT,
+        components::pagination::{PaginationButtons, PaginationState},
+        utils::formatting::{render_date_time, user_link},
+    },
+};
+use leptos::prelude::*;
+use leptos_meta::Title;
+
+#[component]
+pub fn Modlog() -> impl IntoView {
+    let pagination = PaginationState::default();
+    let modlog = Resource::new(
+        move || pagination.cursor(),
+        |cursor| async move {
+            let params = GetModlogParams {
+                cursor,
+                ..Default::default()
+            };
+            CLIENT.list_modlog(&params).await.unwrap_or_default()
+        },
+    );
+
+    view! {
+        <Title text="Moderation Log" />
+        <h1 class="my-4 font-serif text-4xl font-bold">"Moderation Log"</h1>
+        <Suspense fallback=|| view! { "Loading..." }>
+            <table class="table table-sm">
+                <thead>
+                    <tr>
+                        <th>"Time"</th>
+                        <th>"Moderator"</th>
+                        <th>"Action"</th>
+                        <th>"Target"</th>
+                        <th>"Reason"</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {move || {
+                        modlog
+                            .get()
+                            .unwrap_or_default()
+                            .items
+                            .into_iter()
+                            .map(|ModlogView { modlog, actor }| {
+                                view! {
+                                    <tr>
+                                        <td>{render_date_time(modlog.published)}</td>
+                                        <td>{actor.map(|a| user_link(&a))}</td>
+                                        <td>{action_label(modlog.action)}</td>
+                                        <td>{modlog.target_name}</td>
+                                        <td>{modlog.reason}</td>
+                                    </tr>
+                                }
+                            })
+                            .collect::<Vec<_>>()
+                    }}
+                </tbody>
+            </table>
+            {move || {
+                modlog
+                    .get()
+                    .map(|m| {
+                        view! { <PaginationButtons state=pagination next_cursor=m.next_cursor /> }
+                    })
+            }}
+        </Suspense>
+    }
+}
+
+fn action_label(action: ModlogAction) -> &'static str {
+    match action {
+        ModlogAction::ProtectArticle => "Protected article",
+        ModlogAction::UnprotectArticle => "Unprotected article",
+        ModlogAction::ApproveArticle => "Approved article",
+        ModlogAction::RejectArticle => "Rejected article",
+        ModlogAction::UpdateInstance => "Updated instance settings",
+        ModlogAction::BanUser => "Banned user",
+        ModlogAction::UnbanUser => "Unbanned user",
+        ModlogAction::DeleteUser => "Deleted user",
+        ModlogAction::ResolveReport => "Resolved report",
+        ModlogAction::DismissReport => "Dismissed report",
+        ModlogAction::RemoveComment => "Removed comment",
+        ModlogAction::RestoreComment => "Restored comment",
+        ModlogAction::UpdateUserRole => "Changed user role",
+        ModlogAction::BlockInstance => "Blocked instance",
+        ModlogAction::AllowInstance => "Allowed instance",
+        ModlogAction::RemoveFederationListEntry => "Removed instance from federation list",
+    }
+}
@@ -0,0 +1,144 @@
+use crate::{
+    common::{
+        article::{RecentChangeView, RecentChangesParams},
+        utils::extract_domain,
+    },
+    frontend::{
+        api::CLIENT,
+        components::{
+            feed_links::FeedLinks,
+            pagination::{PaginationButtons, PaginationState},
+        },
+        utils::formatting::{article_link, render_date_time, user_link},
+    },
+};
+use leptos::prelude::*;
+use leptos_meta::Title;
+
+#[component]
+pub fn RecentChanges() -> impl IntoView {
+    let params = RwSignal::new(RecentChangesParams::default());
+    let pagination = PaginationState::default();
+    let changes = Resource::new(
+        move || (params.get(), pagination.cursor()),
+        |(params, cursor)| async move {
+            let params = RecentChangesParams { cursor, ..params };
+            CLIENT.recent_changes(&params).await.unwrap_or_default()
+        },
+    );
+    let update = move |f: &dyn Fn(&mut RecentChangesParams)| {
+        pagination.reset();
+        params.update(|p| f(p));
+    };
+
+    view! {
+        <Title text="Recent changes" />
+        <FeedLinks title="Recent changes".to_string() path="/feed/recent_changes".to_string() />
+        <h1 class="my-4 font-serif text-4xl font-bold">"Recent changes"</h1>
+        <div class="flex flex-wrap gap-4 items-center">
+            <select
+                class="select select-bordered select-sm"
+                on:change=move |ev| {
+                    let local = match event_target_value(&ev).as_str() {
+                        "Local" => Some(true),
+                        "Remote" => Some(false),
+                        _ => None,
+                    };
+                    update(&|p| p.local = local);
+                }
+            >
+                <option value="All">"All articles"</option>
+                <option value="Local">"Local articles"</option>
+                <option value="Remote">"Remote articles"</option>
+            </select>
+            <select
+                class="select select-bordered select-sm"
+                on:change=move |ev| {
+                    let pending = match event_target_value(&ev).as_str() {
+                        "Pending" => Some(true),
+                        "Accepted" => Some(false),
+                        _ => None,
+                    };
+                    update(&|p| p.pending = pending);
+                }
+            >
+                <option value="All">"All edits"</option>
+                <option value="Pending">"Pending edits"</option>
+                <option value="Accepted">"Accepted edits"</option>
+            </select>
+            <label class="flex gap-2 items-center">
+                <input
+                    type="checkbox"
+                    class="checkbox"
+                    on:change=move |ev| {
+                        let hide_bots = Some(event_target_checked(&ev));
+                        update(&|p| p.hide_bots = hide_bots);
+                    }
+                />
+                "Hide bot edits"
+            </label>
+            <label class="flex gap-2 items-center">
+                <input
+                    type="checkbox"
+                    class="checkbox"
+                    on:change=move |ev| {
+                        let new_articles = Some(event_target_checked(&ev));
+                        update(&|p| p.new_articles = new_articles);
+                    }
+                />
+                "Only new articles"
+            </label>
+        </div>
+        <Suspense fallback=|| {
+            view! { "Loading..." }
+        }>
+            {move || {
+                changes
+                    .get()
+                    .map(|changes| {
+                        view! {
+                            <ul class="my-4">
+                                {changes.items.into_iter().map(recent_change).collect_view()}
+                            </ul>
+                            <PaginationButtons state=pagination next_cursor=changes.next_cursor />
+                        }
+                    })
+            }}
+        </Suspense>
+    }
+}
+
+fn recent_change(change: RecentChangeView) -> impl IntoView {
+    let path = format!(
+        "/article/{}@{}/diff/{}",
+        change.article.title,
+        extract_domain(&change.article.ap_id),
+        change.edit.hash.0,
+    );
+    let (delta, delta_class) = if change.byte_delta > 0 {
+        (format!("+{}", change.byte_delta), "text-success")
+    } else if change.byte_delta < 0 {
+        (change.byte_delta.to_string(), "text-error")
+    } else {
+        ("0".to_string(), "")
+    };
+    view! {
+        <li class="flex flex-wrap gap-2 items-baseline py-1 border-b border-base-300">
+            <span class="text-sm">{render_date_time(change.edit.published)}</span>
+            {article_link(&change.article)}
+            <span class=format!("font-mono text-sm {delta_class}")>"(" {delta} ")"</span>
+            <a class="link link-primary" href=path>
+                {change.edit.summary}
+            </a>
+            <span>"by " {user_link(&change.creator)} {change.creator.bot.then_some(" (bot)")}</span>
+            {change
+                .edit
+                .pending
+                .then(|| {
+                    view! {
+                        <span class="p-1 w-min rounded border-2 border-rose-300">Pending</span>
+                    }
+                })}
+        </li>
+    }
+}
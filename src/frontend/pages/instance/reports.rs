@@ -0,0 +1,122 @@
+use crate::{
+    common::report::{ListReportsParams, ReportStatus, ResolveReportParams},
+    frontend::{
+        api::CLIENT,
+        components::pagination::{PaginationButtons, PaginationState},
+        utils::formatting::{article_title, render_date_time, report_target_path, user_link},
+    },
+};
+use leptos::prelude::*;
+use leptos_meta::Title;
+
+#[component]
+pub fn Reports() -> impl IntoView {
+    let (include_closed, set_include_closed) = signal(false);
+    let pagination = PaginationState::default();
+    let reports = Resource::new(
+        move || (include_closed.get(), pagination.cursor()),
+        |(include_closed, cursor)| async move {
+            let params = ListReportsParams {
+                include_closed: Some(include_closed),
+                cursor,
+                ..Default::default()
+            };
+            CLIENT.list_reports(&params).await.unwrap_or_default()
+        },
+    );
+    let resolve_action = Action::new(move |params: &ResolveReportParams| {
+        let params = params.clone();
+        async move {
+            CLIENT.resolve_report(&params).await.ok();
+            reports.refetch();
+        }
+    });
+
+    view! {
+        <Title text="Reports" />
+        <h1 class="my-4 font-serif text-4xl font-bold">Reports</h1>
+        <label class="flex gap-2 items-center my-2">
+            <input
+                type="checkbox"
+                class="checkbox"
+                prop:checked=include_closed
+                on:change=move |ev| {
+                    pagination.reset();
+                    set_include_closed.set(event_target_checked(&ev));
+                }
+            />
+            "Show closed reports"
+        </label>
+        <Suspense fallback=|| view! { "Loading..." }>
+            <ul class="divide-y divide-solid">
+                {move || {
+                    reports
+                        .get()
+                        .unwrap_or_default()
+                        .items
+                        .into_iter()
+                        .map(|r| {
+                            let report_id = r.report.id;
+                            let is_open = r.report.status == ReportStatus::Open;
+                            let target = if r.comment.is_some() {
+                                "Comment on"
+                            } else if r.edit.is_some() {
+                                "Edit of"
+                            } else {
+                                "Article"
+                            };
+                            view! {
+                                <li class="py-2">
+                                    <a class="text-lg link" href=report_target_path(&r)>
+                                        {format!("{target} {}", article_title(&r.article))}
+                                    </a>
+                                    <p>{r.report.reason.clone()}</p>
+                                    <p class="text-xs">
+                                        {render_date_time(r.report.published)} " by "
+                                        {user_link(&r.creator)}
+                                        {(!is_open).then(|| format!(" ({:?})", r.report.status))}
+                                    </p>
+                                    <Show when=move || is_open>
+                                        <div class="mt-2 card-actions">
+                                            <button
+                                                class="btn btn-sm btn-outline"
+                                                on:click=move |_| {
+                                                    resolve_action
+                                                        .dispatch(ResolveReportParams {
+                                                            report_id,
+                                                            dismiss: false,
+                                                        });
+                                                }
+                                            >
+                                                Resolve
+                                            </button>
+                                            <button
+                                                class="btn btn-sm btn-outline"
+                                                on:click=move |_| {
+                                                    resolve_action
+                                                        .dispatch(ResolveReportParams {
+                                                            report_id,
+                                                            dismiss: true,
+                                                        });
+                                                }
+                                            >
+                                                Dismiss
+                                            </button>
+                                        </div>
+                                    </Show>
+                                </li>
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                }}
+            </ul>
+            {move || {
+                reports
+                    .get()
+                    .map(|r| {
+                        view! { <PaginationButtons state=pagination next_cursor=r.next_cursor /> }
+                    })
+            }}
+        </Suspense>
+    }
+}
@@ -1,28 +1,57 @@
 use crate::{
     common::{
-        article::{DbArticle, SearchArticleParams},
+        article::{
+            ArticleSuggestion,
+            DbArticle,
+            RemoteSearchResult,
+            SearchArticleParams,
+            SearchArticleResult,
+            SearchSuggestParams,
+        },
         instance::DbInstance,
+        newtypes::InstanceId,
+        pagination::PaginationCursor,
+        utils::article_path,
     },
     frontend::{
         api::CLIENT,
-        utils::formatting::{article_path, article_title},
+        components::pagination::{PaginationButtons, PaginationState},
+        utils::{
+            formatting::{
+                article_title,
+                search_result_path,
+                search_result_snippet,
+                search_result_title,
+            },
+            resources::is_logged_in,
+        },
     },
 };
+use chrono::NaiveDate;
 use leptos::prelude::*;
 use leptos_meta::Title;
-use leptos_router::hooks::use_query_map;
+use leptos_router::hooks::{use_navigate, use_query_map};
 use serde::{Deserialize, Serialize};
 use url::Url;
 
 #[derive(Default, Clone, Deserialize, Serialize, Debug)]
 struct SearchResults {
-    articles: Vec<DbArticle>,
+    articles: Vec<SearchArticleResult>,
+    /// Articles from federated search which are not fetched yet
+    remote: Vec<RemoteSearchResult>,
+    resolved_article: Option<DbArticle>,
     instance: Option<DbInstance>,
+    next_cursor: Option<PaginationCursor>,
+    /// Similar titles, only filled if there are no results
+    suggestions: Vec<ArticleSuggestion>,
 }
 
 impl SearchResults {
     pub fn is_empty(&self) -> bool {
-        self.articles.is_empty() && self.instance.is_none()
+        self.articles.is_empty()
+            && self.remote.is_empty()
+            && self.resolved_article.is_none()
+            && self.instance.is_none()
     }
 }
 
@@ -31,37 +60,77 @@ pub fn Search() -> impl IntoView {
     let params = use_query_map();
     let query = move || params.get().get("query").clone().unwrap_or_default();
     let (error, set_error) = signal(None::<String>);
-    let search_results = Resource::new(query, move |query| async move {
-        set_error.set(None);
-        let mut search_results = SearchResults::default();
-        let url = Url::parse(&query);
-        let search_data = SearchArticleParams { query };
-        let search = CLIENT.search(&search_data);
-
-        match search.await {
-            Ok(mut a) => search_results.articles.append(&mut a),
-            Err(e) => set_error.set(Some(e.to_string())),
+    // Query and cursor are ignored here, they are set when sending the request
+    let filters = RwSignal::new(SearchArticleParams::default());
+    let pagination = PaginationState::default();
+    // Start from the first page when the search query changes
+    Effect::new(move |_| {
+        query();
+        pagination.reset();
+    });
+    // Articles from federated search need to be fetched first
+    let open_remote_action = Action::new(move |ap_id: &Url| {
+        let ap_id = ap_id.clone();
+        async move {
+            match CLIENT.resolve_article(ap_id).await {
+                Ok(a) => use_navigate()(&article_path(&a.article), Default::default()),
+                Err(e) => set_error.set(Some(e.to_string())),
+            }
         }
+    });
+    let search_results = Resource::new(
+        move || (query(), filters.get(), pagination.cursor()),
+        move |(query, filters, cursor)| async move {
+            set_error.set(None);
+            let mut search_results = SearchResults::default();
+            // Only resolve federated objects on the first page
+            let url = Url::parse(&query).ok().filter(|_| cursor.is_none());
+            let search_data = SearchArticleParams {
+                query: query.clone(),
+                cursor,
+                ..filters
+            };
+            let search = CLIENT.search(&search_data);
 
-        // If its a valid url, also attempt to resolve as federation object
-        if let Ok(url) = url {
-            match CLIENT.resolve_article(url.clone()).await {
-                Ok(a) => search_results.articles.push(a.article),
+            match search.await {
+                Ok(res) => {
+                    search_results.articles = res.articles.items;
+                    search_results.next_cursor = res.articles.next_cursor;
+                    search_results.remote = res.remote;
+                }
                 Err(e) => set_error.set(Some(e.to_string())),
             }
-            match CLIENT.resolve_instance(url).await {
-                Ok(a) => search_results.instance = Some(a),
-                Err(e) => set_error.set(Some(e.to_string())),
+
+            // If its a valid url, also attempt to resolve as federation object
+            if let Some(url) = url {
+                match CLIENT.resolve_article(url.clone()).await {
+                    Ok(a) => search_results.resolved_article = Some(a.article),
+                    Err(e) => set_error.set(Some(e.to_string())),
+                }
+                match CLIENT.resolve_instance(url).await {
+                    Ok(a) => search_results.instance = Some(a),
+                    Err(e) => set_error.set(Some(e.to_string())),
+                }
             }
-        }
-        search_results
-    });
+
+            if search_results.is_empty() && !query.is_empty() {
+                let params = SearchSuggestParams {
+                    query,
+                    limit: Some(3),
+                };
+                search_results.suggestions =
+                    CLIENT.search_suggest(&params).await.unwrap_or_default();
+            }
+            search_results
+        },
+    );
 
     view! {
         <Title text=format!("Search - {}", query()) />
         <h1 class="flex-auto my-6 font-serif text-4xl font-bold grow">
             "Search results for " {query}
         </h1>
+        <SearchFilters filters pagination />
         <Suspense fallback=|| {
             view! { "Loading..." }
         }>
@@ -70,6 +139,8 @@ pub fn Search() -> impl IntoView {
                     .get()
                     .map(move |search_results| {
                         let is_empty = search_results.is_empty();
+                        let next_cursor = search_results.next_cursor.clone();
+                        let suggestions = search_results.suggestions.clone();
                         view! {
                             <Show
                                 when=move || !is_empty
@@ -81,9 +152,29 @@ pub fn Search() -> impl IntoView {
                                                 view! { <p style="color:red;">{err}</p> }
                                             })
                                     };
+                                    let did_you_mean = (!suggestions.is_empty())
+                                        .then(|| {
+                                            let links = suggestions
+                                                .iter()
+                                                .map(|s| {
+                                                    let title = s.title.replace('_', " ");
+                                                    view! {
+                                                        " "
+                                                        <a
+                                                            class="link"
+                                                            href=format!("/search?query={title}")
+                                                        >
+                                                            {title.clone()}
+                                                        </a>
+                                                    }
+                                                })
+                                                .collect_view();
+                                            view! { <p>"Did you mean:" {links}</p> }
+                                        });
                                     view! {
                                         {error_view}
                                         <p>No results found</p>
+                                        {did_you_mean}
                                     }
                                 }
                             >
@@ -104,10 +195,10 @@ pub fn Search() -> impl IntoView {
                                         ]
                                     } else {
                                         vec![]
-                                    }} // render articles from resolve/search
+                                    }} // render resolved article
                                     {search_results
-                                        .articles
-                                        .iter()
+                                        .resolved_article
+                                        .as_ref()
                                         .map(|a| {
                                             view! {
                                                 <li>
@@ -116,10 +207,65 @@ pub fn Search() -> impl IntoView {
                                                     </a>
                                                 </li>
                                             }
+                                        })} // render articles from search
+                                    {search_results
+                                        .articles
+                                        .iter()
+                                        .map(|a| {
+                                            view! {
+                                                <li class="mb-2">
+                                                    <a class="text-lg link" href=search_result_path(a)>
+                                                        {search_result_title(a)}
+                                                    </a>
+                                                    <span class="ml-2 badge badge-ghost badge-sm">
+                                                        {a.instance_domain.clone()}
+                                                    </span>
+                                                    <p class="text-sm">
+                                                        {search_result_snippet(&a.snippet)}
+                                                    </p>
+                                                </li>
+                                            }
                                         })
                                         .collect::<Vec<_>>()}
-
                                 </ul>
+                                <PaginationButtons state=pagination next_cursor=next_cursor.clone() />
+                                // render results from other instances
+                                {(!search_results.remote.is_empty())
+                                    .then(|| {
+                                        view! {
+                                            <h2 class="mt-4 text-xl font-bold">"Other instances"</h2>
+                                            <ul>
+                                                {search_results
+                                                    .remote
+                                                    .iter()
+                                                    .map(|a| {
+                                                        let ap_id = Url::parse(&a.ap_id.to_string()).ok();
+                                                        view! {
+                                                            <li class="mb-2">
+                                                                <a
+                                                                    class="text-lg link"
+                                                                    on:click=move |_| {
+                                                                        if let Some(ap_id) = ap_id.clone() {
+                                                                            open_remote_action.dispatch(ap_id);
+                                                                        }
+                                                                    }
+                                                                >
+                                                                    {format!(
+                                                                        "{}@{}",
+                                                                        a.title.replace('_', " "),
+                                                                        a.instance_domain,
+                                                                    )}
+                                                                </a>
+                                                                <p class="text-sm">
+                                                                    {search_result_snippet(&a.snippet)}
+                                                                </p>
+                                                            </li>
+                                                        }
+                                                    })
+                                                    .collect::<Vec<_>>()}
+                                            </ul>
+                                        }
+                                    })}
                             </Show>
                         }
                     })
@@ -128,3 +274,140 @@ pub fn Search() -> impl IntoView {
         </Suspense>
     }
 }
+
+#[component]
+fn SearchFilters(
+    filters: RwSignal<SearchArticleParams>,
+    pagination: PaginationState,
+) -> impl IntoView {
+    let instances = Resource::new(
+        || (),
+        |_| async move {
+            CLIENT
+                .list_instances(&Default::default())
+                .await
+                .map(|i| i.items)
+                .unwrap_or_default()
+        },
+    );
+    let update = move |f: &dyn Fn(&mut SearchArticleParams)| {
+        pagination.reset();
+        filters.update(|p| f(p));
+    };
+    // Dates from the input are inclusive, so the end of the range is the start of the next day
+    let parse_date = |value: String, next_day: bool| {
+        let date = NaiveDate::parse_from_str(&value, "%Y-%m-%d").ok()?;
+        let date = if next_day { date.succ_opt()? } else { date };
+        Some(date.and_hms_opt(0, 0, 0)?.and_utc())
+    };
+
+    view! {
+        <details class="mb-4">
+            <summary class="cursor-pointer">"Filters"</summary>
+            <div class="flex flex-wrap gap-4 items-end mt-2">
+                <label class="form-control">
+                    <span class="label-text">"Instance"</span>
+                    <select
+                        class="select select-bordered select-sm"
+                        on:change=move |ev| {
+                            let instance_id = event_target_value(&ev).parse().ok().map(InstanceId);
+                            update(&|p| p.instance_id = instance_id);
+                        }
+                    >
+                        <option value="">"All"</option>
+                        <Transition>
+                            {move || {
+                                instances
+                                    .get()
+                                    .unwrap_or_default()
+                                    .into_iter()
+                                    .map(|i| view! { <option value=i.id.0>{i.domain}</option> })
+                                    .collect_view()
+                            }}
+                        </Transition>
+                    </select>
+                </label>
+                <label class="form-control">
+                    <span class="label-text">"Edited by"</span>
+                    <input
+                        type="text"
+                        class="input input-bordered input-sm"
+                        placeholder="name or name@domain"
+                        on:change=move |ev| {
+                            let author = Some(event_target_value(&ev))
+                                .filter(|a| !a.trim().is_empty());
+                            update(&|p| p.author = author.clone());
+                        }
+                    />
+                </label>
+                <label class="form-control">
+                    <span class="label-text">"Edited from"</span>
+                    <input
+                        type="date"
+                        class="input input-bordered input-sm"
+                        on:change=move |ev| {
+                            let date = parse_date(event_target_value(&ev), false);
+                            update(&|p| p.edited_after = date);
+                        }
+                    />
+                </label>
+                <label class="form-control">
+                    <span class="label-text">"Edited until"</span>
+                    <input
+                        type="date"
+                        class="input input-bordered input-sm"
+                        on:change=move |ev| {
+                            let date = parse_date(event_target_value(&ev), true);
+                            update(&|p| p.edited_before = date);
+                        }
+                    />
+                </label>
+                <label class="form-control">
+                    <span class="label-text">"Origin"</span>
+                    <select
+                        class="select select-bordered select-sm"
+                        on:change=move |ev| {
+                            let local = match event_target_value(&ev).as_str() {
+                                "Local" => Some(true),
+                                "Remote" => Some(false),
+                                _ => None,
+                            };
+                            update(&|p| p.local = local);
+                        }
+                    >
+                        <option value="All">"All"</option>
+                        <option value="Local">"Local"</option>
+                        <option value="Remote">"Remote"</option>
+                    </select>
+                </label>
+            </div>
+            <div class="flex flex-wrap gap-4 mt-2">
+                <label class="flex gap-2 items-center">
+                    <input
+                        type="checkbox"
+                        class="checkbox"
+                        on:change=move |ev| {
+                            let protected = event_target_checked(&ev).then_some(true);
+                            update(&|p| p.protected = protected);
+                        }
+                    />
+                    "Only protected"
+                </label>
+                // only available after login
+                <Show when=is_logged_in>
+                    <label class="flex gap-2 items-center">
+                        <input
+                            type="checkbox"
+                            class="checkbox"
+                            on:change=move |ev| {
+                                let federated = Some(event_target_checked(&ev));
+                                update(&|p| p.federated = federated);
+                            }
+                        />
+                        "Search other instances"
+                    </label>
+                </Show>
+            </div>
+        </details>
+    }
+}
@@ -1,4 +1,13 @@
-use crate::{common::instance::UpdateInstanceParams, frontend::api::CLIENT};
+use crate::{
+    common::{
+        instance::{RemoveFederationListParams, UpdateFederationListParams, UpdateInstanceParams},
+        user::Permission,
+    },
+    frontend::{
+        api::CLIENT,
+        utils::resources::{has_permission, is_admin},
+    },
+};
 use leptos::prelude::*;
 use leptos_meta::Title;
 
@@ -34,79 +43,218 @@ pub fn InstanceSettings() -> impl IntoView {
     //       that completely breaks reactivity.
     view! {
         <Title text="Instance Settings" />
+        <h1 class="flex-auto my-6 font-serif text-4xl font-bold grow">"Instance Settings"</h1>
         <Suspense fallback=|| {
             view! { "Loading..." }
         }>
-            {move || Suspend::new(async move {
-                let instance = instance.await;
-                let (name, set_name) = signal(instance.instance.name.unwrap_or_default());
-                let (topic, set_topic) = signal(instance.instance.topic.unwrap_or_default());
-                view! {
-                    <h1 class="flex-auto my-6 font-serif text-4xl font-bold grow">
-                        "Instance Settings"
-                    </h1>
-                    {move || {
-                        submit_error
-                            .get()
-                            .map(|err| {
-                                view! { <p class="alert alert-error">{err}</p> }
-                            })
-                    }}
-                    <div class="flex flex-row mb-2">
-                        <label class="block w-20" for="name">
-                            Name
-                        </label>
-                        <input
-                            type="text"
-                            id="name"
-                            class="w-80 input input-secondary input-bordered"
-                            prop:value=name
-                            value=name
-                            on:change=move |ev| {
-                                let val = event_target_value(&ev);
-                                set_name.set(val);
-                            }
-                        />
-                    </div>
-                    <div class="flex flex-row mb-2">
-                        <label class="block w-20" for="topic">
-                            "Topic"
-                        </label>
-                        <input
-                            type="text"
-                            id="name"
-                            class="w-80 input input-secondary input-bordered"
-                            prop:value=topic
-                            value=topic
-                            on:change=move |ev| {
-                                let val = event_target_value(&ev);
-                                set_topic.set(val);
+            <Show when=is_admin>
+                {move || Suspend::new(async move {
+                    let instance = instance.await;
+                    let (name, set_name) = signal(instance.instance.name.unwrap_or_default());
+                    let (topic, set_topic) = signal(instance.instance.topic.unwrap_or_default());
+                    view! {
+                        {move || {
+                            submit_error
+                                .get()
+                                .map(|err| {
+                                    view! { <p class="alert alert-error">{err}</p> }
+                                })
+                        }}
+                        <div class="flex flex-row mb-2">
+                            <label class="block w-20" for="name">
+                                Name
+                            </label>
+                            <input
+                                type="text"
+                                id="name"
+                                class="w-80 input input-secondary input-bordered"
+                                prop:value=name
+                                value=name
+                                on:change=move |ev| {
+                                    let val = event_target_value(&ev);
+                                    set_name.set(val);
+                                }
+                            />
+                        </div>
+                        <div class="flex flex-row mb-2">
+                            <label class="block w-20" for="topic">
+                                "Topic"
+                            </label>
+                            <input
+                                type="text"
+                                id="name"
+                                class="w-80 input input-secondary input-bordered"
+                                prop:value=topic
+                                value=topic
+                                on:change=move |ev| {
+                                    let val = event_target_value(&ev);
+                                    set_topic.set(val);
+                                }
+                            />
+                        </div>
+                        <button
+                            class="btn btn-primary"
+                            on:click=move |_| {
+                                let form = UpdateInstanceParams {
+                                    name: Some(name.get()),
+                                    topic: Some(topic.get()),
+                                };
+                                submit_action.dispatch(form);
                             }
-                        />
-                    </div>
-                    <button
-                        class="btn btn-primary"
-                        on:click=move |_| {
-                            let form = UpdateInstanceParams {
-                                name: Some(name.get()),
-                                topic: Some(topic.get()),
-                            };
-                            submit_action.dispatch(form);
-                        }
-                    >
-                        Submit
-                    </button>
+                        >
+                            Submit
+                        </button>
 
-                    <Show when=move || saved.get()>
-                        <div class="toast">
-                            <div class="alert alert-info">
-                                <span>Saved!</span>
+                        <Show when=move || saved.get()>
+                            <div class="toast">
+                                <div class="alert alert-info">
+                                    <span>Saved!</span>
+                                </div>
                             </div>
-                        </div>
-                    </Show>
+                        </Show>
+                    }
+                })}
+            </Show>
+            <Show when=move || has_permission(Permission::ManageFederation)>
+                <FederationList />
+            </Show>
+        </Suspense>
+    }
+}
+
+/// Domains which are explicitly allowed or blocked for federation.
+#[component]
+fn FederationList() -> impl IntoView {
+    let (domain, set_domain) = signal(String::new());
+    let (reason, set_reason) = signal(String::new());
+    let (purge, set_purge) = signal(false);
+    let (message, set_message) = signal(None::<String>);
+    let entries = Resource::new(
+        || (),
+        |_| async move { CLIENT.list_federation_list().await.unwrap_or_default() },
+    );
+    let update_action = Action::new(move |params: &UpdateFederationListParams| {
+        let params = params.clone();
+        async move {
+            match CLIENT.update_federation_list(&params).await {
+                Ok(_) => {
+                    entries.refetch();
+                    set_message.set(None);
+                }
+                Err(err) => set_message.set(Some(err.to_string())),
+            }
+        }
+    });
+    let remove_action = Action::new(move |domain: &String| {
+        let params = RemoveFederationListParams {
+            domain: domain.clone(),
+        };
+        async move {
+            match CLIENT.remove_federation_list(&params).await {
+                Ok(_) => {
+                    entries.refetch();
+                    set_message.set(None);
                 }
-            })}
+                Err(err) => set_message.set(Some(err.to_string())),
+            }
+        }
+    });
+    let dispatch = move |blocked: bool| {
+        update_action.dispatch(UpdateFederationListParams {
+            domain: domain.get(),
+            blocked,
+            reason: Some(reason.get()),
+            purge: purge.get(),
+        });
+    };
 
-        </Suspense>
+    view! {
+        <h2 class="mt-6 mb-2 font-serif text-xl font-bold">"Federation"</h2>
+        <p class="mb-2">
+            "If any domain is allowed, federation with all other domains is blocked."
+        </p>
+        {move || {
+            message.get().map(|m| view! { <p class="alert alert-error">{m}</p> })
+        }}
+        <table class="table mb-4 table-sm">
+            <thead>
+                <tr>
+                    <th>"Domain"</th>
+                    <th>"Status"</th>
+                    <th>"Reason"</th>
+                    <th>"Added"</th>
+                    <th></th>
+                </tr>
+            </thead>
+            <tbody>
+                {move || {
+                    entries
+                        .get()
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|e| {
+                            let domain = e.domain.clone();
+                            view! {
+                                <tr>
+                                    <td>{e.domain}</td>
+                                    <td>{if e.blocked { "Blocked" } else { "Allowed" }}</td>
+                                    <td>{e.reason}</td>
+                                    <td>{e.published.format("%Y-%m-%d").to_string()}</td>
+                                    <td>
+                                        <button
+                                            class="btn btn-secondary btn-xs"
+                                            on:click=move |_| {
+                                                remove_action.dispatch(domain.clone());
+                                            }
+                                        >
+                                            Remove
+                                        </button>
+                                    </td>
+                                </tr>
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                }}
+            </tbody>
+        </table>
+        <div class="flex flex-row mb-2">
+            <label class="block w-40" for="federation-domain">
+                Domain
+            </label>
+            <input
+                type="text"
+                id="federation-domain"
+                class="w-80 input input-secondary input-bordered"
+                on:change=move |ev| set_domain.set(event_target_value(&ev))
+            />
+        </div>
+        <div class="flex flex-row mb-2">
+            <label class="block w-40" for="federation-reason">
+                Reason
+            </label>
+            <input
+                type="text"
+                id="federation-reason"
+                class="w-80 input input-secondary input-bordered"
+                on:change=move |ev| set_reason.set(event_target_value(&ev))
+            />
+        </div>
+        <div class="flex flex-row mb-2">
+            <label class="block w-40" for="federation-purge">
+                Delete content of blocked instance
+            </label>
+            <input
+                type="checkbox"
+                id="federation-purge"
+                class="checkbox"
+                on:change=move |ev| set_purge.set(event_target_checked(&ev))
+            />
+        </div>
+        <button class="mr-2 btn btn-primary btn-sm" on:click=move |_| dispatch(false)>
+            Allow
+        </button>
+        <button class="btn btn-error btn-sm" on:click=move |_| dispatch(true)>
+            Block
+        </button>
     }
 }
@@ -0,0 +1,113 @@
+use crate::{
+    common::instance::{GetInstanceStatsParams, InstanceStatsDay},
+    frontend::{api::CLIENT, utils::resources::is_admin},
+};
+use leptos::prelude::*;
+use leptos_meta::Title;
+
+type Column = (&'static str, fn(&InstanceStatsDay) -> i32);
+
+/// Name and getter of each column in the stats table
+const COLUMNS: [Column; 7] = [
+    ("Edits", |s| s.edits),
+    ("New articles", |s| s.articles),
+    ("New users", |s| s.new_users),
+    ("Comments", |s| s.comments),
+    ("Active editors", |s| s.active_editors),
+    ("Activities received", |s| s.activities_received),
+    ("Activities sent", |s| s.activities_sent),
+];
+
+#[component]
+pub fn InstanceStats() -> impl IntoView {
+    let (days, set_days) = signal(30);
+    let stats = Resource::new(
+        move || days.get(),
+        |days| async move {
+            let params = GetInstanceStatsParams { days: Some(days) };
+            CLIENT.instance_stats(&params).await.unwrap_or_default()
+        },
+    );
+
+    view! {
+        <Title text="Statistics" />
+        <h1 class="my-4 font-serif text-4xl font-bold">"Statistics"</h1>
+        <Show when=is_admin>
+            <select
+                class="select select-bordered select-sm"
+                on:change=move |ev| {
+                    if let Ok(days) = event_target_value(&ev).parse() {
+                        set_days.set(days);
+                    }
+                }
+            >
+                <option value="7">"Last 7 days"</option>
+                <option value="30" selected=true>
+                    "Last 30 days"
+                </option>
+                <option value="90">"Last 90 days"</option>
+                <option value="365">"Last year"</option>
+            </select>
+            <Suspense fallback=|| {
+                view! { "Loading..." }
+            }>{move || stats.get().map(stats_view)}</Suspense>
+        </Show>
+    }
+}
+
+fn stats_view(stats: Vec<InstanceStatsDay>) -> impl IntoView {
+    let totals = COLUMNS
+        .iter()
+        .map(|(name, value)| {
+            let total: i32 = stats.iter().map(value).sum();
+            view! {
+                <div class="stat">
+                    <div class="stat-title">{*name}</div>
+                    <div class="stat-value">{total}</div>
+                </div>
+            }
+        })
+        .collect_view();
+    // Used to scale the bars, so that the busiest day fills the whole width
+    let max_edits = stats
+        .iter()
+        .map(|s| s.edits)
+        .max()
+        .unwrap_or_default()
+        .max(1);
+    let rows = stats
+        .into_iter()
+        .rev()
+        .map(|s| {
+            let cells = COLUMNS
+                .iter()
+                .map(|(_, value)| view! { <td>{value(&s)}</td> })
+                .collect_view();
+            let width = format!("width: {}%", s.edits * 100 / max_edits);
+            view! {
+                <tr>
+                    <td>{s.day.to_string()}</td>
+                    <td class="w-32">
+                        <div class="h-2 rounded bg-primary" style=width></div>
+                    </td>
+                    {cells}
+                </tr>
+            }
+        })
+        .collect_view();
+    view! {
+        <div class="my-4 stats stats-vertical lg:stats-horizontal">{totals}</div>
+        <div class="overflow-x-auto">
+            <table class="table table-zebra table-sm">
+                <thead>
+                    <tr>
+                        <th>"Day (UTC)"</th>
+                        <th></th>
+                        {COLUMNS.iter().map(|(name, _)| view! { <th>{*name}</th> }).collect_view()}
+                    </tr>
+                </thead>
+                <tbody>{rows}</tbody>
+            </table>
+        </div>
+    }
+}
@@ -12,7 +12,9 @@ pub mod article;
 pub mod instance;
 pub mod user;
 
-fn article_resource() -> Resource<DbArticleView> {
+/// Article for the current route. Only the page which shows the article text should record a
+/// view, not the other tabs.
+fn article_resource(record_view: bool) -> Resource<DbArticleView> {
     let params = use_params_map();
     let title = move || params.get().get("title").clone();
     Resource::new(title, move |title| async move {
@@ -27,6 +29,7 @@ fn article_resource() -> Resource<DbArticleView> {
                 title: Some(title),
                 domain,
                 id: None,
+                record_view: Some(record_view),
             })
             .await
             .unwrap()
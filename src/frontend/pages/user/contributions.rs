@@ -0,0 +1,230 @@
+use crate::{
+    common::{
+        article::GetEditList,
+        newtypes::PersonId,
+        user::{UserContributionsParams, UserStats},
+        utils::article_path,
+    },
+    frontend::{
+        api::CLIENT,
+        components::{
+            edit_list::EditList,
+            pagination::{PaginationButtons, PaginationState},
+        },
+        markdown::render_comment_markdown,
+        utils::formatting::{article_link, render_date_time},
+    },
+};
+use leptos::prelude::*;
+
+#[derive(Clone, Copy, PartialEq)]
+enum ContributionsTab {
+    Edits,
+    Comments,
+    Articles,
+}
+
+/// Statistics and paginated lists of edits, comments and created articles of a user.
+#[component]
+pub fn UserContributions(person_id: PersonId) -> impl IntoView {
+    let stats = Resource::new(
+        move || person_id,
+        |person_id| async move { CLIENT.user_stats(person_id).await },
+    );
+    let (tab, set_tab) = signal(ContributionsTab::Edits);
+    let pagination = PaginationState::default();
+    let tab_button = move |label: &'static str, value: ContributionsTab| {
+        view! {
+            <button
+                role="tab"
+                class=move || if tab.get() == value { "tab tab-active" } else { "tab" }
+                on:click=move |_| {
+                    pagination.reset();
+                    set_tab.set(value);
+                }
+            >
+                {label}
+            </button>
+        }
+    };
+
+    view! {
+        <h2 class="font-serif text-xl font-bold">Contributions</h2>
+        <Suspense>{move || stats.get().flatten().map(stats_view)}</Suspense>
+        <div role="tablist" class="my-4 tabs tabs-bordered">
+            {tab_button("Edits", ContributionsTab::Edits)}
+            {tab_button("Comments", ContributionsTab::Comments)}
+            {tab_button("Articles", ContributionsTab::Articles)}
+        </div>
+        {move || match tab.get() {
+            ContributionsTab::Edits => view! { <UserEdits person_id pagination /> }.into_any(),
+            ContributionsTab::Comments => {
+                view! { <UserComments person_id pagination /> }.into_any()
+            }
+            ContributionsTab::Articles => {
+                view! { <UserArticles person_id pagination /> }.into_any()
+            }
+        }}
+    }
+}
+
+fn stats_view(stats: UserStats) -> impl IntoView {
+    let date = |d: Option<_>| d.map(render_date_time).unwrap_or("-".to_string());
+    view! {
+        <div class="my-2 stats stats-vertical md:stats-horizontal">
+            <div class="stat">
+                <div class="stat-title">"Edits"</div>
+                <div class="stat-value">{stats.edit_count}</div>
+                <div class="stat-desc">
+                    {format!("+{} / -{} bytes", stats.bytes_added, stats.bytes_removed)}
+                </div>
+            </div>
+            <div class="stat">
+                <div class="stat-title">"Articles created"</div>
+                <div class="stat-value">{stats.article_count}</div>
+            </div>
+            <div class="stat">
+                <div class="stat-title">"Comments"</div>
+                <div class="stat-value">{stats.comment_count}</div>
+            </div>
+        </div>
+        <p>"First edit: " {date(stats.first_edit)}</p>
+        <p>"Last edit: " {date(stats.last_edit)}</p>
+        {(!stats.top_articles.is_empty())
+            .then(|| {
+                let top_articles = stats
+                    .top_articles
+                    .iter()
+                    .map(|t| {
+                        view! {
+                            <li>{article_link(&t.article)} {format!(" ({} edits)", t.edit_count)}</li>
+                        }
+                    })
+                    .collect_view();
+                view! {
+                    <h3 class="mt-2 font-bold">"Most edited articles"</h3>
+                    <ul class="list-disc list-inside">{top_articles}</ul>
+                }
+            })}
+    }
+}
+
+#[component]
+fn UserEdits(person_id: PersonId, pagination: PaginationState) -> impl IntoView {
+    let edits = Resource::new(
+        move || pagination.cursor(),
+        move |cursor| async move {
+            let params = GetEditList {
+                person_id: Some(person_id),
+                cursor,
+                ..Default::default()
+            };
+            CLIENT.list_edits(params).await.unwrap_or_default()
+        },
+    );
+    view! {
+        <Suspense fallback=|| view! { "Loading..." }>
+            {move || {
+                edits
+                    .get()
+                    .map(|edits| {
+                        // api returns newest edits first, but the list expects chronological order
+                        let items = edits.items.into_iter().rev().collect();
+                        view! {
+                            <EditList edits=items for_article=false />
+                            <PaginationButtons state=pagination next_cursor=edits.next_cursor />
+                        }
+                    })
+            }}
+        </Suspense>
+    }
+}
+
+#[component]
+fn UserComments(person_id: PersonId, pagination: PaginationState) -> impl IntoView {
+    let comments = Resource::new(
+        move || pagination.cursor(),
+        move |cursor| async move {
+            let params = UserContributionsParams {
+                person_id,
+                cursor,
+                ..Default::default()
+            };
+            CLIENT.user_comments(&params).await.unwrap_or_default()
+        },
+    );
+    view! {
+        <Suspense fallback=|| view! { "Loading..." }>
+            {move || {
+                comments
+                    .get()
+                    .map(|comments| {
+                        let items = comments
+                            .items
+                            .into_iter()
+                            .map(|c| {
+                                let discussion = format!(
+                                    "{}/discussion",
+                                    article_path(&c.article),
+                                );
+                                view! {
+                                    <li class="m-2 card card-compact bg-base-100 card-bordered rounded-s">
+                                        <div class="card-body">
+                                            <div
+                                                class="max-w-full prose prose-slate"
+                                                inner_html=render_comment_markdown(&c.comment.content)
+                                            ></div>
+                                            <p>
+                                                {render_date_time(c.comment.published)} " on "
+                                                <a class="link" href=discussion>
+                                                    {c.article.title.replace('_', " ")}
+                                                </a>
+                                            </p>
+                                        </div>
+                                    </li>
+                                }
+                            })
+                            .collect_view();
+                        view! {
+                            <ul>{items}</ul>
+                            <PaginationButtons state=pagination next_cursor=comments.next_cursor />
+                        }
+                    })
+            }}
+        </Suspense>
+    }
+}
+
+#[component]
+fn UserArticles(person_id: PersonId, pagination: PaginationState) -> impl IntoView {
+    let articles = Resource::new(
+        move || pagination.cursor(),
+        move |cursor| async move {
+            let params = UserContributionsParams {
+                person_id,
+                cursor,
+                ..Default::default()
+            };
+            CLIENT.user_articles(&params).await.unwrap_or_default()
+        },
+    );
+    view! {
+        <Suspense fallback=|| view! { "Loading..." }>
+            {move || {
+                articles
+                    .get()
+                    .map(|articles| {
+                        let items = articles
+                            .items
+                            .iter()
+                            .map(|a| view! { <li>{article_link(a)}</li> })
+                            .collect_view();
+                        view! {
+                            <ul class="list-disc list-inside">{items}</ul>
+                            <PaginationButtons state=pagination next_cursor=articles.next_cursor />
+                        }
+                    })
+            }}
+        </Suspense>
+    }
+}
@@ -1,5 +1,13 @@
 use crate::{
-    common::user::UpdateUserParams,
+    common::user::{
+        CreateApiTokenParams,
+        DeleteAccountParams,
+        DeleteApiTokenParams,
+        DisableTotpParams,
+        EnableTotpParams,
+        TotpSetupResponse,
+        UpdateUserParams,
+    },
     frontend::{
         api::CLIENT,
         utils::resources::{site, DefaultResource},
@@ -7,6 +15,7 @@ use crate::{
 };
 use leptos::prelude::*;
 use leptos_meta::Title;
+use leptos_router::hooks::use_navigate;
 
 #[component]
 pub fn UserEditProfile() -> impl IntoView {
@@ -32,6 +41,24 @@ pub fn UserEditProfile() -> impl IntoView {
         }
     });
 
+    let delete_action = Action::new(move |params: &DeleteAccountParams| {
+        let params = params.clone();
+        async move {
+            let result = CLIENT.delete_account(&params).await;
+            match result {
+                Ok(_res) => {
+                    site().refetch();
+                    use_navigate()("/", Default::default());
+                }
+                Err(err) => {
+                    let msg = err.to_string();
+                    log::warn!("Unable to delete account: {msg}");
+                    set_submit_error.set(Some(msg));
+                }
+            }
+        }
+    });
+
     // TODO: It would make sense to use a table for the labels and inputs, but for some reason
     //       that completely breaks reactivity.
     view! {
@@ -98,6 +125,59 @@ pub fn UserEditProfile() -> impl IntoView {
                         Submit
                     </button>
 
+                    <TotpSettings totp_enabled=my_profile.local_user.totp_enabled />
+                    <ApiTokens />
+
+                    <h2 class="mt-8 mb-2 font-serif text-xl font-bold">Delete Account</h2>
+                    <p class="mb-2">
+                        "Your edits are kept and attributed to a placeholder user. This cannot be undone."
+                    </p>
+                    {
+                        let (password, set_password) = signal(String::new());
+                        let (delete_comments, set_delete_comments) = signal(false);
+                        view! {
+                            <div class="flex flex-row mb-2">
+                                <label class="block w-40" for="delete-password">
+                                    Password
+                                </label>
+                                <input
+                                    type="password"
+                                    id="delete-password"
+                                    class="w-80 input input-secondary input-bordered"
+                                    on:change=move |ev| {
+                                        set_password.set(event_target_value(&ev));
+                                    }
+                                />
+                            </div>
+                            <div class="flex flex-row mb-2">
+                                <label class="block w-40" for="delete-comments">
+                                    Delete comments
+                                </label>
+                                <input
+                                    type="checkbox"
+                                    id="delete-comments"
+                                    class="checkbox"
+                                    on:change=move |ev| {
+                                        set_delete_comments.set(event_target_checked(&ev));
+                                    }
+                                />
+                            </div>
+                            <OidcConfirmLinks />
+                            <button
+                                class="btn btn-error"
+                                on:click=move |_| {
+                                    let form = DeleteAccountParams {
+                                        password: password.get(),
+                                        delete_comments: delete_comments.get(),
+                                    };
+                                    delete_action.dispatch(form);
+                                }
+                            >
+                                Delete Account
+                            </button>
+                        }
+                    }
+
                     <Show when=move || saved.get()>
                         <div class="toast">
                             <div class="alert alert-info">
@@ -111,3 +191,325 @@ pub fn UserEditProfile() -> impl IntoView {
         </Suspense>
     }
 }
+
+/// Accounts which were created through OpenID Connect have no known password, so they can
+/// confirm by logging in with the provider again. Afterwards the password can be left empty.
+#[component]
+fn OidcConfirmLinks() -> impl IntoView {
+    view! {
+        <Suspense>
+            {move || {
+                let providers = site().with_default(|s| s.oidc_providers.clone());
+                (!providers.is_empty())
+                    .then(|| {
+                        view! {
+                            <p class="mb-2">
+                                "Or confirm by logging in again with "
+                                {providers
+                                    .into_iter()
+                                    .map(|p| {
+                                        let href = format!(
+                                            "/api/v1/account/oidc/login?provider={}",
+                                            p.name,
+                                        );
+                                        view! {
+                                            <a class="mr-2 link" href=href rel="external">
+                                                {p.display_name}
+                                            </a>
+                                        }
+                                    })
+                                    .collect::<Vec<_>>()}
+                            </p>
+                        }
+                    })
+            }}
+        </Suspense>
+    }
+}
+
+#[component]
+fn TotpSettings(totp_enabled: bool) -> impl IntoView {
+    let (enabled, set_enabled) = signal(totp_enabled);
+    let (setup, set_setup) = signal(None::<TotpSetupResponse>);
+    let (recovery_codes, set_recovery_codes) = signal(Vec::<String>::new());
+    let (totp_token, set_totp_token) = signal(String::new());
+    let (password, set_password) = signal(String::new());
+    let (error, set_error) = signal(None::<String>);
+
+    let setup_action = Action::new(move |_: &()| async move {
+        match CLIENT.setup_totp().await {
+            Ok(res) => {
+                set_setup.set(Some(res));
+                set_error.set(None);
+            }
+            Err(err) => set_error.set(Some(err.to_string())),
+        }
+    });
+    let enable_action = Action::new(move |params: &EnableTotpParams| {
+        let params = params.clone();
+        async move {
+            match CLIENT.enable_totp(&params).await {
+                Ok(res) => {
+                    set_recovery_codes.set(res.codes);
+                    set_setup.set(None);
+                    set_enabled.set(true);
+                    set_error.set(None);
+                    site().refetch();
+                }
+                Err(err) => set_error.set(Some(err.to_string())),
+            }
+        }
+    });
+    let disable_action = Action::new(move |params: &DisableTotpParams| {
+        let params = params.clone();
+        async move {
+            match CLIENT.disable_totp(&params).await {
+                Ok(_) => {
+                    set_enabled.set(false);
+                    set_recovery_codes.set(vec![]);
+                    set_error.set(None);
+                    site().refetch();
+                }
+                Err(err) => set_error.set(Some(err.to_string())),
+            }
+        }
+    });
+
+    let token_input = move || {
+        view! {
+            <div class="flex flex-row mb-2">
+                <label class="block w-40" for="totp-token">
+                    Code
+                </label>
+                <input
+                    type="text"
+                    id="totp-token"
+                    autocomplete="one-time-code"
+                    class="w-80 input input-secondary input-bordered"
+                    on:change=move |ev| set_totp_token.set(event_target_value(&ev))
+                />
+            </div>
+        }
+    };
+
+    view! {
+        <h2 class="mt-8 mb-2 font-serif text-xl font-bold">Two Factor Authentication</h2>
+        {move || {
+            error.get().map(|err| view! { <p class="alert alert-error">{err}</p> })
+        }}
+        <Show when=move || !recovery_codes.get().is_empty()>
+            <p class="mb-2">
+                "Store these recovery codes in a safe place. Each of them can be used once to login if you lose access to your authenticator app."
+            </p>
+            <ul class="mb-2 font-mono">
+                {move || {
+                    recovery_codes
+                        .get()
+                        .into_iter()
+                        .map(|c| view! { <li>{c}</li> })
+                        .collect::<Vec<_>>()
+                }}
+            </ul>
+        </Show>
+        {move || {
+            if enabled.get() {
+                view! {
+                    <div class="flex flex-row mb-2">
+                        <label class="block w-40" for="totp-password">
+                            Password
+                        </label>
+                        <input
+                            type="password"
+                            id="totp-password"
+                            class="w-80 input input-secondary input-bordered"
+                            on:change=move |ev| set_password.set(event_target_value(&ev))
+                        />
+                    </div>
+                    <OidcConfirmLinks />
+                    {token_input()}
+                    <button
+                        class="btn btn-secondary"
+                        on:click=move |_| {
+                            disable_action
+                                .dispatch(DisableTotpParams {
+                                    password: password.get(),
+                                    totp_token: totp_token.get(),
+                                });
+                        }
+                    >
+                        Disable
+                    </button>
+                }
+                    .into_any()
+            } else if let Some(setup) = setup.get() {
+                view! {
+                    <p class="mb-2">
+                        "Add this account to your authenticator app, then enter the generated code to confirm."
+                    </p>
+                    <p class="mb-2">
+                        <a class="link" href=setup.otpauth_url>
+                            "Open in authenticator app"
+                        </a>
+                        ", or enter the secret manually: "
+                        <span class="font-mono">{setup.secret}</span>
+                    </p>
+                    {token_input()}
+                    <button
+                        class="btn btn-primary"
+                        on:click=move |_| {
+                            enable_action
+                                .dispatch(EnableTotpParams {
+                                    totp_token: totp_token.get(),
+                                });
+                        }
+                    >
+                        Enable
+                    </button>
+                }
+                    .into_any()
+            } else {
+                view! {
+                    <button class="btn btn-secondary" on:click=move |_| { setup_action.dispatch(()); }>
+                        Setup
+                    </button>
+                }
+                    .into_any()
+            }
+        }}
+    }
+}
+
+#[component]
+fn ApiTokens() -> impl IntoView {
+    let api_tokens = Resource::new(|| (), |_| async move { CLIENT.list_api_tokens().await });
+    let (params, set_params) = signal(CreateApiTokenParams::default());
+    let (new_token, set_new_token) = signal(None::<String>);
+    let (error, set_error) = signal(None::<String>);
+
+    let create_action = Action::new(move |params: &CreateApiTokenParams| {
+        let params = params.clone();
+        async move {
+            match CLIENT.create_api_token(&params).await {
+                Ok(res) => {
+                    set_new_token.set(Some(res.token));
+                    set_params.set(CreateApiTokenParams::default());
+                    set_error.set(None);
+                    api_tokens.refetch();
+                }
+                Err(err) => set_error.set(Some(err.to_string())),
+            }
+        }
+    });
+    let delete_action = Action::new(move |params: &DeleteApiTokenParams| {
+        let params = params.clone();
+        async move {
+            match CLIENT.delete_api_token(&params).await {
+                Ok(_) => api_tokens.refetch(),
+                Err(err) => set_error.set(Some(err.to_string())),
+            }
+        }
+    });
+
+    let scope_checkbox = move |label: &'static str,
+                               get: fn(&CreateApiTokenParams) -> bool,
+                               set: fn(&mut CreateApiTokenParams, bool)| {
+        view! {
+            <label class="flex gap-1 items-center">
+                <input
+                    type="checkbox"
+                    class="checkbox"
+                    prop:checked=move || params.with(get)
+                    on:change=move |ev| {
+                        set_params.update(|p| set(p, event_target_checked(&ev)));
+                    }
+                />
+                {label}
+            </label>
+        }
+    };
+
+    view! {
+        <h2 class="mt-8 mb-2 font-serif text-xl font-bold">Api Tokens</h2>
+        <p class="mb-2">
+            "Tokens allow bots and scripts to use the api. Pass them in the "
+            <code>"Authorization: Bearer"</code>" header."
+        </p>
+        {move || {
+            error.get().map(|err| view! { <p class="alert alert-error">{err}</p> })
+        }}
+        {move || {
+            new_token
+                .get()
+                .map(|token| {
+                    view! {
+                        <p class="mb-2">
+                            "Copy the new token now, it will not be shown again: "
+                            <span class="font-mono">{token}</span>
+                        </p>
+                    }
+                })
+        }}
+        <Suspense>
+            <ul class="mb-2">
+                {move || {
+                    api_tokens
+                        .get()
+                        .flatten()
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|t| {
+                            let id = t.id;
+                            let last_used = t
+                                .last_used
+                                .map(|l| l.to_rfc3339())
+                                .unwrap_or_else(|| "never".to_string());
+                            view! {
+                                <li class="flex gap-2 items-center mb-1">
+                                    <span class="font-bold">{t.name}</span>
+                                    <span>"Last used: "{last_used}</span>
+                                    <button
+                                        class="btn btn-xs btn-error"
+                                        on:click=move |_| {
+                                            delete_action.dispatch(DeleteApiTokenParams { id });
+                                        }
+                                    >
+                                        Revoke
+                                    </button>
+                                </li>
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                }}
+            </ul>
+        </Suspense>
+        <div class="flex flex-row mb-2">
+            <label class="block w-40" for="api-token-name">
+                Name
+            </label>
+            <input
+                type="text"
+                id="api-token-name"
+                class="w-80 input input-secondary input-bordered"
+                prop:value=move || params.with(|p| p.name.clone())
+                on:change=move |ev| {
+                    set_params.update(|p| p.name = event_target_value(&ev));
+                }
+            />
+        </div>
+        <div class="flex flex-row gap-4 mb-2">
+            {scope_checkbox("Read", |p| p.scope_read, |p, v| p.scope_read = v)}
+            {scope_checkbox("Edit", |p| p.scope_edit, |p, v| p.scope_edit = v)}
+            {scope_checkbox("Comment", |p| p.scope_comment, |p, v| p.scope_comment = v)}
+            {scope_checkbox("Admin", |p| p.scope_admin, |p, v| p.scope_admin = v)}
+        </div>
+        <button
+            class="btn btn-secondary"
+            disabled=move || params.with(|p| p.name.is_empty())
+            on:click=move |_| {
+                create_action.dispatch(params.get());
+            }
+        >
+            Create Token
+        </button>
+    }
+}
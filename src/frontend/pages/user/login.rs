@@ -1,24 +1,31 @@
 use crate::{
-    common::user::LoginUserParams,
-    frontend::{api::CLIENT, components::credentials::*, utils::resources::site},
+    common::user::{LoginUserParams, OidcTotpParams, TOTP_REQUIRED_ERROR},
+    frontend::{
+        api::CLIENT,
+        components::credentials::*,
+        utils::resources::{site, DefaultResource},
+    },
 };
 use leptos::prelude::*;
 use leptos_meta::Title;
-use leptos_router::components::Redirect;
+use leptos_router::{components::Redirect, hooks::use_query_map};
 
 #[component]
 pub fn Login() -> impl IntoView {
     let (login_response, set_login_response) = signal(false);
     let (login_error, set_login_error) = signal(None::<String>);
     let (wait_for_response, set_wait_for_response) = signal(false);
+    // Username and password are kept here in case a second factor is required
+    let (credentials, set_credentials) = signal(None::<(String, String)>);
+    let (totp_token, set_totp_token) = signal(String::new());
+    // Set after OpenID Connect login, if the account has two factor authentication
+    let oidc_totp = use_query_map().with_untracked(|q| q.get("oidc_totp").is_some());
 
-    let login_action = Action::new(move |(email, password): &(String, String)| {
-        let username = email.to_string();
-        let password = password.to_string();
-        let params = LoginUserParams { username, password };
+    let login_action = Action::new(move |params: &LoginUserParams| {
+        let params = params.clone();
         async move {
             set_wait_for_response.update(|w| *w = true);
-            let result = CLIENT.login(params).await;
+            let result = CLIENT.login(params.clone()).await;
             set_wait_for_response.update(|w| *w = false);
             match result {
                 Ok(_res) => {
@@ -26,6 +33,10 @@ pub fn Login() -> impl IntoView {
                     set_login_response.set(true);
                     set_login_error.update(|e| *e = None);
                 }
+                Err(err) if err.to_string().contains(TOTP_REQUIRED_ERROR) => {
+                    set_credentials.set(Some((params.username, params.password)));
+                    set_login_error.update(|e| *e = None);
+                }
                 Err(err) => {
                     let msg = err.to_string();
                     log::warn!("Unable to login: {msg}");
@@ -34,6 +45,31 @@ pub fn Login() -> impl IntoView {
             }
         }
     });
+    let oidc_totp_action = Action::new(move |params: &OidcTotpParams| {
+        let params = params.clone();
+        async move {
+            set_wait_for_response.update(|w| *w = true);
+            let result = CLIENT.oidc_totp(&params).await;
+            set_wait_for_response.update(|w| *w = false);
+            match result {
+                Ok(_res) => {
+                    site().refetch();
+                    set_login_response.set(true);
+                    set_login_error.update(|e| *e = None);
+                }
+                Err(err) => set_login_error.update(|e| *e = Some(err.to_string())),
+            }
+        }
+    });
+    let credentials_action = Action::new(move |(email, password): &(String, String)| {
+        let params = LoginUserParams {
+            username: email.to_string(),
+            password: password.to_string(),
+            totp_token: None,
+        };
+        login_action.dispatch(params);
+        async {}
+    });
 
     let disabled = Signal::derive(move || wait_for_response.get());
 
@@ -43,13 +79,84 @@ pub fn Login() -> impl IntoView {
             when=move || login_response.get()
             fallback=move || {
                 view! {
-                    <CredentialsForm
-                        title="Login"
-                        action_label="Login"
-                        action=login_action
-                        error=login_error.into()
-                        disabled
-                    />
+                    <Show
+                        when=move || credentials.get().is_some() || oidc_totp
+                        fallback=move || {
+                            view! {
+                                <CredentialsForm
+                                    title="Login"
+                                    action_label="Login"
+                                    action=credentials_action
+                                    error=login_error.into()
+                                    disabled
+                                />
+                                <Suspense>
+                                    <div class="flex flex-col gap-2 mt-4 max-w-80">
+                                        {move || {
+                                            site()
+                                                .with_default(|s| s.oidc_providers.clone())
+                                                .into_iter()
+                                                .map(|p| {
+                                                    let href = format!(
+                                                        "/api/v1/account/oidc/login?provider={}",
+                                                        p.name,
+                                                    );
+                                                    view! {
+                                                        <a class="btn btn-secondary" href=href rel="external">
+                                                            "Login with "
+                                                            {p.display_name}
+                                                        </a>
+                                                    }
+                                                })
+                                                .collect::<Vec<_>>()
+                                        }}
+                                    </div>
+                                </Suspense>
+                            }
+                        }
+                    >
+                        <form class="form-control max-w-80" on:submit=|ev| ev.prevent_default()>
+                            <h1 class="my-4 font-serif text-4xl font-bold grow max-w-fit">
+                                Two Factor Authentication
+                            </h1>
+                            {move || {
+                                login_error
+                                    .get()
+                                    .map(|err| {
+                                        view! { <p class="alert alert-error">{err}</p> }
+                                    })
+                            }}
+                            <input
+                                type="text"
+                                class="input input-primary input-bordered"
+                                autocomplete="one-time-code"
+                                placeholder="Authenticator code or recovery code"
+                                on:keyup=move |ev| set_totp_token.set(event_target_value(&ev))
+                            />
+                            <div class="h-2"></div>
+                            <button
+                                class="btn btn-primary"
+                                disabled=move || disabled.get() || totp_token.get().is_empty()
+                                on:click=move |_| {
+                                    if let Some((username, password)) = credentials.get() {
+                                        login_action
+                                            .dispatch(LoginUserParams {
+                                                username,
+                                                password,
+                                                totp_token: Some(totp_token.get()),
+                                            });
+                                    } else if oidc_totp {
+                                        oidc_totp_action
+                                            .dispatch(OidcTotpParams {
+                                                totp_token: totp_token.get(),
+                                            });
+                                    }
+                                }
+                            >
+                                Login
+                            </button>
+                        </form>
+                    </Show>
                 }
             }
         >
@@ -1,3 +1,4 @@
+pub mod contributions;
 pub mod edit_profile;
 pub mod login;
 pub mod notifications;
@@ -1,8 +1,14 @@
 use crate::{
-    common::Notification,
+    common::{
+        report::ResolveReportParams,
+        utils::article_path,
+        ListNotificationsParams,
+        Notification,
+    },
     frontend::{
         api::CLIENT,
-        utils::formatting::{article_path, article_title},
+        components::pagination::{PaginationButtons, PaginationState},
+        utils::formatting::{article_title, report_target_path},
     },
 };
 use leptos::prelude::*;
@@ -10,9 +16,16 @@ use leptos_meta::Title;
 
 #[component]
 pub fn Notifications() -> impl IntoView {
+    let pagination = PaginationState::default();
     let notifications = Resource::new(
-        move || {},
-        |_| async move { CLIENT.notifications_list().await.unwrap_or_default() },
+        move || pagination.cursor(),
+        |cursor| async move {
+            let params = ListNotificationsParams {
+                cursor,
+                ..Default::default()
+            };
+            CLIENT.notifications_list(&params).await.unwrap_or_default()
+        },
     );
 
     view! {
@@ -24,10 +37,11 @@ pub fn Notifications() -> impl IntoView {
                     notifications
                         .get()
                         .map(|n| {
-                            n.into_iter()
+                            n.items
+                                .into_iter()
                                 .map(|ref notif| {
                                     use Notification::*;
-                                    let (my_style, link, title) = match notif {
+                                    let (my_style, link, title, labels) = match notif {
                                         EditConflict(c) => {
                                             (
                                                 "visibility: hidden",
@@ -37,6 +51,7 @@ pub fn Notifications() -> impl IntoView {
                                                     article_title(&c.article),
                                                     c.summary,
                                                 ),
+                                                ("Approve", "Reject"),
                                             )
                                         }
                                         ArticleApprovalRequired(a) => {
@@ -44,6 +59,19 @@ pub fn Notifications() -> impl IntoView {
                                                 "",
                                                 article_path(a),
                                                 format!("Approval required: {}", a.title),
+                                                ("Approve", "Reject"),
+                                            )
+                                        }
+                                        Report(r) => {
+                                            (
+                                                "",
+                                                report_target_path(r),
+                                                format!(
+                                                    "Report: {} - {}",
+                                                    article_title(&r.article),
+                                                    r.report.reason,
+                                                ),
+                                                ("Resolve", "Dismiss"),
                                             )
                                         }
                                     };
@@ -51,8 +79,18 @@ pub fn Notifications() -> impl IntoView {
                                     let click_approve = Action::new(move |_: &()| {
                                         let notif_ = notif_.clone();
                                         async move {
-                                            if let ArticleApprovalRequired(a) = notif_ {
-                                                CLIENT.approve_article(a.id, true).await.unwrap();
+                                            match notif_ {
+                                                ArticleApprovalRequired(a) => {
+                                                    CLIENT.approve_article(a.id, true).await.unwrap();
+                                                }
+                                                Report(r) => {
+                                                    let params = ResolveReportParams {
+                                                        report_id: r.report.id,
+                                                        dismiss: false,
+                                                    };
+                                                    CLIENT.resolve_report(&params).await.unwrap();
+                                                }
+                                                EditConflict(_) => {}
                                             }
                                             notifications.refetch();
                                         }
@@ -68,6 +106,13 @@ pub fn Notifications() -> impl IntoView {
                                                 ArticleApprovalRequired(a) => {
                                                     CLIENT.approve_article(a.id, false).await.unwrap();
                                                 }
+                                                Report(r) => {
+                                                    let params = ResolveReportParams {
+                                                        report_id: r.report.id,
+                                                        dismiss: true,
+                                                    };
+                                                    CLIENT.resolve_report(&params).await.unwrap();
+                                                }
                                             }
                                             notifications.refetch();
                                         }
@@ -85,7 +130,7 @@ pub fn Notifications() -> impl IntoView {
                                                         click_approve.dispatch(());
                                                     }
                                                 >
-                                                    Approve
+                                                    {labels.0}
                                                 </button>
                                                 <button
                                                     class="btn btn-sm btn-outline"
@@ -93,7 +138,7 @@ pub fn Notifications() -> impl IntoView {
                                                         click_reject.dispatch(());
                                                     }
                                                 >
-                                                    Reject
+                                                    {labels.1}
                                                 </button>
                                             </div>
                                         </li>
@@ -104,6 +149,13 @@ pub fn Notifications() -> impl IntoView {
                 }}
 
             </ul>
+            {move || {
+                notifications
+                    .get()
+                    .map(|n| {
+                        view! { <PaginationButtons state=pagination next_cursor=n.next_cursor /> }
+                    })
+            }}
         </Suspense>
     }
 }
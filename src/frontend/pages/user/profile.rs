@@ -1,15 +1,30 @@
 use crate::{
-    common::user::GetUserParams,
+    common::{
+        newtypes::PersonId,
+        user::{
+            BanUserParams,
+            DeleteUserParams,
+            GetUserParams,
+            Permission,
+            UpdateUserRoleParams,
+            UserRole,
+        },
+    },
     frontend::{
         api::CLIENT,
-        components::edit_list::EditList,
+        components::feed_links::FeedLinks,
         markdown::render_article_markdown,
-        utils::formatting::user_title,
+        pages::user::contributions::UserContributions,
+        utils::{
+            formatting::user_title,
+            resources::{has_permission, is_admin},
+        },
     },
 };
+use chrono::{Duration, Utc};
 use leptos::prelude::*;
 use leptos_meta::Title;
-use leptos_router::hooks::use_params_map;
+use leptos_router::hooks::{use_navigate, use_params_map};
 
 #[component]
 pub fn UserProfile() -> impl IntoView {
@@ -27,17 +42,26 @@ pub fn UserProfile() -> impl IntoView {
         CLIENT.get_user(params).await.unwrap()
     });
 
-    let edits = Resource::new(
-        move || user_profile.get(),
-        move |_| async move {
-            CLIENT
-                .get_person_edits(user_profile.await.id)
-                .await
-                .unwrap_or_default()
-        },
-    );
+    let delete_action = Action::new(move |params: &DeleteUserParams| {
+        let params = params.clone();
+        async move {
+            match CLIENT.delete_user(&params).await {
+                Ok(_) => use_navigate()("/", Default::default()),
+                Err(e) => set_error.set(Some(e.to_string())),
+            }
+        }
+    });
 
     view! {
+        {move || {
+            let name = name();
+            view! {
+                <FeedLinks
+                    title=format!("Contributions of {name}")
+                    path=format!("/feed/user/{name}")
+                />
+            }
+        }}
         {move || {
             error
                 .get()
@@ -50,24 +74,263 @@ pub fn UserProfile() -> impl IntoView {
             view! { "Loading..." }
         }>
             {move || Suspend::new(async move {
-                let edits = edits.await;
                 let person = user_profile.await;
+                let person_id = person.id;
+                let is_local = person.local;
+                let is_bot = person.bot;
+                let is_banned = person.is_banned();
+                let ban_reason = person.ban_reason.clone().unwrap_or_default();
                 view! {
                     <Title text=user_title(&person) />
                     <h1 class="flex-auto my-6 font-serif text-4xl font-bold grow">
                         {user_title(&person)}
                     </h1>
+                    <Show when=move || is_bot>
+                        <span class="p-1 mb-2 rounded border-2 border-sky-300">Bot</span>
+                    </Show>
+                    <Show when=move || is_banned>
+                        <p class="mb-2 alert alert-warning">"Banned: "{ban_reason.clone()}</p>
+                    </Show>
 
                     <div
                         class="mb-2 max-w-full prose prose-slate"
                         inner_html=render_article_markdown(&person.bio.unwrap_or_default())
                     ></div>
 
-                    <h2 class="font-serif text-xl font-bold">Edits</h2>
-                    <EditList edits=edits for_article=false />
+                    <UserContributions person_id />
+
+                    <Show when=move || has_permission(Permission::Ban)>
+                        <BanForm person_id is_banned />
+                    </Show>
+                    <Show when=move || is_admin() && is_local>
+                        <UserRoleForm person_id is_bot />
+                    </Show>
+                    <Show when=move || has_permission(Permission::ManageUsers) && is_local>
+                        <button
+                            class="mt-4 btn btn-error btn-sm"
+                            on:click=move |_| {
+                                delete_action
+                                    .dispatch(DeleteUserParams {
+                                        person_id,
+                                        delete_comments: false,
+                                    });
+                            }
+                        >
+                            Delete User
+                        </button>
+                    </Show>
                 }
             })}
 
         </Suspense>
     }
 }
+
+#[component]
+fn UserRoleForm(person_id: PersonId, is_bot: bool) -> impl IntoView {
+    let (params, set_params) = signal(UpdateUserRoleParams {
+        person_id,
+        bot: is_bot,
+        ..Default::default()
+    });
+    let (message, set_message) = signal(None::<String>);
+    let role = Resource::new(
+        move || person_id,
+        move |person_id| async move {
+            if let Some(local_user) = CLIENT.get_user_role(person_id).await {
+                set_params.set(UpdateUserRoleParams {
+                    person_id,
+                    role: local_user.role(),
+                    perm_protect_article: local_user.perm_protect_article,
+                    perm_approve_article: local_user.perm_approve_article,
+                    perm_remove_comment: local_user.perm_remove_comment,
+                    perm_ban: local_user.perm_ban,
+                    perm_manage_federation: local_user.perm_manage_federation,
+                    perm_manage_users: local_user.perm_manage_users,
+                    perm_handle_reports: local_user.perm_handle_reports,
+                    bot: is_bot,
+                });
+            }
+        },
+    );
+    let update_action = Action::new(move |params: &UpdateUserRoleParams| {
+        let params = params.clone();
+        async move {
+            match CLIENT.update_user_role(&params).await {
+                Ok(_) => set_message.set(Some("Role updated".to_string())),
+                Err(err) => set_message.set(Some(err.to_string())),
+            }
+        }
+    });
+
+    let role_option = move |label: &'static str, value: UserRole| {
+        view! {
+            <option value=label selected=move || params.with(|p| p.role == value)>
+                {label}
+            </option>
+        }
+    };
+    let permission_checkbox =
+        move |label: &'static str,
+              get: fn(&UpdateUserRoleParams) -> bool,
+              set: fn(&mut UpdateUserRoleParams, bool)| {
+            view! {
+                <label class="flex gap-1 items-center">
+                    <input
+                        type="checkbox"
+                        class="checkbox"
+                        prop:checked=move || params.with(get)
+                        on:change=move |ev| {
+                            set_params.update(|p| set(p, event_target_checked(&ev)));
+                        }
+                    />
+                    {label}
+                </label>
+            }
+        };
+
+    view! {
+        <Suspense>
+            {move || role.get().map(|_| ())}
+            <h2 class="mt-4 font-serif text-xl font-bold">Role</h2>
+            <select
+                class="mb-2 select select-bordered"
+                on:change=move |ev| {
+                    let role = match event_target_value(&ev).as_str() {
+                        "Admin" => UserRole::Admin,
+                        "Moderator" => UserRole::Moderator,
+                        _ => UserRole::User,
+                    };
+                    set_params.update(|p| p.role = role);
+                }
+            >
+                {role_option("User", UserRole::User)}
+                {role_option("Moderator", UserRole::Moderator)}
+                {role_option("Admin", UserRole::Admin)}
+            </select>
+            <p class="mb-2">"Additional permissions:"</p>
+            <div class="flex flex-row flex-wrap gap-4 mb-2">
+                {permission_checkbox(
+                    "Protect articles",
+                    |p| p.perm_protect_article,
+                    |p, v| p.perm_protect_article = v,
+                )}
+                {permission_checkbox(
+                    "Approve articles",
+                    |p| p.perm_approve_article,
+                    |p, v| p.perm_approve_article = v,
+                )}
+                {permission_checkbox(
+                    "Remove comments",
+                    |p| p.perm_remove_comment,
+                    |p, v| p.perm_remove_comment = v,
+                )}
+                {permission_checkbox("Ban users", |p| p.perm_ban, |p, v| p.perm_ban = v)}
+                {permission_checkbox(
+                    "Manage federation",
+                    |p| p.perm_manage_federation,
+                    |p, v| p.perm_manage_federation = v,
+                )}
+                {permission_checkbox(
+                    "Manage users",
+                    |p| p.perm_manage_users,
+                    |p, v| p.perm_manage_users = v,
+                )}
+                {permission_checkbox(
+                    "Handle reports",
+                    |p| p.perm_handle_reports,
+                    |p, v| p.perm_handle_reports = v,
+                )}
+                {permission_checkbox("Bot account", |p| p.bot, |p, v| p.bot = v)}
+            </div>
+            {move || message.get().map(|m| view! { <p class="mb-2">{m}</p> })}
+            <button
+                class="btn btn-secondary btn-sm"
+                on:click=move |_| {
+                    update_action.dispatch(params.get());
+                }
+            >
+                Save Role
+            </button>
+        </Suspense>
+    }
+}
+
+#[component]
+fn BanForm(person_id: PersonId, is_banned: bool) -> impl IntoView {
+    let (reason, set_reason) = signal(String::new());
+    let (days, set_days) = signal(None::<i64>);
+    let (remove_content, set_remove_content) = signal(false);
+    let (message, set_message) = signal(None::<String>);
+    let ban_action = Action::new(move |params: &BanUserParams| {
+        let params = params.clone();
+        async move {
+            match CLIENT.ban_user(&params).await {
+                Ok(_) if params.ban => set_message.set(Some("User banned".to_string())),
+                Ok(_) => set_message.set(Some("User unbanned".to_string())),
+                Err(err) => set_message.set(Some(err.to_string())),
+            }
+        }
+    });
+    let dispatch = move |ban: bool| {
+        ban_action.dispatch(BanUserParams {
+            person_id,
+            ban,
+            reason: Some(reason.get()),
+            expires: days.get().map(|d| Utc::now() + Duration::days(d)),
+            remove_content: remove_content.get(),
+        });
+    };
+
+    view! {
+        <h2 class="mt-4 font-serif text-xl font-bold">Ban</h2>
+        {move || message.get().map(|m| view! { <p class="mb-2">{m}</p> })}
+        <Show
+            when=move || !is_banned
+            fallback=move || {
+                view! {
+                    <button class="btn btn-secondary btn-sm" on:click=move |_| dispatch(false)>
+                        Unban User
+                    </button>
+                }
+            }
+        >
+            <div class="flex flex-row mb-2">
+                <label class="block w-40" for="ban-reason">
+                    Reason
+                </label>
+                <input
+                    type="text"
+                    id="ban-reason"
+                    class="w-80 input input-secondary input-bordered"
+                    on:change=move |ev| set_reason.set(event_target_value(&ev))
+                />
+            </div>
+            <div class="flex flex-row mb-2">
+                <label class="block w-40" for="ban-days">
+                    Days (empty for permanent)
+                </label>
+                <input
+                    type="number"
+                    id="ban-days"
+                    class="w-80 input input-secondary input-bordered"
+                    on:change=move |ev| set_days.set(event_target_value(&ev).parse().ok())
+                />
+            </div>
+            <div class="flex flex-row mb-2">
+                <label class="block w-40" for="ban-remove-content">
+                    Remove comments and revert edits
+                </label>
+                <input
+                    type="checkbox"
+                    id="ban-remove-content"
+                    class="checkbox"
+                    on:change=move |ev| set_remove_content.set(event_target_checked(&ev))
+                />
+            </div>
+            <button class="btn btn-error btn-sm" on:click=move |_| dispatch(true)>
+                Ban User
+            </button>
+        </Show>
+    }
+}
@@ -1,6 +1,10 @@
 use crate::{
     common::user::RegisterUserParams,
-    frontend::{api::CLIENT, components::credentials::*, utils::resources::site},
+    frontend::{
+        api::CLIENT,
+        components::credentials::*,
+        utils::resources::{site, DefaultResource},
+    },
 };
 use leptos::prelude::*;
 use leptos_meta::Title;
@@ -44,13 +48,29 @@ pub fn Register() -> impl IntoView {
             when=move || register_response.get()
             fallback=move || {
                 view! {
-                    <CredentialsForm
-                        title="Register"
-                        action_label="Register"
-                        action=register_action
-                        error=register_error.into()
-                        disabled
-                    />
+                    <Suspense>
+                        <Show
+                            when=move || site().with_default(|s| s.config.password_registration)
+                            fallback=|| {
+                                view! {
+                                    <p>
+                                        "Registration with password is disabled. Use one of the providers on the "
+                                        <a class="link" href="/login">
+                                            login page
+                                        </a> " instead."
+                                    </p>
+                                }
+                            }
+                        >
+                            <CredentialsForm
+                                title="Register"
+                                action_label="Register"
+                                action=register_action
+                                error=register_error.into()
+                                disabled
+                            />
+                        </Show>
+                    </Suspense>
                 }
             }
         >
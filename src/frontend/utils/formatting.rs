@@ -1,23 +1,24 @@
 use crate::common::{
-    article::DbArticle,
+    article::{DbArticle, SearchArticleResult},
     instance::DbInstance,
+    report::ReportView,
     user::DbPerson,
-    utils::extract_domain,
+    utils::{article_path, extract_domain},
 };
 use chrono::{DateTime, Local, Utc};
-use leptos::prelude::*;
+use leptos::{either::Either, prelude::*};
 use std::sync::OnceLock;
 use timeago::Formatter;
 
-pub fn article_path(article: &DbArticle) -> String {
-    if article.local {
-        format!("/article/{}", article.title)
+/// Link to the reported article, edit or comment
+pub fn report_target_path(report: &ReportView) -> String {
+    let article_path = article_path(&report.article);
+    if let Some(comment) = &report.comment {
+        format!("{article_path}/discussion#comment-{}", comment.id.0)
+    } else if let Some(edit) = &report.edit {
+        format!("{article_path}/diff/{}", edit.hash.0)
     } else {
-        format!(
-            "/article/{}@{}",
-            article.title,
-            extract_domain(&article.ap_id)
-        )
+        article_path
     }
 }
 
@@ -39,6 +40,45 @@ pub fn article_title(article: &DbArticle) -> String {
     }
 }
 
+pub fn search_result_path(result: &SearchArticleResult) -> String {
+    if result.local {
+        format!("/article/{}", result.title)
+    } else {
+        format!(
+            "/article/{}@{}",
+            result.title,
+            extract_domain(&result.ap_id)
+        )
+    }
+}
+
+pub fn search_result_title(result: &SearchArticleResult) -> String {
+    let title = result.title.replace('_', " ");
+    if result.local {
+        title
+    } else {
+        format!("{}@{}", title, extract_domain(&result.ap_id))
+    }
+}
+
+/// Render a search snippet, only the `<b>` tags which mark matching words are treated as html.
+pub fn search_result_snippet(snippet: &str) -> impl IntoView {
+    snippet
+        .split("<b>")
+        .enumerate()
+        .map(|(i, part)| {
+            if i == 0 {
+                return Either::Left(part.to_string());
+            }
+            let (matched, rest) = part.split_once("</b>").unwrap_or((part, ""));
+            Either::Right(view! {
+                <b>{matched.to_string()}</b>
+                {rest.to_string()}
+            })
+        })
+        .collect_view()
+}
+
 pub fn user_title(person: &DbPerson) -> String {
     let name = person
         .display_name
@@ -51,8 +91,8 @@ pub fn user_title(person: &DbPerson) -> String {
     }
 }
 
-pub fn user_link(person: &DbPerson) -> impl IntoView {
-    let creator_path = if person.local {
+pub fn user_path(person: &DbPerson) -> String {
+    if person.local {
         format!("/user/{}", person.username)
     } else {
         format!(
@@ -60,9 +100,12 @@ pub fn user_link(person: &DbPerson) -> impl IntoView {
             person.username,
             extract_domain(&person.ap_id)
         )
-    };
+    }
+}
+
+pub fn user_link(person: &DbPerson) -> impl IntoView {
     view! {
-        <a class="link" href=creator_path>
+        <a class="link" href=user_path(person)>
             {user_title(person)}
         </a>
     }
@@ -1,4 +1,4 @@
-use crate::common::instance::SiteView;
+use crate::common::{instance::SiteView, user::Permission};
 use leptos::prelude::*;
 
 pub fn site() -> Resource<SiteView> {
@@ -16,6 +16,14 @@ pub fn is_admin() -> bool {
             .unwrap_or(false)
     })
 }
+pub fn has_permission(permission: Permission) -> bool {
+    site().with_default(|site| {
+        site.my_profile
+            .as_ref()
+            .map(|p| p.local_user.has_permission(permission))
+            .unwrap_or(false)
+    })
+}
 pub trait DefaultResource<T> {
     fn with_default<O>(&self, f: impl FnOnce(&T) -> O) -> O;
     fn get_default(&self) -> T;
@@ -1,28 +1,56 @@
 #![expect(clippy::unwrap_used)]
 
 use anyhow::Result;
+use axum::{
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+    response::Redirect,
+    routing::{get, post},
+    Form,
+    Json,
+    Router,
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use diesel::{
+    r2d2::{ConnectionManager, Pool},
+    PgConnection,
+};
 use ibis::{
     backend::{
+        database::DbPool,
         start,
-        utils::config::{IbisConfig, IbisConfigDatabase, IbisConfigFederation},
+        utils::config::{IbisConfig, IbisConfigDatabase, IbisConfigFederation, IbisConfigOidc},
+    },
+    common::{
+        article::GetArticleParams,
+        comment::{CommentTreeNode, DbCommentView, ListCommentsParams},
+        instance::Options,
+        user::RegisterUserParams,
     },
-    common::{instance::Options, user::RegisterUserParams},
     frontend::api::ApiClient,
 };
+use jsonwebtoken::{encode, get_current_timestamp, EncodingKey, Header};
 use reqwest::ClientBuilder;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
 use std::{
+    collections::HashMap,
     env::current_dir,
     fs::{create_dir_all, remove_dir_all},
     ops::Deref,
     process::{Command, Stdio},
     sync::{
         atomic::{AtomicI32, Ordering},
+        Arc,
+        Mutex,
         Once,
     },
     thread::spawn,
 };
-use tokio::{join, sync::oneshot, task::JoinHandle};
+use tokio::{join, net::TcpListener, sync::oneshot, task::JoinHandle};
 use tracing::log::LevelFilter;
+use url::Url;
+use uuid::Uuid;
 
 pub struct TestData(pub IbisInstance, pub IbisInstance, pub IbisInstance);
 
@@ -59,10 +87,29 @@ impl TestData {
             j.join().unwrap();
         }
 
+        let oidc_issuer = start_oidc_mock().await;
         let (alpha, beta, gamma) = join!(
-            IbisInstance::start(alpha_db_path, port_alpha, "alpha", article_approval),
-            IbisInstance::start(beta_db_path, port_beta, "beta", article_approval),
-            IbisInstance::start(gamma_db_path, port_gamma, "gamma", article_approval)
+            IbisInstance::start(
+                alpha_db_path,
+                port_alpha,
+                "alpha",
+                article_approval,
+                oidc_issuer.clone()
+            ),
+            IbisInstance::start(
+                beta_db_path,
+                port_beta,
+                "beta",
+                article_approval,
+                oidc_issuer.clone()
+            ),
+            IbisInstance::start(
+                gamma_db_path,
+                port_gamma,
+                "gamma",
+                article_approval,
+                oidc_issuer
+            )
         );
 
         Self(alpha, beta, gamma)
@@ -108,7 +155,13 @@ impl IbisInstance {
         })
     }
 
-    async fn start(db_path: String, port: i32, username: &str, article_approval: bool) -> Self {
+    async fn start(
+        db_path: String,
+        port: i32,
+        username: &str,
+        article_approval: bool,
+        oidc_issuer: String,
+    ) -> Self {
         let connection_url = format!("postgresql://ibis:password@/ibis?host={db_path}");
         let hostname = format!("127.0.0.1:{port}");
         let domain = format!("localhost:{port}");
@@ -124,7 +177,15 @@ impl IbisInstance {
             options: Options {
                 registration_open: true,
                 article_approval,
+                ..Default::default()
             },
+            oidc: vec![IbisConfigOidc {
+                name: OIDC_MOCK_NAME.to_string(),
+                display_name: "Mock".to_string(),
+                issuer_url: oidc_issuer,
+                client_id: OIDC_MOCK_CLIENT_ID.to_string(),
+                client_secret: OIDC_MOCK_CLIENT_SECRET.to_string(),
+            }],
             ..Default::default()
         };
         let client = ClientBuilder::new().cookie_store(true).build().unwrap();
@@ -149,6 +210,37 @@ impl IbisInstance {
         }
     }
 
+    /// Read all comments of an article as flat list, newest first.
+    pub async fn get_comments(&self, params: GetArticleParams) -> Vec<DbCommentView> {
+        fn flatten(nodes: Vec<CommentTreeNode>, res: &mut Vec<DbCommentView>) {
+            for node in nodes {
+                res.push(node.comment);
+                flatten(node.children, res);
+            }
+        }
+        let article = self.get_article(params).await.unwrap();
+        let params = ListCommentsParams {
+            article_id: article.article.id,
+            limit: Some(100),
+            max_depth: Some(10),
+            ..Default::default()
+        };
+        let tree = self.list_comments(&params).await.unwrap();
+        let mut comments = vec![];
+        flatten(tree.comments, &mut comments);
+        comments.sort_by_key(|c| std::cmp::Reverse(c.comment.published));
+        comments
+    }
+
+    /// Direct database access, for backend functions which are not exposed over the api.
+    pub fn db_pool(&self) -> DbPool {
+        let connection_url = format!("postgresql://ibis:password@/ibis?host={}", self.db_path);
+        Pool::builder()
+            .max_size(1)
+            .build(ConnectionManager::<PgConnection>::new(connection_url))
+            .unwrap()
+    }
+
     fn stop(self) -> std::thread::JoinHandle<()> {
         self.db_handle.abort();
         Self::stop_internal(self.db_path)
@@ -175,3 +267,122 @@ impl Deref for IbisInstance {
 }
 
 pub const TEST_ARTICLE_DEFAULT_TEXT: &str = "some example text\n";
+
+pub const OIDC_MOCK_NAME: &str = "mock";
+const OIDC_MOCK_CLIENT_ID: &str = "ibis";
+const OIDC_MOCK_CLIENT_SECRET: &str = "secret";
+const OIDC_MOCK_ACCESS_TOKEN: &str = "mock-token";
+const OIDC_MOCK_SUBJECT: &str = "mock-user-1";
+pub const OIDC_MOCK_USERNAME: &str = "staff";
+
+#[derive(Clone)]
+struct OidcMockState {
+    issuer: String,
+    /// Nonce and PKCE challenge for each issued authorization code
+    codes: Arc<Mutex<HashMap<String, (String, String)>>>,
+}
+
+/// Minimal OpenID Connect provider which logs in the same user without any interaction.
+/// Returns the issuer url.
+async fn start_oidc_mock() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let issuer = format!("http://{}", listener.local_addr().unwrap());
+    let state = OidcMockState {
+        issuer: issuer.clone(),
+        codes: Default::default(),
+    };
+    let app = Router::new()
+        .route(
+            "/.well-known/openid-configuration",
+            get(oidc_mock_discovery),
+        )
+        .route("/authorize", get(oidc_mock_authorize))
+        .route("/token", post(oidc_mock_token))
+        .route("/userinfo", get(oidc_mock_userinfo))
+        .with_state(state);
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    issuer
+}
+
+async fn oidc_mock_discovery(
+    State(OidcMockState { issuer, .. }): State<OidcMockState>,
+) -> Json<Value> {
+    Json(json!({
+        "issuer": issuer,
+        "authorization_endpoint": format!("{issuer}/authorize"),
+        "token_endpoint": format!("{issuer}/token"),
+        "userinfo_endpoint": format!("{issuer}/userinfo"),
+    }))
+}
+
+async fn oidc_mock_authorize(
+    State(state): State<OidcMockState>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Redirect, StatusCode> {
+    if params.get("client_id").map(String::as_str) != Some(OIDC_MOCK_CLIENT_ID)
+        || params.get("response_type").map(String::as_str) != Some("code")
+        || params.get("code_challenge_method").map(String::as_str) != Some("S256")
+    {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let (Some(nonce), Some(challenge)) = (params.get("nonce"), params.get("code_challenge")) else {
+        return Err(StatusCode::BAD_REQUEST);
+    };
+    let code = format!("code-{}", Uuid::new_v4());
+    state
+        .codes
+        .lock()
+        .unwrap()
+        .insert(code.clone(), (nonce.clone(), challenge.clone()));
+    let mut redirect = Url::parse(&params["redirect_uri"]).unwrap();
+    redirect
+        .query_pairs_mut()
+        .append_pair("code", &code)
+        .append_pair("state", &params["state"]);
+    Ok(Redirect::to(redirect.as_str()))
+}
+
+async fn oidc_mock_token(
+    State(state): State<OidcMockState>,
+    Form(params): Form<HashMap<String, String>>,
+) -> Result<Json<Value>, StatusCode> {
+    let code = params.get("code").cloned().unwrap_or_default();
+    let Some((nonce, challenge)) = state.codes.lock().unwrap().remove(&code) else {
+        return Err(StatusCode::BAD_REQUEST);
+    };
+    let verifier = params.get("code_verifier").cloned().unwrap_or_default();
+    if URL_SAFE_NO_PAD.encode(Sha256::digest(verifier)) != challenge
+        || params.get("client_secret").map(String::as_str) != Some(OIDC_MOCK_CLIENT_SECRET)
+    {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let claims = json!({
+        "iss": state.issuer,
+        "sub": OIDC_MOCK_SUBJECT,
+        "aud": OIDC_MOCK_CLIENT_ID,
+        "exp": get_current_timestamp() + 60,
+        "nonce": nonce,
+    });
+    let id_token = encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(b"mock"),
+    )
+    .unwrap();
+    Ok(Json(json!({
+        "access_token": OIDC_MOCK_ACCESS_TOKEN,
+        "token_type": "Bearer",
+        "id_token": id_token,
+    })))
+}
+
+async fn oidc_mock_userinfo(headers: HeaderMap) -> Result<Json<Value>, StatusCode> {
+    let expected = format!("Bearer {OIDC_MOCK_ACCESS_TOKEN}");
+    if headers.get("authorization").and_then(|h| h.to_str().ok()) != Some(&expected) {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+    Ok(Json(json!({
+        "sub": OIDC_MOCK_SUBJECT,
+        "preferred_username": OIDC_MOCK_USERNAME,
+    })))
+}
@@ -2,28 +2,76 @@
 
 mod common;
 
-use crate::common::{TestData, TEST_ARTICLE_DEFAULT_TEXT};
+use crate::common::{TestData, OIDC_MOCK_NAME, OIDC_MOCK_USERNAME, TEST_ARTICLE_DEFAULT_TEXT};
 use anyhow::Result;
-use ibis::common::{
-    article::{
-        CreateArticleParams,
-        DbArticleView,
-        EditArticleParams,
-        ForkArticleParams,
-        GetArticleParams,
-        ListArticlesParams,
-        ProtectArticleParams,
-        SearchArticleParams,
+use ibis::{
+    backend::utils::config::IbisConfig,
+    common::{
+        article::{
+            CreateArticleParams,
+            DbArticle,
+            DbArticleView,
+            EditArticleParams,
+            ForkArticleParams,
+            GetArticleParams,
+            GetEditList,
+            ListArticlesParams,
+            ProtectArticleParams,
+            RecentChangesParams,
+            SearchArticleParams,
+            SearchSuggestParams,
+        },
+        comment::{
+            CreateCommentParams,
+            EditCommentParams,
+            ListCommentsParams,
+            RemoveCommentParams,
+            VoteCommentParams,
+        },
+        instance::{
+            DbFederationListEntry,
+            FollowInstanceParams,
+            GetInstanceStatsParams,
+            ListInstancesParams,
+            RemoveFederationListParams,
+            SiteView,
+            UpdateFederationListParams,
+            UpdateInstanceParams,
+        },
+        modlog::{GetModlogParams, ModlogAction},
+        newtypes::ConflictId,
+        pagination::PaginationCursor,
+        report::{CreateReportParams, ListReportsParams, ReportStatus, ResolveReportParams},
+        user::{
+            BanUserParams,
+            CreateApiTokenParams,
+            DeleteAccountParams,
+            DeleteApiTokenParams,
+            DeleteUserParams,
+            DisableTotpParams,
+            EnableTotpParams,
+            GetUserParams,
+            LoginUserParams,
+            OidcTotpParams,
+            RegisterUserParams,
+            UpdateUserParams,
+            UpdateUserRoleParams,
+            UserContributionsParams,
+            UserRole,
+            TOTP_REQUIRED_ERROR,
+        },
+        utils::extract_domain,
+        ListNotificationsParams,
+        Notification,
     },
-    comment::{CreateCommentParams, EditCommentParams},
-    user::{GetUserParams, LoginUserParams, RegisterUserParams},
-    utils::extract_domain,
-    Notification,
+    frontend::api::ApiClient,
 };
 use pretty_assertions::{assert_eq, assert_ne};
+use reqwest::{redirect::Policy, ClientBuilder, Method, StatusCode};
 use retry_future::{LinearRetryStrategy, RetryFuture, RetryPolicy};
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::time::sleep;
+use totp_rs::TOTP;
 use url::Url;
 
 #[tokio::test]
@@ -36,6 +84,7 @@ async fn test_create_read_and_edit_local_article() -> Result<()> {
         title: "Manu Chao".to_string(),
         text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
         summary: "create article".to_string(),
+        language: None,
     };
     let create_res = alpha.create_article(&create_params).await.unwrap();
     assert_eq!(TITLE, create_res.article.title);
@@ -46,6 +95,7 @@ async fn test_create_read_and_edit_local_article() -> Result<()> {
         title: Some(create_res.article.title.clone()),
         domain: None,
         id: None,
+        record_view: None,
     };
     let get_res = alpha.get_article(get_article_data.clone()).await.unwrap();
     assert_eq!(TITLE, get_res.article.title);
@@ -75,18 +125,20 @@ async fn test_create_read_and_edit_local_article() -> Result<()> {
 
     let search_params = SearchArticleParams {
         query: create_params.title.clone(),
+        ..Default::default()
     };
-    let search_res = alpha.search(&search_params).await.unwrap();
+    let search_res = alpha.search(&search_params).await.unwrap().articles.items;
     assert_eq!(1, search_res.len());
-    assert_eq!(edit_res.article, search_res[0]);
+    assert_eq!(edit_res.article.id, search_res[0].id);
 
     let list_articles = alpha
         .list_articles(ListArticlesParams {
             only_local: Some(false),
-            instance_id: None,
+            ..Default::default()
         })
         .await
-        .unwrap();
+        .unwrap()
+        .items;
     assert_eq!(2, list_articles.len());
     assert_eq!(edit_res.article, list_articles[0]);
 
@@ -102,6 +154,7 @@ async fn test_create_duplicate_article() -> Result<()> {
         title: "Manu_Chao".to_string(),
         text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
         summary: "create article".to_string(),
+        language: None,
     };
     let create_res = alpha.create_article(&create_params).await.unwrap();
     assert_eq!(create_params.title, create_res.article.title);
@@ -149,6 +202,7 @@ async fn test_synchronize_articles() -> Result<()> {
         title: "Manu_Chao".to_string(),
         text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
         summary: "create article".to_string(),
+        language: None,
     };
     let create_res = alpha.create_article(&create_params).await.unwrap();
     assert_eq!(create_params.title, create_res.article.title);
@@ -194,6 +248,7 @@ async fn test_synchronize_articles() -> Result<()> {
                 title: Some(create_res.article.title.clone()),
                 domain: Some(instance.domain.clone()),
                 id: None,
+                record_view: None,
             };
             let res = beta.get_article(get_article_data).await;
             match res {
@@ -231,6 +286,7 @@ async fn test_edit_local_article() -> Result<()> {
         title: "Manu_Chao".to_string(),
         text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
         summary: "create article".to_string(),
+        language: None,
     };
     let create_res = beta.create_article(&create_params).await.unwrap();
     assert_eq!(create_params.title, create_res.article.title);
@@ -241,6 +297,7 @@ async fn test_edit_local_article() -> Result<()> {
         title: Some(create_res.article.title.to_string()),
         domain: Some(beta_instance.domain),
         id: None,
+        record_view: None,
     };
     let get_res = alpha.get_article(get_article_data.clone()).await.unwrap();
     let edits = alpha.get_article_edits(get_res.article.id).await.unwrap();
@@ -298,6 +355,7 @@ async fn test_edit_remote_article() -> Result<()> {
         title: "Manu_Chao".to_string(),
         text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
         summary: "create article".to_string(),
+        language: None,
     };
     let create_res = beta.create_article(&create_params).await.unwrap();
     assert_eq!(&create_params.title, &create_res.article.title);
@@ -308,6 +366,7 @@ async fn test_edit_remote_article() -> Result<()> {
         title: Some(create_res.article.title.to_string()),
         domain: Some(beta_id_on_alpha.domain),
         id: None,
+        record_view: None,
     };
     let get_res = alpha
         .get_article(get_article_data_alpha.clone())
@@ -322,6 +381,7 @@ async fn test_edit_remote_article() -> Result<()> {
         title: Some(create_res.article.title.to_string()),
         domain: Some(beta_id_on_gamma.domain),
         id: None,
+        record_view: None,
     };
     let get_res = gamma
         .get_article(get_article_data_gamma.clone())
@@ -376,6 +436,7 @@ async fn test_local_edit_conflict() -> Result<()> {
         title: "Manu_Chao".to_string(),
         text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
         summary: "create article".to_string(),
+        language: None,
     };
     let create_res = alpha.create_article(&create_params).await.unwrap();
     assert_eq!(create_params.title, create_res.article.title);
@@ -408,7 +469,11 @@ async fn test_local_edit_conflict() -> Result<()> {
     let edit_res = alpha.edit_article(&edit_params).await.unwrap().unwrap();
     assert_eq!("<<<<<<< ours\nIpsum Lorem\n||||||| original\nsome example text\n=======\nLorem Ipsum\n>>>>>>> theirs\n", edit_res.three_way_merge);
 
-    let notifications = alpha.notifications_list().await.unwrap();
+    let notifications = alpha
+        .notifications_list(&Default::default())
+        .await
+        .unwrap()
+        .items;
     assert_eq!(1, notifications.len());
     let Notification::EditConflict(conflict) = &notifications[0] else {
         panic!()
@@ -447,6 +512,7 @@ async fn test_federated_edit_conflict() -> Result<()> {
         title: "Manu_Chao".to_string(),
         text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
         summary: "create article".to_string(),
+        language: None,
     };
     let create_res = beta.create_article(&create_params).await.unwrap();
     let beta_edits = beta.get_article_edits(create_res.article.id).await.unwrap();
@@ -465,6 +531,7 @@ async fn test_federated_edit_conflict() -> Result<()> {
         title: Some(create_params.title.to_string()),
         domain: Some(beta_id_on_alpha.domain),
         id: None,
+        record_view: None,
     };
     let get_res = alpha.get_article(get_article_data).await.unwrap();
     let alpha_edits = alpha.get_article_edits(get_res.article.id).await.unwrap();
@@ -511,7 +578,11 @@ async fn test_federated_edit_conflict() -> Result<()> {
     assert!(!edit_res.article.local);
 
     assert_eq!(1, gamma.notifications_count().await.unwrap());
-    let notifications = gamma.notifications_list().await.unwrap();
+    let notifications = gamma
+        .notifications_list(&Default::default())
+        .await
+        .unwrap()
+        .items;
     assert_eq!(1, notifications.len());
     let Notification::EditConflict(conflict) = &notifications[0] else {
         panic!()
@@ -535,7 +606,11 @@ async fn test_federated_edit_conflict() -> Result<()> {
     assert!(gamma_edits.iter().all(|e| !e.edit.pending));
 
     assert_eq!(0, gamma.notifications_count().await.unwrap());
-    let notifications = gamma.notifications_list().await.unwrap();
+    let notifications = gamma
+        .notifications_list(&Default::default())
+        .await
+        .unwrap()
+        .items;
     assert_eq!(0, notifications.len());
 
     TestData::stop(alpha, beta, gamma)
@@ -557,6 +632,7 @@ async fn test_overlapping_edits_no_conflict() -> Result<()> {
 "#
         .to_string(),
         summary: "create article".to_string(),
+        language: None,
     };
     let create_res = alpha.create_article(&create_params).await.unwrap();
     assert_eq!(create_params.title, create_res.article.title);
@@ -624,6 +700,7 @@ async fn test_fork_article() -> Result<()> {
         title: "Manu_Chao".to_string(),
         text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
         summary: "create article".to_string(),
+        language: None,
     };
     let create_res = alpha.create_article(&create_params).await.unwrap();
     let create_edits = alpha
@@ -666,8 +743,9 @@ async fn test_fork_article() -> Result<()> {
     // now search returns two articles for this title (original and forked)
     let search_params = SearchArticleParams {
         query: create_params.title.clone(),
+        ..Default::default()
     };
-    let search_res = beta.search(&search_params).await.unwrap();
+    let search_res = beta.search(&search_params).await.unwrap().articles.items;
     assert_eq!(2, search_res.len());
 
     TestData::stop(alpha, beta, gamma)
@@ -687,6 +765,7 @@ async fn test_user_registration_login() -> Result<()> {
     let login_data = LoginUserParams {
         username: username.to_string(),
         password: "asd123".to_string(),
+        totp_token: None,
     };
     let invalid_login = alpha.login(login_data).await;
     assert!(invalid_login.is_err());
@@ -694,6 +773,7 @@ async fn test_user_registration_login() -> Result<()> {
     let login_data = LoginUserParams {
         username: username.to_string(),
         password: password.to_string(),
+        totp_token: None,
     };
     alpha.login(login_data).await.unwrap();
 
@@ -717,6 +797,7 @@ async fn test_user_profile() -> Result<()> {
         title: "Manu_Chao".to_string(),
         text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
         summary: "create article".to_string(),
+        language: None,
     };
     let create_res = alpha.create_article(&create_params).await.unwrap();
     beta.resolve_article(create_res.article.ap_id.into_inner())
@@ -745,6 +826,7 @@ async fn test_lock_article() -> Result<()> {
         title: "Manu_Chao".to_string(),
         text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
         summary: "create article".to_string(),
+        language: None,
     };
     let create_res = alpha.create_article(&create_params).await.unwrap();
     assert!(!create_res.article.protected);
@@ -753,6 +835,7 @@ async fn test_lock_article() -> Result<()> {
     let lock_params = ProtectArticleParams {
         article_id: create_res.article.id,
         protected: true,
+        reason: None,
     };
     let lock_res = alpha.protect_article(&lock_params).await;
     assert!(lock_res.is_err());
@@ -761,6 +844,7 @@ async fn test_lock_article() -> Result<()> {
     let params = LoginUserParams {
         username: "ibis".to_string(),
         password: "ibis".to_string(),
+        totp_token: None,
     };
     alpha.login(params).await.unwrap();
     let lock_res = alpha.protect_article(&lock_params).await.unwrap();
@@ -791,7 +875,11 @@ async fn test_synchronize_instances() -> Result<()> {
     beta.resolve_instance(Url::parse(&format!("http://{}", &alpha.hostname))?)
         .await
         .unwrap();
-    let beta_instances = beta.list_instances().await.unwrap();
+    let beta_instances = beta
+        .list_instances(&Default::default())
+        .await
+        .unwrap()
+        .items;
     assert_eq!(2, beta_instances.len());
 
     // fetch beta instance on gamma
@@ -803,11 +891,11 @@ async fn test_synchronize_instances() -> Result<()> {
     // wait until instance collection is fetched
     let gamma_instances = RetryFuture::new(
         || async {
-            let res = gamma.list_instances().await;
+            let res = gamma.list_instances(&Default::default()).await;
             match res {
                 None => Err(RetryPolicy::<String>::Retry(None)),
-                Some(i) if i.len() < 3 => Err(RetryPolicy::Retry(None)),
-                Some(i) => Ok(i),
+                Some(i) if i.items.len() < 3 => Err(RetryPolicy::Retry(None)),
+                Some(i) => Ok(i.items),
             }
         },
         LinearRetryStrategy::new(),
@@ -830,23 +918,34 @@ async fn test_article_approval_required() -> Result<()> {
         title: "Manu_Chao".to_string(),
         text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
         summary: "create article".to_string(),
+        language: None,
     };
     let create_res = alpha.create_article(&create_params).await.unwrap();
     assert!(!create_res.article.approved);
 
-    let list_all = alpha.list_articles(Default::default()).await.unwrap();
+    let list_all = alpha.list_articles(Default::default()).await.unwrap().items;
     assert_eq!(1, list_all.len());
     assert!(list_all.iter().all(|a| a.id != create_res.article.id));
 
+    // feeds don't expose the article before it is approved
+    let feed_url = format!("http://{}/feed/article/Manu_Chao", alpha.hostname);
+    let res = reqwest::get(&feed_url).await?;
+    assert!(!res.status().is_success());
+
     // login as admin to handle approvals
     let params = LoginUserParams {
         username: "ibis".to_string(),
         password: "ibis".to_string(),
+        totp_token: None,
     };
     alpha.login(params).await.unwrap();
 
     assert_eq!(1, alpha.notifications_count().await.unwrap());
-    let notifications = alpha.notifications_list().await.unwrap();
+    let notifications = alpha
+        .notifications_list(&Default::default())
+        .await
+        .unwrap()
+        .items;
     assert_eq!(1, notifications.len());
     let Notification::ArticleApprovalRequired(notif) = &notifications[0] else {
         panic!()
@@ -862,9 +961,11 @@ async fn test_article_approval_required() -> Result<()> {
     assert_eq!(create_res.article.id, approved.article.id);
     assert!(approved.article.approved);
 
-    let list_all = alpha.list_articles(Default::default()).await.unwrap();
+    let list_all = alpha.list_articles(Default::default()).await.unwrap().items;
     assert_eq!(2, list_all.len());
     assert!(list_all.iter().any(|a| a.id == create_res.article.id));
+    let res = reqwest::get(&feed_url).await?;
+    assert_eq!(StatusCode::OK, res.status());
 
     TestData::stop(alpha, beta, gamma)
 }
@@ -886,6 +987,7 @@ async fn test_comment_create_edit() -> Result<()> {
         title: "Manu_Chao".to_string(),
         text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
         summary: "create article".to_string(),
+        language: None,
     };
     let alpha_article = alpha.create_article(&params).await.unwrap();
 
@@ -916,11 +1018,12 @@ async fn test_comment_create_edit() -> Result<()> {
         ..Default::default()
     };
     let article = alpha.get_article(get_params.clone()).await.unwrap();
-    assert_eq!(1, article.comments.len());
+    assert_eq!(1, article.comment_count);
+    let alpha_comments = alpha.get_comments(get_params.clone()).await;
     let params = CreateCommentParams {
         content: "child comment".to_string(),
         article_id: article.article.id,
-        parent_id: Some(article.comments[0].comment.id),
+        parent_id: Some(alpha_comments[0].comment.id),
     };
     let child_comment = alpha.create_comment(&params).await.unwrap().comment;
     assert_eq!(child_comment.parent_id, Some(top_comment.id));
@@ -936,7 +1039,7 @@ async fn test_comment_create_edit() -> Result<()> {
     assert_eq!(edited_comment.article_id, article.article.id);
     assert_eq!(Some(&edited_comment.content), edit_params.content.as_ref());
 
-    let beta_comments = beta.get_article(get_params.clone()).await.unwrap().comments;
+    let beta_comments = beta.get_comments(get_params.clone()).await;
     assert_eq!(2, beta_comments.len());
     assert_eq!(beta_comments[1].comment.content, top_comment.content);
     assert_eq!(
@@ -944,13 +1047,125 @@ async fn test_comment_create_edit() -> Result<()> {
         edit_params.content.as_ref()
     );
 
-    let gamma_comments = gamma.get_article(get_params).await.unwrap().comments;
+    let gamma_comments = gamma.get_comments(get_params).await;
     assert_eq!(2, gamma_comments.len());
     assert_eq!(edited_comment.content, gamma_comments[0].comment.content);
 
     TestData::stop(alpha, beta, gamma)
 }
 
+#[tokio::test]
+async fn test_comment_tree() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    let params = CreateArticleParams {
+        title: "Manu_Chao".to_string(),
+        text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+        summary: "create article".to_string(),
+        language: None,
+    };
+    let article = alpha.create_article(&params).await.unwrap();
+    let article_id = article.article.id;
+
+    // three top level comments, the first one with two replies which have one reply each
+    let mut top_comments = vec![];
+    for i in 0..3 {
+        let params = CreateCommentParams {
+            content: format!("top comment {i}"),
+            article_id,
+            parent_id: None,
+        };
+        top_comments.push(alpha.create_comment(&params).await.unwrap().comment);
+    }
+    for i in 0..2 {
+        let params = CreateCommentParams {
+            content: format!("reply {i}"),
+            article_id,
+            parent_id: Some(top_comments[0].id),
+        };
+        let reply = alpha.create_comment(&params).await.unwrap().comment;
+        let params = CreateCommentParams {
+            content: format!("nested reply {i}"),
+            article_id,
+            parent_id: Some(reply.id),
+        };
+        alpha.create_comment(&params).await.unwrap();
+    }
+    let get_params = GetArticleParams {
+        id: Some(article_id),
+        ..Default::default()
+    };
+    let article = alpha.get_article(get_params).await.unwrap();
+    assert_eq!(7, article.comment_count);
+
+    // top level is paginated, newest first
+    let mut list_params = ListCommentsParams {
+        article_id,
+        limit: Some(2),
+        ..Default::default()
+    };
+    let page1 = alpha.list_comments(&list_params).await.unwrap();
+    assert_eq!(2, page1.comments.len());
+    assert_eq!(top_comments[2].id, page1.comments[0].comment.comment.id);
+    list_params.cursor = page1.next_cursor;
+    assert!(list_params.cursor.is_some());
+    let page2 = alpha.list_comments(&list_params).await.unwrap();
+    assert_eq!(1, page2.comments.len());
+    assert_eq!(top_comments[0].id, page2.comments[0].comment.comment.id);
+    assert!(page2.next_cursor.is_none());
+
+    // replies are nested, and limited per level
+    let first = &page2.comments[0];
+    assert_eq!(2, first.reply_count);
+    assert_eq!(2, first.children.len());
+    assert_eq!(1, first.children[0].children.len());
+    assert!(first.more_replies.is_none());
+    list_params.limit = Some(1);
+    let tree = alpha.list_comments(&list_params).await.unwrap();
+    let first = &tree.comments[0];
+    assert_eq!(2, first.reply_count);
+    assert_eq!(1, first.children.len());
+    assert!(first.more_replies.is_some());
+
+    // replies beyond max depth are not included, but can be loaded separately
+    let list_params = ListCommentsParams {
+        article_id,
+        max_depth: Some(0),
+        ..Default::default()
+    };
+    let tree = alpha.list_comments(&list_params).await.unwrap();
+    assert_eq!(3, tree.comments.len());
+    let first = &tree.comments[2];
+    assert!(first.children.is_empty());
+    assert_eq!(2, first.reply_count);
+    let list_params = ListCommentsParams {
+        article_id,
+        parent_id: Some(first.comment.comment.id),
+        cursor: first.more_replies.clone(),
+        ..Default::default()
+    };
+    let replies = alpha.list_comments(&list_params).await.unwrap();
+    assert_eq!(2, replies.comments.len());
+    assert_eq!(1, replies.comments[0].children.len());
+
+    // parent comment must belong to the listed article
+    let params = CreateArticleParams {
+        title: "Clandestino".to_string(),
+        text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+        summary: "create article".to_string(),
+        language: None,
+    };
+    let other_article = alpha.create_article(&params).await.unwrap();
+    let list_params = ListCommentsParams {
+        article_id: other_article.article.id,
+        parent_id: Some(first.comment.comment.id),
+        ..Default::default()
+    };
+    assert!(alpha.list_comments(&list_params).await.is_none());
+
+    TestData::stop(alpha, beta, gamma)
+}
+
 #[tokio::test]
 async fn test_comment_delete_restore() -> Result<()> {
     let TestData(alpha, beta, gamma) = TestData::start(true).await;
@@ -964,6 +1179,7 @@ async fn test_comment_delete_restore() -> Result<()> {
         title: "Manu_Chao".to_string(),
         text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
         summary: "create article".to_string(),
+        language: None,
     };
     let alpha_article = alpha.create_article(&params).await.unwrap();
 
@@ -986,17 +1202,13 @@ async fn test_comment_delete_restore() -> Result<()> {
         content: None,
     };
     alpha.edit_comment(&params).await.unwrap();
-    let alpha_comments = alpha
-        .get_article(get_params.clone())
-        .await
-        .unwrap()
-        .comments;
+    let alpha_comments = alpha.get_comments(get_params.clone()).await;
     assert!(alpha_comments[0].comment.deleted);
     assert!(alpha_comments[0].comment.content.is_empty());
     sleep(Duration::from_secs(1)).await;
 
     // check that comment is deleted on beta
-    let beta_comments = beta.get_article(get_params.clone()).await.unwrap().comments;
+    let beta_comments = beta.get_comments(get_params.clone()).await;
     assert_eq!(comment.comment.ap_id, beta_comments[0].comment.ap_id);
     assert!(beta_comments[0].comment.deleted);
     assert!(beta_comments[0].comment.content.is_empty());
@@ -1004,19 +1216,2381 @@ async fn test_comment_delete_restore() -> Result<()> {
     // restore comment
     params.deleted = Some(false);
     alpha.edit_comment(&params).await.unwrap();
-    let alpha_comments = alpha
-        .get_article(get_params.clone())
-        .await
-        .unwrap()
-        .comments;
+    let alpha_comments = alpha.get_comments(get_params.clone()).await;
     assert!(!alpha_comments[0].comment.deleted);
     assert!(!alpha_comments[0].comment.content.is_empty());
     sleep(Duration::from_secs(1)).await;
 
     // check that comment is restored on beta
-    let beta_comments = beta.get_article(get_params).await.unwrap().comments;
+    let beta_comments = beta.get_comments(get_params).await;
     assert!(!beta_comments[0].comment.deleted);
     assert!(!beta_comments[0].comment.content.is_empty());
 
     TestData::stop(alpha, beta, gamma)
 }
+
+#[tokio::test]
+async fn test_comment_remove() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    beta.follow_instance_with_resolve(&alpha.hostname)
+        .await
+        .unwrap();
+
+    // create article on alpha and comment from beta
+    let params = CreateArticleParams {
+        title: "Manu_Chao".to_string(),
+        text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+        summary: "create article".to_string(),
+        language: None,
+    };
+    let alpha_article = alpha.create_article(&params).await.unwrap();
+    let beta_article = beta
+        .resolve_article(alpha_article.article.ap_id.inner().clone())
+        .await
+        .unwrap();
+    let params = CreateCommentParams {
+        content: "spam comment".to_string(),
+        article_id: beta_article.article.id,
+        parent_id: None,
+    };
+    let beta_comment = beta.create_comment(&params).await.unwrap();
+    sleep(Duration::from_secs(1)).await;
+    let get_params = GetArticleParams {
+        title: Some(alpha_article.article.title),
+        domain: Some(alpha.hostname.clone()),
+        ..Default::default()
+    };
+    let alpha_comments = alpha.get_comments(get_params.clone()).await;
+    assert_eq!(1, alpha_comments.len());
+
+    // normal users cannot remove comments
+    let mut remove_params = RemoveCommentParams {
+        id: alpha_comments[0].comment.id,
+        removed: true,
+        reason: Some("spam".to_string()),
+    };
+    assert!(alpha.remove_comment(&remove_params).await.is_err());
+
+    // admin removes the comment
+    let client = ClientBuilder::new().cookie_store(true).build()?;
+    let admin = ApiClient::new(client, Some(alpha.hostname.clone()));
+    let login_params = LoginUserParams {
+        username: "ibis".to_string(),
+        password: "ibis".to_string(),
+        totp_token: None,
+    };
+    admin.login(login_params).await.unwrap();
+    let removed = admin.remove_comment(&remove_params).await.unwrap();
+    assert!(removed.comment.removed);
+    assert!(!removed.comment.deleted);
+    let alpha_comments = alpha.get_comments(get_params.clone()).await;
+    assert!(alpha_comments[0].comment.removed);
+    assert!(alpha_comments[0].comment.content.is_empty());
+    let modlog = alpha.list_modlog(&Default::default()).await.unwrap().items;
+    assert_eq!(ModlogAction::RemoveComment, modlog[0].modlog.action);
+    assert_eq!(Some("spam".to_string()), modlog[0].modlog.reason);
+    sleep(Duration::from_secs(1)).await;
+
+    // removal is federated to followers
+    let beta_comments = beta.get_comments(get_params.clone()).await;
+    assert_eq!(beta_comment.comment.ap_id, beta_comments[0].comment.ap_id);
+    assert!(beta_comments[0].comment.removed);
+    assert!(beta_comments[0].comment.content.is_empty());
+
+    // author cannot revert the removal
+    let edit_params = EditCommentParams {
+        id: beta_comment.comment.id,
+        deleted: Some(false),
+        content: None,
+    };
+    assert!(beta.edit_comment(&edit_params).await.is_err());
+
+    // admin restores the comment
+    remove_params.removed = false;
+    remove_params.reason = None;
+    let restored = admin.remove_comment(&remove_params).await.unwrap();
+    assert!(!restored.comment.removed);
+    sleep(Duration::from_secs(1)).await;
+    let beta_comments = beta.get_comments(get_params).await;
+    assert!(!beta_comments[0].comment.removed);
+    assert_eq!(
+        beta_comment.comment.content,
+        beta_comments[0].comment.content
+    );
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_comment_vote() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    beta.follow_instance_with_resolve(&alpha.hostname)
+        .await
+        .unwrap();
+
+    // create article and comment on alpha
+    let params = CreateArticleParams {
+        title: "Manu_Chao".to_string(),
+        text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+        summary: "create article".to_string(),
+        language: None,
+    };
+    let alpha_article = alpha.create_article(&params).await.unwrap();
+    let params = CreateCommentParams {
+        content: "my comment".to_string(),
+        article_id: alpha_article.article.id,
+        parent_id: None,
+    };
+    let alpha_comment = alpha.create_comment(&params).await.unwrap();
+    assert_eq!(0, alpha_comment.upvotes);
+    assert_eq!(0, alpha_comment.downvotes);
+    sleep(Duration::from_secs(1)).await;
+    let get_params = GetArticleParams {
+        title: Some(alpha_article.article.title),
+        domain: Some(alpha.hostname.clone()),
+        ..Default::default()
+    };
+
+    // upvote from beta
+    let beta_comments = beta.get_comments(get_params.clone()).await;
+    let mut vote_params = VoteCommentParams {
+        comment_id: beta_comments[0].comment.id,
+        score: 1,
+    };
+    let voted = beta.vote_comment(&vote_params).await.unwrap();
+    assert_eq!(1, voted.upvotes);
+    assert_eq!(Some(1), voted.my_vote);
+    vote_params.score = 2;
+    assert!(beta.vote_comment(&vote_params).await.is_err());
+    sleep(Duration::from_secs(1)).await;
+
+    // downvote from alpha, both votes are counted on alpha
+    let vote_params = VoteCommentParams {
+        comment_id: alpha_comment.comment.id,
+        score: -1,
+    };
+    alpha.vote_comment(&vote_params).await.unwrap();
+    let alpha_comments = alpha.get_comments(get_params.clone()).await;
+    assert_eq!(1, alpha_comments[0].upvotes);
+    assert_eq!(1, alpha_comments[0].downvotes);
+    assert_eq!(Some(-1), alpha_comments[0].my_vote);
+    sleep(Duration::from_secs(1)).await;
+
+    // votes are announced to followers
+    let beta_comments = beta.get_comments(get_params.clone()).await;
+    assert_eq!(1, beta_comments[0].upvotes);
+    assert_eq!(1, beta_comments[0].downvotes);
+    assert_eq!(Some(1), beta_comments[0].my_vote);
+
+    // remove vote from beta
+    let vote_params = VoteCommentParams {
+        comment_id: beta_comments[0].comment.id,
+        score: 0,
+    };
+    let voted = beta.vote_comment(&vote_params).await.unwrap();
+    assert_eq!(0, voted.upvotes);
+    assert_eq!(None, voted.my_vote);
+    sleep(Duration::from_secs(1)).await;
+    let alpha_comments = alpha.get_comments(get_params).await;
+    assert_eq!(0, alpha_comments[0].upvotes);
+    assert_eq!(1, alpha_comments[0].downvotes);
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_delete_account() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    beta.follow_instance_with_resolve(&alpha.hostname)
+        .await
+        .unwrap();
+
+    // create article and comment
+    let params = CreateArticleParams {
+        title: "Manu_Chao".to_string(),
+        text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+        summary: "create article".to_string(),
+        language: None,
+    };
+    let alpha_article = alpha.create_article(&params).await.unwrap();
+    let params = CreateCommentParams {
+        content: "my comment".to_string(),
+        article_id: alpha_article.article.id,
+        parent_id: None,
+    };
+    alpha.create_comment(&params).await.unwrap();
+    sleep(Duration::from_secs(1)).await;
+
+    let get_params = GetArticleParams {
+        title: Some(alpha_article.article.title),
+        domain: Some(alpha.hostname.clone()),
+        ..Default::default()
+    };
+    let beta_article = beta.get_article(get_params.clone()).await.unwrap();
+    let beta_comments = beta.get_comments(get_params.clone()).await;
+    assert_eq!(1, beta_comments.len());
+    assert_eq!("alpha", beta_comments[0].creator.username);
+
+    // deletion requires correct password
+    let mut params = DeleteAccountParams {
+        password: "wrong".to_string(),
+        delete_comments: true,
+    };
+    assert!(alpha.delete_account(&params).await.is_err());
+    params.password = "hunter2".to_string();
+    alpha.delete_account(&params).await.unwrap();
+    assert!(alpha.site().await.unwrap().my_profile.is_none());
+
+    // edits and comments are reassigned to ghost user
+    let alpha_edits = alpha
+        .get_article_edits(alpha_article.article.id)
+        .await
+        .unwrap();
+    assert_eq!("ghost", alpha_edits[0].creator.username);
+    let alpha_comments = alpha.get_comments(get_params.clone()).await;
+    assert_eq!("ghost", alpha_comments[0].creator.username);
+    assert!(alpha_comments[0].comment.deleted);
+    let user_params = GetUserParams {
+        name: "alpha".to_string(),
+        domain: None,
+    };
+    assert!(alpha.get_user(user_params).await.is_none());
+    let login_params = LoginUserParams {
+        username: "alpha".to_string(),
+        password: "hunter2".to_string(),
+        totp_token: None,
+    };
+    assert!(alpha.login(login_params).await.is_err());
+    sleep(Duration::from_secs(1)).await;
+
+    // deletion was federated to beta
+    let beta_edits = beta
+        .get_article_edits(beta_article.article.id)
+        .await
+        .unwrap();
+    assert_eq!("ghost", beta_edits[0].creator.username);
+    let beta_comments = beta.get_comments(get_params).await;
+    assert_eq!("ghost", beta_comments[0].creator.username);
+    assert!(beta_comments[0].comment.deleted);
+    assert!(beta_comments[0].comment.content.is_empty());
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_totp_login() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    // setup and enable 2fa
+    let setup = alpha.setup_totp().await.unwrap();
+    let totp = TOTP::from_url(&setup.otpauth_url)?;
+    let token = totp_token_for_step(&totp).await;
+    let params = EnableTotpParams {
+        totp_token: "000000".to_string(),
+    };
+    assert!(alpha.enable_totp(&params).await.is_err());
+    let params = EnableTotpParams {
+        totp_token: token(-1),
+    };
+    let recovery_codes = alpha.enable_totp(&params).await.unwrap().codes;
+    assert_eq!(10, recovery_codes.len());
+    assert!(
+        alpha
+            .site()
+            .await
+            .unwrap()
+            .my_profile
+            .unwrap()
+            .local_user
+            .totp_enabled
+    );
+    alpha.logout().await.unwrap();
+
+    // login requires token
+    let mut login_params = LoginUserParams {
+        username: "alpha".to_string(),
+        password: "hunter2".to_string(),
+        totp_token: None,
+    };
+    let err = alpha.login(login_params.clone()).await.unwrap_err();
+    assert!(err.to_string().contains(TOTP_REQUIRED_ERROR));
+    login_params.totp_token = Some("000000".to_string());
+    assert!(alpha.login(login_params.clone()).await.is_err());
+    login_params.totp_token = Some(token(0));
+    alpha.login(login_params.clone()).await.unwrap();
+    alpha.logout().await.unwrap();
+
+    // token can only be used once, and not after a token of a later time step
+    assert!(alpha.login(login_params.clone()).await.is_err());
+    login_params.totp_token = Some(token(-1));
+    assert!(alpha.login(login_params.clone()).await.is_err());
+
+    // recovery code can only be used once
+    login_params.totp_token = Some(recovery_codes[0].clone());
+    alpha.login(login_params.clone()).await.unwrap();
+    assert!(alpha.login(login_params.clone()).await.is_err());
+
+    // disable 2fa
+    let params = DisableTotpParams {
+        password: "hunter2".to_string(),
+        totp_token: token(1),
+    };
+    alpha.disable_totp(&params).await.unwrap();
+    login_params.totp_token = None;
+    alpha.login(login_params).await.unwrap();
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_totp_attempts_limited() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    let setup = alpha.setup_totp().await.unwrap();
+    let totp = TOTP::from_url(&setup.otpauth_url)?;
+    let token = totp_token_for_step(&totp).await;
+    let params = EnableTotpParams {
+        totp_token: token(-1),
+    };
+    let recovery_codes = alpha.enable_totp(&params).await.unwrap().codes;
+    alpha.logout().await.unwrap();
+
+    // after five failed attempts even valid tokens are rejected
+    let mut login_params = LoginUserParams {
+        username: "alpha".to_string(),
+        password: "hunter2".to_string(),
+        totp_token: Some("000000".to_string()),
+    };
+    for _ in 0..5 {
+        assert!(alpha.login(login_params.clone()).await.is_err());
+    }
+    login_params.totp_token = Some(token(0));
+    let err = alpha.login(login_params.clone()).await.unwrap_err();
+    assert!(err.to_string().contains("Too many failed attempts"));
+    login_params.totp_token = Some(recovery_codes[0].clone());
+    assert!(alpha.login(login_params).await.is_err());
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+/// Returns a function which generates the token for a time step relative to the current one.
+/// Each token is only accepted once, so tests need tokens of the previous and next step as well.
+/// If the current step is about to end, this waits for the next one so that they stay valid.
+async fn totp_token_for_step(totp: &TOTP) -> impl Fn(i64) -> String + '_ {
+    let now = || {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    };
+    let elapsed = now() % totp.step;
+    if elapsed > totp.step - 10 {
+        sleep(Duration::from_secs(totp.step - elapsed)).await;
+    }
+    let step = now() / totp.step;
+    move |offset| totp.generate((step as i64 + offset) as u64 * totp.step)
+}
+
+#[tokio::test]
+async fn test_oidc_login() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    // first login creates a new account
+    let client = oidc_client();
+    oidc_login(&client, &alpha.hostname).await?;
+    let api_client = ApiClient::new(client, Some(alpha.hostname.clone()));
+    let my_profile = api_client.site().await.unwrap().my_profile.unwrap();
+    assert_eq!(OIDC_MOCK_USERNAME, my_profile.person.username);
+
+    // later logins use the same account
+    let client = oidc_client();
+    oidc_login(&client, &alpha.hostname).await?;
+    let api_client = ApiClient::new(client.clone(), Some(alpha.hostname.clone()));
+    let my_profile2 = api_client.site().await.unwrap().my_profile.unwrap();
+    assert_eq!(my_profile.person.id, my_profile2.person.id);
+
+    // the generated password is unknown, instead the deletion is confirmed by logging in again
+    let mut params = DeleteAccountParams {
+        password: String::new(),
+        delete_comments: false,
+    };
+    assert!(api_client.delete_account(&params).await.is_err());
+    params.password = "hunter2".to_string();
+    assert!(api_client.delete_account(&params).await.is_err());
+    let url = format!(
+        "http://{}/api/v1/account/oidc/login?provider={OIDC_MOCK_NAME}",
+        alpha.hostname
+    );
+    let res = client.get(url).send().await?;
+    assert_eq!("/edit_profile", res.headers()["location"]);
+    params.password = String::new();
+    api_client.delete_account(&params).await.unwrap();
+    assert!(api_client.site().await.unwrap().my_profile.is_none());
+
+    // identity gets linked to the logged in user
+    let client = oidc_client();
+    let api_client = ApiClient::new(client.clone(), Some(beta.hostname.clone()));
+    let params = LoginUserParams {
+        username: "beta".to_string(),
+        password: "hunter2".to_string(),
+        totp_token: None,
+    };
+    api_client.login(params).await.unwrap();
+    oidc_login(&client, &beta.hostname).await?;
+    let client = oidc_client();
+    oidc_login(&client, &beta.hostname).await?;
+    let api_client = ApiClient::new(client, Some(beta.hostname.clone()));
+    let my_profile = api_client.site().await.unwrap().my_profile.unwrap();
+    assert_eq!("beta", my_profile.person.username);
+
+    // with two factor authentication the login needs to be confirmed with a token
+    let setup = api_client.setup_totp().await.unwrap();
+    let totp = TOTP::from_url(&setup.otpauth_url)?;
+    let params = EnableTotpParams {
+        totp_token: totp.generate_current()?,
+    };
+    let recovery_codes = api_client.enable_totp(&params).await.unwrap().codes;
+    let client = oidc_client();
+    let url = format!(
+        "http://{}/api/v1/account/oidc/login?provider={OIDC_MOCK_NAME}",
+        beta.hostname
+    );
+    let res = client.get(url).send().await?;
+    assert_eq!("/login?oidc_totp=true", res.headers()["location"]);
+    let api_client = ApiClient::new(client, Some(beta.hostname.clone()));
+    assert!(api_client.site().await.unwrap().my_profile.is_none());
+    let params = OidcTotpParams {
+        totp_token: "000000".to_string(),
+    };
+    assert!(api_client.oidc_totp(&params).await.is_err());
+    let params = OidcTotpParams {
+        totp_token: recovery_codes[0].clone(),
+    };
+    let user = api_client.oidc_totp(&params).await.unwrap();
+    assert_eq!("beta", user.person.username);
+    assert!(api_client.site().await.unwrap().my_profile.is_some());
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+/// Http client which stops at the final redirect to the frontend, so that the login cookie
+/// can be used for api calls.
+fn oidc_client() -> reqwest::Client {
+    ClientBuilder::new()
+        .cookie_store(true)
+        .redirect(Policy::custom(|attempt| {
+            if ["/", "/login", "/edit_profile"].contains(&attempt.url().path()) {
+                attempt.stop()
+            } else {
+                attempt.follow()
+            }
+        }))
+        .build()
+        .unwrap()
+}
+
+async fn oidc_login(client: &reqwest::Client, hostname: &str) -> Result<()> {
+    let url = format!("http://{hostname}/api/v1/account/oidc/login?provider={OIDC_MOCK_NAME}");
+    let res = client.get(url).send().await?;
+    assert_eq!(StatusCode::SEE_OTHER, res.status());
+    assert_eq!("/", res.headers()["location"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_api_token() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    let params = CreateApiTokenParams {
+        name: "my bot".to_string(),
+        scope_read: true,
+        scope_comment: true,
+        ..Default::default()
+    };
+    let token = alpha.create_api_token(&params).await.unwrap().token;
+    let api_tokens = alpha.list_api_tokens().await.unwrap();
+    assert_eq!(1, api_tokens.len());
+    assert_eq!("my bot", api_tokens[0].name);
+    assert!(api_tokens[0].last_used.is_none());
+
+    // read scope
+    let client = reqwest::Client::new();
+    let endpoint = |path: &str| format!("http://{}/api/v1{path}", alpha.hostname);
+    let site: SiteView = client
+        .get(endpoint("/site"))
+        .bearer_auth(&token)
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert_eq!("alpha", site.my_profile.unwrap().person.username);
+
+    // edit scope is missing
+    let create_params = CreateArticleParams {
+        title: "Manu Chao".to_string(),
+        text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+        summary: "create article".to_string(),
+        language: None,
+    };
+    let res = client
+        .post(endpoint("/article"))
+        .bearer_auth(&token)
+        .form(&create_params)
+        .send()
+        .await?;
+    assert!(!res.status().is_success());
+
+    // comment scope
+    let article = alpha.create_article(&create_params).await.unwrap();
+    let comment_params = CreateCommentParams {
+        content: "comment from bot".to_string(),
+        article_id: article.article.id,
+        parent_id: None,
+    };
+    let res = client
+        .post(endpoint("/comment"))
+        .bearer_auth(&token)
+        .form(&comment_params)
+        .send()
+        .await?;
+    assert_eq!(StatusCode::OK, res.status());
+
+    // account management is not possible with api token
+    let res = client
+        .get(endpoint("/account/token/list"))
+        .bearer_auth(&token)
+        .send()
+        .await?;
+    assert!(!res.status().is_success());
+
+    let api_tokens = alpha.list_api_tokens().await.unwrap();
+    assert!(api_tokens[0].last_used.is_some());
+
+    // revoked token cannot be used anymore
+    let params = DeleteApiTokenParams {
+        id: api_tokens[0].id,
+    };
+    alpha.delete_api_token(&params).await.unwrap();
+    let site: SiteView = client
+        .get(endpoint("/site"))
+        .bearer_auth(&token)
+        .send()
+        .await?
+        .json()
+        .await?;
+    assert!(site.my_profile.is_none());
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_bot_account() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    // only admin can mark alpha as bot
+    let my_profile = alpha.site().await.unwrap().my_profile.unwrap();
+    assert!(!my_profile.person.bot);
+    let role_params = UpdateUserRoleParams {
+        person_id: my_profile.person.id,
+        bot: true,
+        ..Default::default()
+    };
+    assert!(alpha.update_user_role(&role_params).await.is_err());
+    let client = ClientBuilder::new().cookie_store(true).build()?;
+    let admin = ApiClient::new(client, Some(alpha.hostname.clone()));
+    let params = LoginUserParams {
+        username: "ibis".to_string(),
+        password: "ibis".to_string(),
+        totp_token: None,
+    };
+    admin.login(params).await.unwrap();
+    admin.update_user_role(&role_params).await.unwrap();
+    let my_profile = alpha.site().await.unwrap().my_profile.unwrap();
+    assert!(my_profile.person.bot);
+
+    let create_params = CreateArticleParams {
+        title: "Manu Chao".to_string(),
+        text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+        summary: "create article".to_string(),
+        language: None,
+    };
+    let create_res = alpha.create_article(&create_params).await.unwrap();
+
+    // bot edits can be hidden
+    let mut params = GetEditList {
+        article_id: Some(create_res.article.id),
+        ..Default::default()
+    };
+    let edits = alpha.list_edits(params.clone()).await.unwrap().items;
+    assert_eq!(1, edits.len());
+    assert!(edits[0].creator.bot);
+    params.hide_bots = Some(true);
+    assert!(alpha.list_edits(params).await.unwrap().items.is_empty());
+
+    let mut params = ListArticlesParams {
+        only_local: Some(true),
+        ..Default::default()
+    };
+    let articles = alpha.list_articles(params.clone()).await.unwrap().items;
+    assert!(articles.iter().any(|a| a.id == create_res.article.id));
+    params.hide_bots = Some(true);
+    let articles = alpha.list_articles(params).await.unwrap().items;
+    assert!(!articles.iter().any(|a| a.id == create_res.article.id));
+
+    // bot flag is federated
+    let beta_article = beta
+        .resolve_article(create_res.article.ap_id.into_inner())
+        .await
+        .unwrap();
+    let beta_edits = beta
+        .get_article_edits(beta_article.article.id)
+        .await
+        .unwrap();
+    assert_eq!("alpha", beta_edits[0].creator.username);
+    assert!(beta_edits[0].creator.bot);
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_unauthorized_actions() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    let create_params = CreateArticleParams {
+        title: "Manu Chao".to_string(),
+        text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+        summary: "create article".to_string(),
+        language: None,
+    };
+    let article = alpha.create_article(&create_params).await.unwrap();
+    let comment_params = CreateCommentParams {
+        content: "my comment".to_string(),
+        article_id: article.article.id,
+        parent_id: None,
+    };
+    let comment = alpha.create_comment(&comment_params).await.unwrap();
+    let alpha_profile = alpha.site().await.unwrap().my_profile.unwrap();
+    let instance = alpha.get_local_instance().await.unwrap();
+    let token_params = CreateApiTokenParams {
+        name: "token".to_string(),
+        scope_read: true,
+        ..Default::default()
+    };
+    let api_token = alpha.create_api_token(&token_params).await.unwrap();
+
+    let edit_params = EditArticleParams {
+        article_id: article.article.id,
+        new_text: "Lorem Ipsum 2\n".to_string(),
+        summary: "summary".to_string(),
+        previous_version_id: article.latest_version,
+        resolve_conflict_id: None,
+    };
+    let fork_params = ForkArticleParams {
+        article_id: article.article.id,
+        new_title: "Manu Chao 2".to_string(),
+    };
+    let protect_params = ProtectArticleParams {
+        article_id: article.article.id,
+        protected: true,
+        reason: None,
+    };
+    let edit_comment_params = EditCommentParams {
+        id: comment.comment.id,
+        content: Some("changed".to_string()),
+        deleted: None,
+    };
+    let update_instance_params = UpdateInstanceParams {
+        name: Some("Changed".to_string()),
+        topic: None,
+    };
+    let follow_params = FollowInstanceParams {
+        id: instance.instance.id,
+    };
+    let delete_user_params = DeleteUserParams {
+        person_id: alpha_profile.person.id,
+        delete_comments: true,
+    };
+    let update_profile_params = UpdateUserParams {
+        person_id: alpha_profile.person.id,
+        display_name: Some("Mallory".to_string()),
+        bio: None,
+    };
+
+    // anonymous user can't perform any mutating actions
+    let anonymous = ApiClient::new(reqwest::Client::new(), Some(alpha.hostname.clone()));
+    assert!(anonymous.create_article(&create_params).await.is_err());
+    assert!(anonymous.edit_article(&edit_params).await.is_err());
+    assert!(anonymous.fork_article(&fork_params).await.is_err());
+    assert!(anonymous.protect_article(&protect_params).await.is_err());
+    assert!(anonymous
+        .approve_article(article.article.id, true)
+        .await
+        .is_none());
+    assert!(anonymous.delete_conflict(ConflictId(1)).await.is_none());
+    assert!(anonymous.create_comment(&comment_params).await.is_err());
+    assert!(anonymous.edit_comment(&edit_comment_params).await.is_err());
+    assert!(anonymous
+        .update_local_instance(&update_instance_params)
+        .await
+        .is_err());
+    assert!(anonymous.follow_instance(follow_params).await.is_none());
+    assert!(anonymous.delete_user(&delete_user_params).await.is_err());
+    assert!(anonymous
+        .update_user_profile(update_profile_params.clone())
+        .await
+        .is_err());
+    let delete_account_params = DeleteAccountParams {
+        password: "hunter2".to_string(),
+        delete_comments: true,
+    };
+    assert!(anonymous
+        .delete_account(&delete_account_params)
+        .await
+        .is_err());
+    assert!(anonymous.setup_totp().await.is_err());
+    assert!(anonymous.create_api_token(&token_params).await.is_err());
+    let delete_token_params = DeleteApiTokenParams {
+        id: api_token.api_token.id,
+    };
+    assert!(anonymous
+        .delete_api_token(&delete_token_params)
+        .await
+        .is_err());
+
+    // regular user can't perform admin actions
+    assert!(alpha.protect_article(&protect_params).await.is_err());
+    assert!(alpha
+        .approve_article(article.article.id, true)
+        .await
+        .is_none());
+    assert!(alpha
+        .update_local_instance(&update_instance_params)
+        .await
+        .is_err());
+    assert!(alpha.delete_user(&delete_user_params).await.is_err());
+
+    // api token with only read scope can't perform other actions
+    let client = reqwest::Client::new();
+    let endpoint = |path: &str| format!("http://{}/api/v1{path}", alpha.hostname);
+    let res = client
+        .patch(endpoint("/article"))
+        .bearer_auth(&api_token.token)
+        .form(&edit_params)
+        .send()
+        .await?;
+    assert!(!res.status().is_success());
+    let res = client
+        .patch(endpoint("/comment"))
+        .bearer_auth(&api_token.token)
+        .form(&edit_comment_params)
+        .send()
+        .await?;
+    assert!(!res.status().is_success());
+    let res = client
+        .post(endpoint("/user/delete"))
+        .bearer_auth(&api_token.token)
+        .form(&delete_user_params)
+        .send()
+        .await?;
+    assert!(!res.status().is_success());
+    let res = client
+        .post(endpoint("/account/update"))
+        .bearer_auth(&api_token.token)
+        .form(&update_profile_params)
+        .send()
+        .await?;
+    assert!(!res.status().is_success());
+
+    // other user can't modify resources owned by alpha
+    let client = ClientBuilder::new().cookie_store(true).build()?;
+    let mallory = ApiClient::new(client, Some(alpha.hostname.clone()));
+    let register_params = RegisterUserParams {
+        username: "mallory".to_string(),
+        password: "hunter2".to_string(),
+    };
+    mallory.register(register_params).await.unwrap();
+    assert!(mallory
+        .update_user_profile(update_profile_params)
+        .await
+        .is_err());
+    assert!(mallory.edit_comment(&edit_comment_params).await.is_err());
+    assert!(mallory
+        .delete_api_token(&delete_token_params)
+        .await
+        .is_err());
+
+    // nothing was changed
+    let alpha_profile2 = alpha.site().await.unwrap().my_profile.unwrap();
+    assert_eq!(
+        alpha_profile.person.display_name,
+        alpha_profile2.person.display_name
+    );
+    let instance2 = alpha.get_local_instance().await.unwrap();
+    assert_eq!(instance.instance.name, instance2.instance.name);
+    assert_eq!(1, alpha.list_api_tokens().await.unwrap().len());
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_user_roles() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    let create_params = CreateArticleParams {
+        title: "Manu Chao".to_string(),
+        text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+        summary: "create article".to_string(),
+        language: None,
+    };
+    let article = alpha.create_article(&create_params).await.unwrap();
+    let protect_params = ProtectArticleParams {
+        article_id: article.article.id,
+        protected: true,
+        reason: None,
+    };
+    let alpha_profile = alpha.site().await.unwrap().my_profile.unwrap();
+    assert_eq!(UserRole::User, alpha_profile.local_user.role());
+    assert!(alpha.protect_article(&protect_params).await.is_err());
+
+    let client = ClientBuilder::new().cookie_store(true).build()?;
+    let admin = ApiClient::new(client, Some(alpha.hostname.clone()));
+    let params = LoginUserParams {
+        username: "ibis".to_string(),
+        password: "ibis".to_string(),
+        totp_token: None,
+    };
+    let admin_profile = admin.login(params).await.unwrap();
+
+    // regular user can't change roles
+    let mut role_params = UpdateUserRoleParams {
+        person_id: alpha_profile.person.id,
+        role: UserRole::Moderator,
+        ..Default::default()
+    };
+    assert!(alpha.update_user_role(&role_params).await.is_err());
+    assert!(alpha.get_user_role(admin_profile.person.id).await.is_none());
+
+    // moderator can protect articles, but not delete users
+    let local_user = admin.update_user_role(&role_params).await.unwrap();
+    assert_eq!(UserRole::Moderator, local_user.role());
+    let role = admin.get_user_role(alpha_profile.person.id).await.unwrap();
+    assert_eq!(UserRole::Moderator, role.role());
+    let protected = alpha.protect_article(&protect_params).await.unwrap();
+    assert!(protected.protected);
+    let delete_params = DeleteUserParams {
+        person_id: admin_profile.person.id,
+        delete_comments: false,
+    };
+    assert!(alpha.delete_user(&delete_params).await.is_err());
+
+    // moderator can't ban users with a higher role
+    let ban_params = BanUserParams {
+        person_id: admin_profile.person.id,
+        ban: true,
+        reason: None,
+        expires: None,
+        remove_content: false,
+    };
+    assert!(alpha.ban_user(&ban_params).await.is_err());
+
+    // demote to regular user, with individual permissions
+    role_params.role = UserRole::User;
+    role_params.perm_protect_article = true;
+    role_params.perm_ban = true;
+    admin.update_user_role(&role_params).await.unwrap();
+    let unprotect_params = ProtectArticleParams {
+        article_id: article.article.id,
+        protected: false,
+        reason: None,
+    };
+    let unprotected = alpha.protect_article(&unprotect_params).await.unwrap();
+    assert!(!unprotected.protected);
+    assert!(alpha
+        .approve_article(article.article.id, true)
+        .await
+        .is_none());
+
+    // granted ban permission works for regular users, but not for higher roles
+    let client = ClientBuilder::new().cookie_store(true).build()?;
+    let mallory = ApiClient::new(client, Some(alpha.hostname.clone()));
+    let register_params = RegisterUserParams {
+        username: "mallory".to_string(),
+        password: "hunter2".to_string(),
+    };
+    let mallory_profile = mallory.register(register_params).await.unwrap();
+    let mallory_ban_params = BanUserParams {
+        person_id: mallory_profile.person.id,
+        ..ban_params.clone()
+    };
+    let banned = alpha.ban_user(&mallory_ban_params).await.unwrap();
+    assert!(banned.banned);
+    assert!(alpha.ban_user(&ban_params).await.is_err());
+
+    // remove all permissions
+    role_params.perm_protect_article = false;
+    role_params.perm_ban = false;
+    admin.update_user_role(&role_params).await.unwrap();
+    assert!(alpha.protect_article(&protect_params).await.is_err());
+
+    // role changes are logged
+    let modlog = alpha.list_modlog(&Default::default()).await.unwrap().items;
+    assert_eq!(ModlogAction::UpdateUserRole, modlog[0].modlog.action);
+    assert_eq!(
+        Some(alpha_profile.person.id),
+        modlog[0].modlog.target_person_id
+    );
+
+    // admin can't change own role
+    let own_role_params = UpdateUserRoleParams {
+        person_id: admin_profile.person.id,
+        role: UserRole::User,
+        ..Default::default()
+    };
+    assert!(admin.update_user_role(&own_role_params).await.is_err());
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_ban_user() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    beta.follow_instance_with_resolve(&alpha.hostname)
+        .await
+        .unwrap();
+
+    let create_params = CreateArticleParams {
+        title: "Manu_Chao".to_string(),
+        text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+        summary: "create article".to_string(),
+        language: None,
+    };
+    let article = alpha.create_article(&create_params).await.unwrap();
+
+    // vandal edits the article and writes a comment
+    let client = ClientBuilder::new().cookie_store(true).build()?;
+    let vandal = ApiClient::new(client, Some(alpha.hostname.clone()));
+    let register_params = RegisterUserParams {
+        username: "vandal".to_string(),
+        password: "hunter2".to_string(),
+    };
+    let vandal_profile = vandal.register(register_params).await.unwrap();
+    let edit_params = EditArticleParams {
+        article_id: article.article.id,
+        new_text: "spam\n".to_string(),
+        summary: "spam".to_string(),
+        previous_version_id: article.latest_version,
+        resolve_conflict_id: None,
+    };
+    vandal.edit_article(&edit_params).await.unwrap();
+    let comment_params = CreateCommentParams {
+        content: "spam".to_string(),
+        article_id: article.article.id,
+        parent_id: None,
+    };
+    vandal.create_comment(&comment_params).await.unwrap();
+    sleep(Duration::from_secs(1)).await;
+
+    // ban with content removal
+    let client = ClientBuilder::new().cookie_store(true).build()?;
+    let admin = ApiClient::new(client, Some(alpha.hostname.clone()));
+    let login_params = LoginUserParams {
+        username: "ibis".to_string(),
+        password: "ibis".to_string(),
+        totp_token: None,
+    };
+    admin.login(login_params).await.unwrap();
+    let mut ban_params = BanUserParams {
+        person_id: vandal_profile.person.id,
+        ban: true,
+        reason: Some("vandalism".to_string()),
+        expires: None,
+        remove_content: true,
+    };
+    assert!(alpha.ban_user(&ban_params).await.is_err());
+    let banned = admin.ban_user(&ban_params).await.unwrap();
+    assert!(banned.is_banned());
+    assert_eq!(Some("vandalism".to_string()), banned.ban_reason);
+
+    // edit is reverted and comment removed
+    let get_params = GetArticleParams {
+        title: Some(article.article.title.clone()),
+        domain: Some(alpha.hostname.clone()),
+        ..Default::default()
+    };
+    let alpha_article = alpha.get_article(get_params.clone()).await.unwrap();
+    assert_eq!(TEST_ARTICLE_DEFAULT_TEXT, alpha_article.article.text);
+    let alpha_comments = alpha.get_comments(get_params.clone()).await;
+    assert!(alpha_comments[0].comment.removed);
+    let edits = alpha.get_article_edits(article.article.id).await.unwrap();
+    assert_eq!("ibis", edits.last().unwrap().creator.username);
+
+    // banned user is logged out and can't login or comment
+    assert!(vandal.site().await.unwrap().my_profile.is_none());
+    assert!(vandal.create_comment(&comment_params).await.is_err());
+    let vandal_login = LoginUserParams {
+        username: "vandal".to_string(),
+        password: "hunter2".to_string(),
+        totp_token: None,
+    };
+    assert!(vandal.login(vandal_login.clone()).await.is_err());
+
+    // ban is federated to beta
+    sleep(Duration::from_secs(1)).await;
+    let user_params = GetUserParams {
+        name: "vandal".to_string(),
+        domain: Some(alpha.hostname.clone()),
+    };
+    let beta_vandal = beta.get_user(user_params.clone()).await.unwrap();
+    assert!(beta_vandal.is_banned());
+    let beta_comments = beta.get_comments(get_params).await;
+    assert!(beta_comments[0].comment.removed);
+
+    // unban
+    ban_params.ban = false;
+    let unbanned = admin.ban_user(&ban_params).await.unwrap();
+    assert!(!unbanned.is_banned());
+    vandal.login(vandal_login).await.unwrap();
+    sleep(Duration::from_secs(1)).await;
+    let beta_vandal = beta.get_user(user_params).await.unwrap();
+    assert!(!beta_vandal.is_banned());
+
+    // ban remote user, which is only applied on alpha
+    let user_params = GetUserParams {
+        name: "beta".to_string(),
+        domain: Some(beta.hostname.clone()),
+    };
+    let alpha_beta_user = alpha.get_user(user_params).await.unwrap();
+    let ban_params = BanUserParams {
+        person_id: alpha_beta_user.id,
+        ban: true,
+        reason: None,
+        expires: None,
+        remove_content: false,
+    };
+    let banned = admin.ban_user(&ban_params).await.unwrap();
+    assert!(banned.is_banned());
+    sleep(Duration::from_secs(1)).await;
+    let beta_profile = beta.site().await.unwrap().my_profile.unwrap();
+    assert!(!beta_profile.person.is_banned());
+
+    // votes and reports of the banned remote user are rejected by alpha
+    let comment_params = CreateCommentParams {
+        content: "my comment".to_string(),
+        ..comment_params
+    };
+    alpha.create_comment(&comment_params).await.unwrap();
+    sleep(Duration::from_secs(1)).await;
+    let get_params = GetArticleParams {
+        title: Some(article.article.title.clone()),
+        domain: Some(alpha.hostname.clone()),
+        ..Default::default()
+    };
+    let beta_comments = beta.get_comments(get_params.clone()).await;
+    let beta_comment = beta_comments
+        .iter()
+        .find(|c| c.comment.content == "my comment")
+        .unwrap();
+    let vote_params = VoteCommentParams {
+        comment_id: beta_comment.comment.id,
+        score: 1,
+    };
+    beta.vote_comment(&vote_params).await.unwrap();
+    let report_params = CreateReportParams {
+        article_id: beta.get_article(get_params).await.unwrap().article.id,
+        edit_id: None,
+        comment_id: None,
+        reason: "spam".to_string(),
+    };
+    beta.create_report(&report_params).await.unwrap();
+    sleep(Duration::from_secs(1)).await;
+    let alpha_comment = alpha
+        .get_comments(GetArticleParams {
+            title: Some(article.article.title),
+            ..Default::default()
+        })
+        .await
+        .into_iter()
+        .find(|c| c.comment.content == "my comment")
+        .unwrap();
+    assert_eq!(0, alpha_comment.upvotes);
+    assert!(admin
+        .list_reports(&Default::default())
+        .await
+        .unwrap()
+        .items
+        .is_empty());
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_federation_list() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    let create_params = CreateArticleParams {
+        title: "Manu_Chao".to_string(),
+        text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+        summary: "create article".to_string(),
+        language: None,
+    };
+    let beta_article = beta.create_article(&create_params).await.unwrap();
+    alpha
+        .resolve_article(beta_article.article.ap_id.inner().clone())
+        .await
+        .unwrap();
+    let list_params = ListArticlesParams {
+        only_local: Some(false),
+        ..Default::default()
+    };
+    let has_beta_article = |articles: Vec<DbArticle>| {
+        articles
+            .iter()
+            .any(|a| a.ap_id == beta_article.article.ap_id)
+    };
+    assert!(has_beta_article(
+        alpha
+            .list_articles(list_params.clone())
+            .await
+            .unwrap()
+            .items
+    ));
+
+    let client = ClientBuilder::new().cookie_store(true).build()?;
+    let admin = ApiClient::new(client, Some(alpha.hostname.clone()));
+    let login_params = LoginUserParams {
+        username: "ibis".to_string(),
+        password: "ibis".to_string(),
+        totp_token: None,
+    };
+    admin.login(login_params).await.unwrap();
+
+    // regular user can't change the list, and the local domain can't be blocked
+    let mut block_params = UpdateFederationListParams {
+        domain: format!("http://{}/", beta.hostname.to_uppercase()),
+        blocked: true,
+        reason: Some("spam".to_string()),
+        purge: true,
+    };
+    assert!(alpha.update_federation_list(&block_params).await.is_err());
+    assert!(alpha.list_federation_list().await.is_none());
+    let local_params = UpdateFederationListParams {
+        domain: alpha.hostname.clone(),
+        ..block_params.clone()
+    };
+    assert!(admin.update_federation_list(&local_params).await.is_err());
+
+    // block beta and delete its articles, the domain is stored with port
+    let entry = admin.update_federation_list(&block_params).await.unwrap();
+    assert_eq!(beta.hostname, entry.domain);
+    assert!(entry.blocked);
+    assert_eq!(Some("spam".to_string()), entry.reason);
+    assert!(!has_beta_article(
+        alpha.list_articles(list_params).await.unwrap().items
+    ));
+    assert!(alpha
+        .resolve_article(beta_article.article.ap_id.inner().clone())
+        .await
+        .is_err());
+    let list = admin.list_federation_list().await.unwrap();
+    assert_eq!(1, list.len());
+    assert_eq!(beta.hostname, list[0].domain);
+
+    // allow only gamma, so beta remains blocked after removing it from the list
+    block_params.domain = gamma.hostname.clone();
+    block_params.blocked = false;
+    admin.update_federation_list(&block_params).await.unwrap();
+    let remove_params = RemoveFederationListParams {
+        domain: beta.hostname.clone(),
+    };
+    admin.remove_federation_list(&remove_params).await.unwrap();
+    assert!(alpha
+        .resolve_article(beta_article.article.ap_id.inner().clone())
+        .await
+        .is_err());
+
+    // federation works again after the list is empty
+    let remove_params = RemoveFederationListParams {
+        domain: gamma.hostname.clone(),
+    };
+    admin.remove_federation_list(&remove_params).await.unwrap();
+    assert!(admin.list_federation_list().await.unwrap().is_empty());
+    let modlog = alpha.list_modlog(&Default::default()).await.unwrap().items;
+    assert_eq!(4, modlog.len());
+    assert_eq!(
+        ModlogAction::RemoveFederationListEntry,
+        modlog[0].modlog.action
+    );
+    assert_eq!(gamma.hostname, modlog[0].modlog.target_name);
+    assert_eq!(ModlogAction::AllowInstance, modlog[2].modlog.action);
+    assert_eq!(ModlogAction::BlockInstance, modlog[3].modlog.action);
+    assert_eq!(Some("spam".to_string()), modlog[3].modlog.reason);
+    alpha
+        .resolve_article(beta_article.article.ap_id.inner().clone())
+        .await
+        .unwrap();
+
+    // config file is only imported on first startup, so removed entries don't come back
+    let mut config = IbisConfig::default();
+    config.federation.blocklist = Some(beta.hostname.clone());
+    DbFederationListEntry::import_config(&config, &alpha.db_pool()).unwrap();
+    assert!(admin.list_federation_list().await.unwrap().is_empty());
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_modlog() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    let create_params = CreateArticleParams {
+        title: "Manu_Chao".to_string(),
+        text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+        summary: "create article".to_string(),
+        language: None,
+    };
+    let article = alpha.create_article(&create_params).await.unwrap();
+
+    let client = ClientBuilder::new().cookie_store(true).build()?;
+    let admin = ApiClient::new(client, Some(alpha.hostname.clone()));
+    let login_params = LoginUserParams {
+        username: "ibis".to_string(),
+        password: "ibis".to_string(),
+        totp_token: None,
+    };
+    admin.login(login_params).await.unwrap();
+
+    // privileged actions are logged
+    let mut protect_params = ProtectArticleParams {
+        article_id: article.article.id,
+        protected: true,
+        reason: Some("vandalism".to_string()),
+    };
+    admin.protect_article(&protect_params).await.unwrap();
+    protect_params.protected = false;
+    protect_params.reason = None;
+    admin.protect_article(&protect_params).await.unwrap();
+    let update_params = UpdateInstanceParams {
+        name: Some("Example".to_string()),
+        topic: None,
+    };
+    admin.update_local_instance(&update_params).await.unwrap();
+
+    // modlog is public, newest entries first
+    let modlog = alpha.list_modlog(&Default::default()).await.unwrap().items;
+    assert_eq!(3, modlog.len());
+    assert_eq!(ModlogAction::UpdateInstance, modlog[0].modlog.action);
+    assert_eq!(alpha.hostname, modlog[0].modlog.target_name);
+    assert_eq!(ModlogAction::UnprotectArticle, modlog[1].modlog.action);
+    assert_eq!(ModlogAction::ProtectArticle, modlog[2].modlog.action);
+    assert_eq!(Some(article.article.id), modlog[2].modlog.target_article_id);
+    assert_eq!(Some("vandalism".to_string()), modlog[2].modlog.reason);
+    assert_eq!("ibis", modlog[2].actor.as_ref().unwrap().username);
+
+    // pagination
+    let mut page_params = GetModlogParams {
+        limit: Some(2),
+        cursor: None,
+    };
+    let page = alpha.list_modlog(&page_params).await.unwrap();
+    assert_eq!(2, page.items.len());
+    page_params.cursor = page.next_cursor;
+    let page = alpha.list_modlog(&page_params).await.unwrap();
+    assert_eq!(1, page.items.len());
+    assert_eq!(ModlogAction::ProtectArticle, page.items[0].modlog.action);
+    assert!(page.next_cursor.is_none());
+
+    // failed actions are not logged
+    protect_params.protected = true;
+    assert!(alpha.protect_article(&protect_params).await.is_err());
+    assert_eq!(
+        3,
+        alpha
+            .list_modlog(&Default::default())
+            .await
+            .unwrap()
+            .items
+            .len()
+    );
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_report() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    let create_params = CreateArticleParams {
+        title: "Manu_Chao".to_string(),
+        text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+        summary: "create article".to_string(),
+        language: None,
+    };
+    let article = alpha.create_article(&create_params).await.unwrap();
+    let beta_article = beta
+        .resolve_article(article.article.ap_id.inner().clone())
+        .await
+        .unwrap();
+
+    // report on beta is forwarded to alpha which hosts the article
+    let mut report_params = CreateReportParams {
+        article_id: beta_article.article.id,
+        edit_id: None,
+        comment_id: None,
+        reason: "spam".to_string(),
+    };
+    let beta_report = beta.create_report(&report_params).await.unwrap();
+    assert!(beta_report.local);
+    assert_eq!(ReportStatus::Open, beta_report.status);
+    sleep(Duration::from_secs(1)).await;
+
+    // report for a single edit on alpha
+    let edits = alpha.get_article_edits(article.article.id).await.unwrap();
+    report_params.article_id = article.article.id;
+    report_params.edit_id = Some(edits[0].edit.id);
+    report_params.reason = "copyright violation".to_string();
+    alpha.create_report(&report_params).await.unwrap();
+    report_params.reason = " ".to_string();
+    assert!(alpha.create_report(&report_params).await.is_err());
+    sleep(Duration::from_secs(1)).await;
+
+    // only moderators can see reports
+    assert!(alpha.list_reports(&Default::default()).await.is_none());
+    let client = ClientBuilder::new().cookie_store(true).build()?;
+    let admin = ApiClient::new(client, Some(alpha.hostname.clone()));
+    let login_params = LoginUserParams {
+        username: "ibis".to_string(),
+        password: "ibis".to_string(),
+        totp_token: None,
+    };
+    admin.login(login_params).await.unwrap();
+    let reports = admin.list_reports(&Default::default()).await.unwrap().items;
+    assert_eq!(2, reports.len());
+    assert_eq!("copyright violation", reports[0].report.reason);
+    assert_eq!(
+        Some(edits[0].edit.id),
+        reports[0].edit.as_ref().map(|e| e.id)
+    );
+    assert_eq!("spam", reports[1].report.reason);
+    assert!(!reports[1].report.local);
+    assert_eq!("beta", reports[1].creator.username);
+    assert_eq!(2, admin.notifications_count().await.unwrap());
+
+    // notifications are paginated with oldest first
+    let mut notif_params = ListNotificationsParams {
+        limit: Some(1),
+        cursor: None,
+    };
+    let page = admin.notifications_list(&notif_params).await.unwrap();
+    let Notification::Report(first) = &page.items[0] else {
+        panic!()
+    };
+    assert_eq!("spam", first.report.reason);
+    notif_params.cursor = page.next_cursor;
+    let page = admin.notifications_list(&notif_params).await.unwrap();
+    let Notification::Report(second) = &page.items[0] else {
+        panic!()
+    };
+    assert_eq!("copyright violation", second.report.reason);
+    assert!(page.next_cursor.is_none());
+
+    // resolve and dismiss
+    let resolve_params = ResolveReportParams {
+        report_id: reports[0].report.id,
+        dismiss: false,
+    };
+    assert!(alpha.resolve_report(&resolve_params).await.is_err());
+    let resolved = admin.resolve_report(&resolve_params).await.unwrap();
+    assert_eq!(ReportStatus::Resolved, resolved.status);
+    let dismiss_params = ResolveReportParams {
+        report_id: reports[1].report.id,
+        dismiss: true,
+    };
+    let dismissed = admin.resolve_report(&dismiss_params).await.unwrap();
+    assert_eq!(ReportStatus::Dismissed, dismissed.status);
+    assert!(admin
+        .list_reports(&Default::default())
+        .await
+        .unwrap()
+        .items
+        .is_empty());
+    let all_params = ListReportsParams {
+        include_closed: Some(true),
+        ..Default::default()
+    };
+    assert_eq!(
+        2,
+        admin.list_reports(&all_params).await.unwrap().items.len()
+    );
+    assert_eq!(0, admin.notifications_count().await.unwrap());
+
+    // listing reports requires the admin scope for api tokens
+    let client = reqwest::Client::new();
+    let endpoint = format!("http://{}/api/v1/report/list", alpha.hostname);
+    for (scope_admin, success) in [(false, false), (true, true)] {
+        let params = CreateApiTokenParams {
+            name: format!("admin {scope_admin}"),
+            scope_read: true,
+            scope_admin,
+            ..Default::default()
+        };
+        let token = admin.create_api_token(&params).await.unwrap().token;
+        let res = client.get(&endpoint).bearer_auth(&token).send().await?;
+        assert_eq!(success, res.status().is_success());
+    }
+    let modlog = alpha.list_modlog(&Default::default()).await.unwrap().items;
+    assert_eq!(ModlogAction::DismissReport, modlog[0].modlog.action);
+    assert_eq!(ModlogAction::ResolveReport, modlog[1].modlog.action);
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_pagination() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    for title in ["Manu_Chao", "Chico_Trujillo", "La_Pegatina"] {
+        let create_params = CreateArticleParams {
+            title: title.to_string(),
+            text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+            summary: "create article".to_string(),
+            language: None,
+        };
+        alpha.create_article(&create_params).await.unwrap();
+    }
+
+    // read all local articles (including main page) two at a time
+    let mut list_params = ListArticlesParams {
+        only_local: Some(true),
+        limit: Some(2),
+        ..Default::default()
+    };
+    let page = alpha.list_articles(list_params.clone()).await.unwrap();
+    assert_eq!(2, page.items.len());
+    assert_eq!("La_Pegatina", page.items[0].title);
+    list_params.cursor = page.next_cursor;
+    assert!(list_params.cursor.is_some());
+    let page2 = alpha.list_articles(list_params.clone()).await.unwrap();
+    assert_eq!(2, page2.items.len());
+    assert!(page2.next_cursor.is_none());
+    assert!(page2.items.iter().all(|a| !page.items.contains(a)));
+
+    // edits are listed newest first
+    let article = alpha
+        .get_article(GetArticleParams {
+            title: Some("Manu_Chao".to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let edit_params = EditArticleParams {
+        article_id: article.article.id,
+        new_text: "Lorem Ipsum 2\n".to_string(),
+        summary: "summary".to_string(),
+        previous_version_id: article.latest_version,
+        resolve_conflict_id: None,
+    };
+    alpha.edit_article(&edit_params).await.unwrap();
+    let mut edit_params = GetEditList {
+        article_id: Some(article.article.id),
+        limit: Some(1),
+        ..Default::default()
+    };
+    let page = alpha.list_edits(edit_params.clone()).await.unwrap();
+    assert_eq!(1, page.items.len());
+    assert_eq!("summary", page.items[0].edit.summary);
+    edit_params.cursor = page.next_cursor;
+    let page = alpha.list_edits(edit_params.clone()).await.unwrap();
+    assert_eq!("create article", page.items[0].edit.summary);
+    assert!(page.next_cursor.is_none());
+
+    // invalid cursor is rejected
+    edit_params.cursor = Some(PaginationCursor("invalid".to_string()));
+    assert!(alpha.list_edits(edit_params).await.is_none());
+    // client follows cursor to read all edits
+    let edits = alpha.get_article_edits(article.article.id).await.unwrap();
+    assert_eq!(2, edits.len());
+    assert_eq!("create article", edits[0].edit.summary);
+
+    let search_params = SearchArticleParams {
+        query: "example".to_string(),
+        limit: Some(1),
+        ..Default::default()
+    };
+    let search_res = alpha.search(&search_params).await.unwrap().articles;
+    assert_eq!(1, search_res.items.len());
+    assert!(search_res.next_cursor.is_some());
+
+    let instance_params = ListInstancesParams {
+        limit: Some(1),
+        ..Default::default()
+    };
+    let instances = alpha.list_instances(&instance_params).await.unwrap();
+    assert_eq!(1, instances.items.len());
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_search_full_text() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    let create_params = CreateArticleParams {
+        title: "Running".to_string(),
+        text: "A sport for people with good shoes\n".to_string(),
+        summary: "create article".to_string(),
+        language: Some("en".to_string()),
+    };
+    let running = alpha.create_article(&create_params).await.unwrap();
+    assert_eq!(Some("en".to_string()), running.article.language);
+    let create_params = CreateArticleParams {
+        title: "Cats".to_string(),
+        text: "The cats are running through the garden\n".to_string(),
+        summary: "create article".to_string(),
+        language: Some("en".to_string()),
+    };
+    let cats = alpha.create_article(&create_params).await.unwrap();
+    let create_params = CreateArticleParams {
+        title: "Invalid_Language".to_string(),
+        text: "some example text\n".to_string(),
+        summary: "create article".to_string(),
+        language: Some("xx".to_string()),
+    };
+    assert!(alpha.create_article(&create_params).await.is_err());
+
+    // words are stemmed, and title matches are ranked higher
+    let mut search_params = SearchArticleParams {
+        query: "runs".to_string(),
+        ..Default::default()
+    };
+    let search_res = alpha.search(&search_params).await.unwrap().articles.items;
+    assert_eq!(2, search_res.len());
+    assert_eq!(running.article.id, search_res[0].id);
+    assert_eq!(cats.article.id, search_res[1].id);
+    assert!(search_res[0].rank > search_res[1].rank);
+    assert!(search_res[1].snippet.contains("<b>running</b>"));
+
+    // web search syntax
+    search_params.query = "running -cats".to_string();
+    let search_res = alpha.search(&search_params).await.unwrap().articles.items;
+    assert_eq!(1, search_res.len());
+    assert_eq!(running.article.id, search_res[0].id);
+    search_params.query = "\"garden cats\"".to_string();
+    assert!(alpha
+        .search(&search_params)
+        .await
+        .unwrap()
+        .articles
+        .items
+        .is_empty());
+
+    // search is updated on edit
+    let edit_params = EditArticleParams {
+        article_id: cats.article.id,
+        new_text: "The cats are sleeping in the garden\n".to_string(),
+        summary: "summary".to_string(),
+        previous_version_id: cats.latest_version,
+        resolve_conflict_id: None,
+    };
+    alpha.edit_article(&edit_params).await.unwrap();
+    search_params.query = "running".to_string();
+    let search_res = alpha.search(&search_params).await.unwrap().articles.items;
+    assert_eq!(1, search_res.len());
+    search_params.query = "sleep".to_string();
+    let search_res = alpha.search(&search_params).await.unwrap().articles.items;
+    assert_eq!(cats.article.id, search_res[0].id);
+
+    // language is federated
+    let beta_article = beta
+        .resolve_article(running.article.ap_id.into_inner())
+        .await
+        .unwrap();
+    assert_eq!(Some("en".to_string()), beta_article.article.language);
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_federated_search() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    // beta knows alpha and its main page, but doesnt fetch articles which are created later
+    beta.resolve_instance(Url::parse(&format!("http://{}", &alpha.hostname))?)
+        .await
+        .unwrap();
+    sleep(Duration::from_secs(1)).await;
+    let create_params = CreateArticleParams {
+        title: "Federated_Search".to_string(),
+        text: "Searching across different instances\n".to_string(),
+        summary: "create article".to_string(),
+        language: None,
+    };
+    let alpha_article = alpha.create_article(&create_params).await.unwrap();
+
+    let mut search_params = SearchArticleParams {
+        query: "instances".to_string(),
+        ..Default::default()
+    };
+    assert!(beta
+        .search(&search_params)
+        .await
+        .unwrap()
+        .articles
+        .items
+        .is_empty());
+
+    // found with federated search, but needs to be resolved before opening it
+    search_params.federated = Some(true);
+    let client = ClientBuilder::new().build()?;
+    let anonymous = ApiClient::new(client, Some(beta.hostname.clone()));
+    assert!(anonymous.search(&search_params).await.is_err());
+    let search_res = beta.search(&search_params).await.unwrap();
+    assert!(search_res.articles.items.is_empty());
+    assert_eq!(1, search_res.remote.len());
+    assert_eq!(alpha_article.article.ap_id, search_res.remote[0].ap_id);
+    let alpha_instance = alpha.get_local_instance().await.unwrap().instance;
+    assert_eq!(alpha_instance.domain, search_res.remote[0].instance_domain);
+
+    // remote results are limited separately from the local page
+    let create_params = CreateArticleParams {
+        title: "Federated_Search_2".to_string(),
+        text: "More instances\n".to_string(),
+        ..create_params
+    };
+    alpha.create_article(&create_params).await.unwrap();
+    search_params.limit = Some(1);
+    let search_res = beta.search(&search_params).await.unwrap();
+    assert_eq!(1, search_res.remote.len());
+    search_params.limit = None;
+
+    beta.resolve_article(alpha_article.article.ap_id.inner().clone())
+        .await
+        .unwrap();
+
+    // now it is known locally and not duplicated
+    let search_res = beta.search(&search_params).await.unwrap();
+    assert_eq!(1, search_res.articles.items.len());
+    assert_eq!(
+        alpha_article.article.ap_id,
+        search_res.articles.items[0].ap_id
+    );
+    assert_eq!(1, search_res.remote.len());
+    assert_ne!(alpha_article.article.ap_id, search_res.remote[0].ap_id);
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_search_filters() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    let create_params = CreateArticleParams {
+        title: "Garden_Cats".to_string(),
+        text: "Cats living in the garden\n".to_string(),
+        summary: "create article".to_string(),
+        language: None,
+    };
+    let cats = alpha.create_article(&create_params).await.unwrap();
+    let username = alpha
+        .site()
+        .await
+        .unwrap()
+        .my_profile
+        .unwrap()
+        .person
+        .username;
+
+    // create a protected article as admin
+    let params = LoginUserParams {
+        username: "ibis".to_string(),
+        password: "ibis".to_string(),
+        totp_token: None,
+    };
+    alpha.login(params).await.unwrap();
+    let create_params = CreateArticleParams {
+        title: "Garden_Dogs".to_string(),
+        text: "Dogs living in the garden\n".to_string(),
+        summary: "create article".to_string(),
+        language: None,
+    };
+    let dogs = alpha.create_article(&create_params).await.unwrap();
+    let protect_params = ProtectArticleParams {
+        article_id: dogs.article.id,
+        protected: true,
+        reason: None,
+    };
+    alpha.protect_article(&protect_params).await.unwrap();
+
+    let mut search_params = SearchArticleParams {
+        query: "garden".to_string(),
+        ..Default::default()
+    };
+    assert_eq!(
+        2,
+        alpha
+            .search(&search_params)
+            .await
+            .unwrap()
+            .articles
+            .items
+            .len()
+    );
+
+    // filter by author
+    search_params.author = Some(username);
+    let search_res = alpha.search(&search_params).await.unwrap().articles.items;
+    assert_eq!(1, search_res.len());
+    assert_eq!(cats.article.id, search_res[0].id);
+    search_params.author = Some("ibis".to_string());
+    let search_res = alpha.search(&search_params).await.unwrap().articles.items;
+    assert_eq!(1, search_res.len());
+    assert_eq!(dogs.article.id, search_res[0].id);
+    search_params.author = Some("unknown_user".to_string());
+    assert!(alpha.search(&search_params).await.is_err());
+    search_params.author = None;
+
+    // filter by protected
+    search_params.protected = Some(true);
+    let search_res = alpha.search(&search_params).await.unwrap().articles.items;
+    assert_eq!(1, search_res.len());
+    assert_eq!(dogs.article.id, search_res[0].id);
+    search_params.protected = None;
+
+    // filter by edit date
+    search_params.edited_after = Some(chrono::Utc::now() + chrono::Duration::hours(1));
+    assert!(alpha
+        .search(&search_params)
+        .await
+        .unwrap()
+        .articles
+        .items
+        .is_empty());
+    search_params.edited_after = Some(chrono::Utc::now() - chrono::Duration::hours(1));
+    search_params.edited_before = Some(chrono::Utc::now() + chrono::Duration::hours(1));
+    assert_eq!(
+        2,
+        alpha
+            .search(&search_params)
+            .await
+            .unwrap()
+            .articles
+            .items
+            .len()
+    );
+
+    // only the last edit is considered
+    search_params.edited_after = None;
+    search_params.edited_before = Some(chrono::Utc::now());
+    let edit_params = EditArticleParams {
+        article_id: dogs.article.id,
+        new_text: "Dogs and puppies living in the garden\n".to_string(),
+        summary: "puppies".to_string(),
+        previous_version_id: dogs.latest_version,
+        resolve_conflict_id: None,
+    };
+    alpha.edit_article(&edit_params).await.unwrap();
+    let search_res = alpha.search(&search_params).await.unwrap().articles.items;
+    assert_eq!(1, search_res.len());
+    assert_eq!(cats.article.id, search_res[0].id);
+    search_params.edited_before = None;
+
+    // filter by local and instance on beta
+    let create_params = CreateArticleParams {
+        title: "Garden_Birds".to_string(),
+        text: "Birds living in the garden\n".to_string(),
+        summary: "create article".to_string(),
+        language: None,
+    };
+    let birds = beta.create_article(&create_params).await.unwrap();
+    let beta_cats = beta
+        .resolve_article(cats.article.ap_id.inner().clone())
+        .await
+        .unwrap();
+    // wait for the other articles of alpha, which are fetched in the background
+    sleep(Duration::from_secs(1)).await;
+    search_params.local = Some(true);
+    let search_res = beta.search(&search_params).await.unwrap().articles.items;
+    assert_eq!(1, search_res.len());
+    assert_eq!(birds.article.id, search_res[0].id);
+    search_params.local = Some(false);
+    let search_res = beta.search(&search_params).await.unwrap().articles.items;
+    assert_eq!(2, search_res.len());
+    assert!(search_res.iter().any(|a| a.id == beta_cats.article.id));
+    assert!(search_res.iter().all(|a| a.id != birds.article.id));
+    search_params.local = None;
+    search_params.instance_id = Some(birds.article.instance_id);
+    let search_res = beta.search(&search_params).await.unwrap().articles.items;
+    assert_eq!(1, search_res.len());
+    assert_eq!(birds.article.id, search_res[0].id);
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_search_suggest() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    for title in ["Manu_Chao", "Manuel_Valls", "Chico_Trujillo"] {
+        let create_params = CreateArticleParams {
+            title: title.to_string(),
+            text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+            summary: "create article".to_string(),
+            language: None,
+        };
+        alpha.create_article(&create_params).await.unwrap();
+    }
+
+    // prefix matches, with spaces instead of underscores
+    let mut params = SearchSuggestParams {
+        query: "manu c".to_string(),
+        ..Default::default()
+    };
+    let suggestions = alpha.search_suggest(&params).await.unwrap();
+    assert_eq!("Manu_Chao", suggestions[0].title);
+    params.query = "Manu".to_string();
+    let suggestions = alpha.search_suggest(&params).await.unwrap();
+    assert_eq!(2, suggestions.len());
+
+    // typo doesnt find anything in full text search, but gives a fuzzy suggestion
+    let search_params = SearchArticleParams {
+        query: "Chico Trujilo".to_string(),
+        ..Default::default()
+    };
+    assert!(alpha
+        .search(&search_params)
+        .await
+        .unwrap()
+        .articles
+        .items
+        .is_empty());
+    params.query = search_params.query;
+    let suggestions = alpha.search_suggest(&params).await.unwrap();
+    assert_eq!("Chico_Trujillo", suggestions[0].title);
+
+    params.query = "xyz".to_string();
+    assert!(alpha.search_suggest(&params).await.unwrap().is_empty());
+    params.query = " ".to_string();
+    assert!(alpha.search_suggest(&params).await.is_none());
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_recent_changes() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    // local article with two edits
+    let create_params = CreateArticleParams {
+        title: "Manu_Chao".to_string(),
+        text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+        summary: "create article".to_string(),
+        language: None,
+    };
+    let create_res = alpha.create_article(&create_params).await.unwrap();
+    let edit_params = EditArticleParams {
+        article_id: create_res.article.id,
+        new_text: "some text\n".to_string(),
+        summary: "shorten".to_string(),
+        previous_version_id: create_res.latest_version,
+        resolve_conflict_id: None,
+    };
+    alpha.edit_article(&edit_params).await.unwrap();
+
+    // remote article
+    let create_params = CreateArticleParams {
+        title: "Chico_Trujillo".to_string(),
+        ..create_params
+    };
+    let beta_article = beta.create_article(&create_params).await.unwrap();
+    let remote_article = alpha
+        .resolve_article(beta_article.article.ap_id.into_inner())
+        .await
+        .unwrap();
+    // wait for the main page of beta, which is fetched in the background
+    sleep(Duration::from_secs(1)).await;
+
+    // newest first, with size change. The oldest edits are the default main pages.
+    let changes = alpha
+        .recent_changes(&Default::default())
+        .await
+        .unwrap()
+        .items;
+    assert_eq!(5, changes.len());
+    assert_eq!("Chico_Trujillo", changes[0].article.title);
+    assert_eq!("shorten", changes[1].edit.summary);
+    assert_eq!(-8, changes[1].byte_delta);
+    assert_eq!("create article", changes[2].edit.summary);
+    assert_eq!(
+        TEST_ARTICLE_DEFAULT_TEXT.len() as i64,
+        changes[2].byte_delta
+    );
+
+    // filters
+    let mut params = RecentChangesParams {
+        local: Some(false),
+        ..Default::default()
+    };
+    let changes = alpha.recent_changes(&params).await.unwrap().items;
+    assert_eq!(2, changes.len());
+    assert_eq!("Chico_Trujillo", changes[0].article.title);
+    params.local = Some(true);
+    assert_eq!(3, alpha.recent_changes(&params).await.unwrap().items.len());
+    params.local = None;
+    params.pending = Some(true);
+    assert!(alpha
+        .recent_changes(&params)
+        .await
+        .unwrap()
+        .items
+        .is_empty());
+    params.pending = None;
+    params.hide_bots = Some(true);
+    assert_eq!(5, alpha.recent_changes(&params).await.unwrap().items.len());
+
+    // pagination
+    params.limit = Some(3);
+    let page = alpha.recent_changes(&params).await.unwrap();
+    assert_eq!(3, page.items.len());
+    params.cursor = page.next_cursor;
+    let page = alpha.recent_changes(&params).await.unwrap();
+    assert_eq!(2, page.items.len());
+    assert!(page.next_cursor.is_none());
+
+    // beta blocks alpha, so an edit of the remote article stays pending
+    let client = ClientBuilder::new().cookie_store(true).build()?;
+    let beta_admin = ApiClient::new(client, Some(beta.hostname.clone()));
+    let login_params = LoginUserParams {
+        username: "ibis".to_string(),
+        password: "ibis".to_string(),
+        totp_token: None,
+    };
+    beta_admin.login(login_params.clone()).await.unwrap();
+    let block_params = UpdateFederationListParams {
+        domain: alpha.hostname.clone(),
+        blocked: true,
+        reason: None,
+        purge: false,
+    };
+    beta_admin
+        .update_federation_list(&block_params)
+        .await
+        .unwrap();
+    let edit_params = EditArticleParams {
+        article_id: remote_article.article.id,
+        new_text: "pending text\n".to_string(),
+        summary: "pending edit".to_string(),
+        previous_version_id: remote_article.latest_version,
+        resolve_conflict_id: None,
+    };
+    alpha.edit_article(&edit_params).await.unwrap();
+
+    // pending edits are visible to the creator and to users who can approve articles
+    let params = RecentChangesParams {
+        pending: Some(true),
+        ..Default::default()
+    };
+    let changes = alpha.recent_changes(&params).await.unwrap().items;
+    assert_eq!(1, changes.len());
+    assert_eq!("pending edit", changes[0].edit.summary);
+    let client = ClientBuilder::new().cookie_store(true).build()?;
+    let anonymous = ApiClient::new(client, Some(alpha.hostname.clone()));
+    assert!(anonymous
+        .recent_changes(&params)
+        .await
+        .unwrap()
+        .items
+        .is_empty());
+    let client = ClientBuilder::new().cookie_store(true).build()?;
+    let alpha_admin = ApiClient::new(client, Some(alpha.hostname.clone()));
+    alpha_admin.login(login_params).await.unwrap();
+    let changes = alpha_admin.recent_changes(&params).await.unwrap().items;
+    assert_eq!(1, changes.len());
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_feeds() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    let create_params = CreateArticleParams {
+        title: "Manu_Chao".to_string(),
+        text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+        summary: "create <article>".to_string(),
+        language: None,
+    };
+    let create_res = alpha.create_article(&create_params).await.unwrap();
+    let edit_params = EditArticleParams {
+        article_id: create_res.article.id,
+        new_text: "some other text\n".to_string(),
+        summary: "change text".to_string(),
+        previous_version_id: create_res.latest_version,
+        resolve_conflict_id: None,
+    };
+    alpha.edit_article(&edit_params).await.unwrap();
+    let username = alpha
+        .site()
+        .await
+        .unwrap()
+        .my_profile
+        .unwrap()
+        .person
+        .username;
+
+    let hostname = alpha.hostname.clone();
+    let get_feed = |path: String| {
+        let url = format!("http://{hostname}{path}");
+        async move {
+            let res = reqwest::get(url).await.unwrap();
+            assert_eq!(StatusCode::OK, res.status());
+            let content_type = res.headers()["content-type"].to_str().unwrap().to_string();
+            (content_type, res.text().await.unwrap())
+        }
+    };
+
+    // atom by default, with summary escaped and diff excerpt
+    let (content_type, feed) = get_feed("/feed/recent_changes".to_string()).await;
+    assert!(content_type.starts_with("application/atom+xml"));
+    assert!(feed.contains("<feed xmlns=\"http://www.w3.org/2005/Atom\">"));
+    assert!(feed.contains("Manu Chao: change text"));
+    assert!(feed.contains("create &lt;article&gt;"));
+    assert!(feed.contains("+some other text"));
+
+    let (content_type, feed) = get_feed("/feed/recent_changes?format=rss".to_string()).await;
+    assert!(content_type.starts_with("application/rss+xml"));
+    assert!(feed.contains("<rss version=\"2.0\""));
+    assert_eq!(3, feed.matches("<item>").count());
+
+    let (_, feed) = get_feed("/feed/new_articles".to_string()).await;
+    assert_eq!(2, feed.matches("<entry>").count());
+    assert!(!feed.contains("change text"));
+
+    let (_, feed) = get_feed("/feed/article/Manu_Chao".to_string()).await;
+    assert_eq!(2, feed.matches("<entry>").count());
+    assert!(feed.contains("/article/Manu_Chao/diff/"));
+
+    let (_, feed) = get_feed(format!("/feed/user/{username}")).await;
+    assert_eq!(2, feed.matches("<entry>").count());
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_user_contributions() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    let create_params = CreateArticleParams {
+        title: "Manu_Chao".to_string(),
+        text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+        summary: "create article".to_string(),
+        language: None,
+    };
+    let create_res = alpha.create_article(&create_params).await.unwrap();
+    let edit_params = EditArticleParams {
+        article_id: create_res.article.id,
+        new_text: "some text\n".to_string(),
+        summary: "shorten".to_string(),
+        previous_version_id: create_res.latest_version,
+        resolve_conflict_id: None,
+    };
+    alpha.edit_article(&edit_params).await.unwrap();
+    for content in ["first comment", "second comment"] {
+        let params = CreateCommentParams {
+            content: content.to_string(),
+            article_id: create_res.article.id,
+            parent_id: None,
+        };
+        alpha.create_comment(&params).await.unwrap();
+    }
+    let person_id = alpha.site().await.unwrap().my_profile.unwrap().person.id;
+
+    // stats
+    let stats = alpha.user_stats(person_id).await.unwrap();
+    assert_eq!(2, stats.edit_count);
+    assert_eq!(
+        TEST_ARTICLE_DEFAULT_TEXT.len() as i64 + 10,
+        stats.bytes_added
+    );
+    assert_eq!(TEST_ARTICLE_DEFAULT_TEXT.len() as i64, stats.bytes_removed);
+    assert!(stats.first_edit < stats.last_edit);
+    assert_eq!(2, stats.comment_count);
+    assert_eq!(1, stats.article_count);
+    assert_eq!(1, stats.top_articles.len());
+    assert_eq!("Manu_Chao", stats.top_articles[0].article.title);
+    assert_eq!(2, stats.top_articles[0].edit_count);
+
+    // created articles
+    let mut params = UserContributionsParams {
+        person_id,
+        ..Default::default()
+    };
+    let articles = alpha.user_articles(&params).await.unwrap();
+    assert_eq!(1, articles.items.len());
+    assert_eq!(create_res.article.id, articles.items[0].id);
+
+    // comments, newest first and paginated
+    params.limit = Some(1);
+    let page = alpha.user_comments(&params).await.unwrap();
+    assert_eq!(1, page.items.len());
+    assert_eq!("second comment", page.items[0].comment.content);
+    assert_eq!("Manu_Chao", page.items[0].article.title);
+    params.cursor = page.next_cursor;
+    let page = alpha.user_comments(&params).await.unwrap();
+    assert_eq!(1, page.items.len());
+    assert_eq!("first comment", page.items[0].comment.content);
+    assert!(page.next_cursor.is_none());
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_instance_stats() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    alpha
+        .follow_instance_with_resolve(&beta.hostname)
+        .await
+        .unwrap();
+    let create_params = CreateArticleParams {
+        title: "Manu_Chao".to_string(),
+        text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+        summary: "create article".to_string(),
+        language: None,
+    };
+    let create_res = alpha.create_article(&create_params).await.unwrap();
+    let edit_params = EditArticleParams {
+        article_id: create_res.article.id,
+        new_text: "some text\n".to_string(),
+        summary: "shorten".to_string(),
+        previous_version_id: create_res.latest_version,
+        resolve_conflict_id: None,
+    };
+    alpha.edit_article(&edit_params).await.unwrap();
+    let comment_params = CreateCommentParams {
+        content: "comment".to_string(),
+        article_id: create_res.article.id,
+        parent_id: None,
+    };
+    alpha.create_comment(&comment_params).await.unwrap();
+
+    // only for admins
+    let params = GetInstanceStatsParams { days: Some(7) };
+    assert!(alpha.instance_stats(&params).await.is_none());
+    let login_params = LoginUserParams {
+        username: "ibis".to_string(),
+        password: "ibis".to_string(),
+        totp_token: None,
+    };
+    alpha.login(login_params).await.unwrap();
+
+    // oldest day first, days without activity are included
+    let stats = alpha.instance_stats(&params).await.unwrap();
+    assert_eq!(7, stats.len());
+    assert!(stats[0].day < stats[6].day);
+    assert_eq!(0, stats[0].edits);
+
+    // today, including the default main page created by admin
+    let today = &stats[6];
+    assert_eq!(3, today.edits);
+    assert_eq!(2, today.articles);
+    assert_eq!(2, today.new_users);
+    assert_eq!(1, today.comments);
+    assert_eq!(2, today.active_editors);
+    // follow and accept
+    assert_eq!(1, today.activities_sent);
+    assert_eq!(1, today.activities_received);
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_article_views() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    for title in ["Manu_Chao", "Chico_Trujillo", "Mano_Negra"] {
+        let create_params = CreateArticleParams {
+            title: title.to_string(),
+            text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+            summary: "create article".to_string(),
+            language: None,
+        };
+        alpha.create_article(&create_params).await.unwrap();
+    }
+
+    // views of logged in users are counted once per day
+    let view_params = |title: &str| GetArticleParams {
+        title: Some(title.to_string()),
+        domain: None,
+        id: None,
+        record_view: Some(true),
+    };
+    for title in ["Manu_Chao", "Manu_Chao", "Chico_Trujillo"] {
+        alpha.get_article(view_params(title)).await.unwrap();
+    }
+    let client = ClientBuilder::new().cookie_store(true).build()?;
+    let admin = ApiClient::new(client, Some(alpha.hostname.clone()));
+    let login_params = LoginUserParams {
+        username: "ibis".to_string(),
+        password: "ibis".to_string(),
+        totp_token: None,
+    };
+    admin.login(login_params).await.unwrap();
+    admin.get_article(view_params("Manu_Chao")).await.unwrap();
+
+    // anonymous views are limited per minute
+    let anonymous = reqwest::Client::new();
+    let view_url = |title: &str| {
+        format!(
+            "http://{}/api/v1/article?title={title}&record_view=true",
+            alpha.hostname
+        )
+    };
+    for (title, count) in [("Manu_Chao", 10), ("Chico_Trujillo", 30)] {
+        for _ in 0..count {
+            let res = anonymous.get(view_url(title)).send().await?;
+            assert_eq!(StatusCode::OK, res.status());
+        }
+    }
+    for _ in 0..3 {
+        alpha
+            .get_article(view_params("Chico_Trujillo"))
+            .await
+            .unwrap();
+    }
+
+    // reading the article for other purposes, eg editing, is not counted
+    let params = GetArticleParams {
+        title: Some("Mano_Negra".to_string()),
+        ..Default::default()
+    };
+    alpha.get_article(params).await.unwrap();
+
+    // views by crawlers are not counted
+    let client = ClientBuilder::new()
+        .user_agent("Mozilla/5.0 (compatible; Googlebot/2.1)")
+        .build()?;
+    for _ in 0..3 {
+        let res = client.get(view_url("Chico_Trujillo")).send().await?;
+        assert_eq!(StatusCode::OK, res.status());
+    }
+
+    // 12 views of Manu_Chao, and only 11 of Chico_Trujillo
+    let params = ListArticlesParams {
+        most_viewed: Some(true),
+        ..Default::default()
+    };
+    let articles = alpha.list_articles(params).await.unwrap().items;
+    let titles: Vec<_> = articles.iter().map(|a| a.title.as_str()).collect();
+    assert_eq!("Manu_Chao", titles[0]);
+    assert_eq!("Chico_Trujillo", titles[1]);
+    assert!(!titles.contains(&"Mano_Negra"));
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+#[tokio::test]
+async fn test_openapi() -> Result<()> {
+    let TestData(alpha, beta, gamma) = TestData::start(false).await;
+
+    let url = format!("http://{}/api/v1/openapi.json", alpha.hostname);
+    let res = reqwest::get(&url).await?;
+    assert_eq!(StatusCode::OK, res.status());
+    let spec: serde_json::Value = res.json().await?;
+    assert_eq!("3.0.3", spec["openapi"]);
+
+    // routes and documentation are generated from the same list, check that every documented
+    // endpoint is actually registered
+    let client = reqwest::Client::new();
+    let endpoint = |path: &str| format!("http://{}/api/v1{path}", alpha.hostname);
+    let res = client.get(endpoint("/missing")).send().await?;
+    assert_eq!(StatusCode::NOT_FOUND, res.status());
+    let mut count = 0;
+    for (path, operations) in spec["paths"].as_object().unwrap() {
+        for method in operations.as_object().unwrap().keys() {
+            let method = Method::from_bytes(method.to_uppercase().as_bytes())?;
+            let res = client
+                .request(method.clone(), endpoint(path))
+                .send()
+                .await?;
+            assert!(
+                ![StatusCode::NOT_FOUND, StatusCode::METHOD_NOT_ALLOWED].contains(&res.status()),
+                "{method} {path} is not registered"
+            );
+            count += 1;
+        }
+    }
+    assert!(count > 50);
+
+    // check some generated schemas
+    let article = &spec["components"]["schemas"]["DbArticle"];
+    assert_eq!("date-time", article["properties"]["published"]["format"]);
+    assert_eq!("uri", article["properties"]["ap_id"]["format"]);
+    let params = spec["paths"]["/article"]["get"]["parameters"]
+        .as_array()
+        .unwrap();
+    assert!(params
+        .iter()
+        .any(|p| p["name"] == "title" && p["in"] == "query" && p["required"] == false));
+    let create = &spec["paths"]["/article"]["post"]["requestBody"]["content"];
+    assert!(create["application/x-www-form-urlencoded"]["schema"].is_object());
+
+    // real responses match the generated schemas
+    let article = alpha
+        .create_article(&CreateArticleParams {
+            title: "Manu_Chao".to_string(),
+            text: TEST_ARTICLE_DEFAULT_TEXT.to_string(),
+            summary: "create article".to_string(),
+            language: None,
+        })
+        .await
+        .unwrap();
+    for (path, query) in [
+        ("/article", format!("?title={}", article.article.title)),
+        ("/article/list", String::new()),
+        (
+            "/edit/list",
+            format!("?article_id={}", article.article.id.0),
+        ),
+        ("/instance", String::new()),
+        ("/site", String::new()),
+    ] {
+        let res: serde_json::Value = client
+            .get(endpoint(&format!("{path}{query}")))
+            .send()
+            .await?
+            .json()
+            .await?;
+        validate_response(&spec, path, &res);
+    }
+
+    TestData::stop(alpha, beta, gamma)
+}
+
+/// Validates the response of a `GET` endpoint against the schema in the OpenAPI document. Optional
+/// values are marked with `nullable` in OpenAPI 3.0, which is converted to standard JSON schema.
+fn validate_response(spec: &serde_json::Value, path: &str, response: &serde_json::Value) {
+    fn convert_nullable(schema: &mut serde_json::Value) {
+        match schema {
+            serde_json::Value::Object(map) => {
+                map.values_mut().for_each(convert_nullable);
+                if map.remove("nullable") == Some(true.into()) {
+                    *schema = serde_json::json!({ "anyOf": [schema, { "type": "null" }] });
+                }
+            }
+            serde_json::Value::Array(list) => list.iter_mut().for_each(convert_nullable),
+            _ => {}
+        }
+    }
+    let content = &spec["paths"][path]["get"]["responses"]["200"]["content"];
+    let mut schema = content["application/json"]["schema"].clone();
+    assert!(schema.is_object(), "{path} has no response schema");
+    schema["components"] = spec["components"].clone();
+    convert_nullable(&mut schema);
+    let validator = jsonschema::validator_for(&schema).unwrap();
+    if let Err(e) = validator.validate(response) {
+        panic!("response of {path} doesn't match schema: {e}\n{response}");
+    }
+}